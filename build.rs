@@ -0,0 +1,100 @@
+//! Embeds the frontend: generates the table of served files in `$OUT_DIR/frontend_files.rs`,
+//! together with gzip and brotli compressed variants of each file.
+
+#[allow(dead_code)]
+#[path = "src/frontend/asset.rs"]
+mod asset;
+
+use std::{
+    collections::HashSet,
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use flate2::{Compression, write::GzEncoder};
+
+const FRONTEND_DIR: &str = "frontend";
+
+fn main() {
+    println!("cargo::rerun-if-changed={FRONTEND_DIR}");
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("cargo sets OUT_DIR"));
+    let compressed_dir = out_dir.join("frontend");
+    fs::create_dir_all(&compressed_dir).expect("creating the directory for compressed files");
+
+    let assets = asset::assets(Path::new(FRONTEND_DIR)).expect("reading the frontend directory");
+    if !assets.iter().any(|asset| asset.kind == asset::FileKind::Js) {
+        println!(
+            "cargo::warning=No compiled JavaScript in {FRONTEND_DIR}/out, run `tsc` in {FRONTEND_DIR} first"
+        );
+    }
+
+    let mut paths = HashSet::new();
+    let mut file_data = String::from("const FILE_DATA: &[FrontEndFile] = &[\n");
+
+    for (i, asset) in assets.iter().enumerate() {
+        assert!(
+            paths.insert(&asset.path),
+            "{} is served at {}, like another file",
+            asset.name,
+            asset.path
+        );
+
+        let content = fs::read(&asset.file_path).expect("reading a frontend file");
+        let file_path = fs::canonicalize(&asset.file_path).expect("resolving a frontend file");
+
+        let gzip = compressed_variant(
+            &content,
+            gzip(&content),
+            &compressed_dir.join(format!("{i}.gz")),
+        );
+        let brotli = compressed_variant(
+            &content,
+            brotli(&content),
+            &compressed_dir.join(format!("{i}.br")),
+        );
+
+        file_data.push_str(&format!(
+            "    FrontEndFile {{\n        kind: FileKind::{:?},\n        name: {:?},\n        path: {:?},\n        content: include_bytes!({:?}),\n        gzip: {gzip},\n        brotli: {brotli},\n        content_hash: {:?},\n    }},\n",
+            asset.kind,
+            asset.name,
+            asset.path,
+            file_path,
+            asset::content_hash(&content),
+        ));
+    }
+
+    file_data.push_str("];\n");
+    fs::write(out_dir.join("frontend_files.rs"), file_data).expect("writing the file table");
+}
+
+/// Writes `compressed` to `file_path` if it is smaller than `content` and answers the
+/// expression embedding it.
+fn compressed_variant(content: &[u8], compressed: Vec<u8>, file_path: &Path) -> String {
+    if compressed.len() >= content.len() {
+        return String::from("None");
+    }
+
+    fs::write(file_path, compressed).expect("writing a compressed frontend file");
+    format!("Some(include_bytes!({file_path:?}))")
+}
+
+fn gzip(content: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(content).expect("gzip to memory");
+    encoder.finish().expect("gzip to memory")
+}
+
+fn brotli(content: &[u8]) -> Vec<u8> {
+    const QUALITY: u32 = 11;
+    const WINDOW_SIZE: u32 = 22;
+
+    let mut compressed = Vec::new();
+    {
+        let mut encoder =
+            brotli::CompressorWriter::new(&mut compressed, 4096, QUALITY, WINDOW_SIZE);
+        encoder.write_all(content).expect("brotli to memory");
+    }
+    compressed
+}
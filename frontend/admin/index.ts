@@ -1,3 +1,16 @@
+import {
+    type Locale,
+    type MessageKey,
+    locale,
+    localeName,
+    localizePage,
+    t,
+    translatedName,
+    translationLocales,
+} from '../i18n.js'
+
+localizePage()
+
 const isLoggedIn = document.cookie
     .split(';')
     .some((c) => c.trim().startsWith('coco_admin_token'))
@@ -6,6 +19,418 @@ if (!isLoggedIn) {
     window.location.href = '/admin/login'
 }
 
+interface EventSummary {
+    slug: string
+    name: string
+    isDefault: boolean
+}
+
+type AdminRole = 'superAdmin' | 'electionOfficer' | 'registrationDesk' | 'observer'
+
+interface AdminAccount {
+    id: number
+    username: string
+    role: AdminRole
+}
+
+const adminRoleKeys: Record<AdminRole, MessageKey> = {
+    superAdmin: 'roleSuperAdmin',
+    electionOfficer: 'roleElectionOfficer',
+    registrationDesk: 'roleRegistrationDesk',
+    observer: 'roleObserver',
+}
+
+const ownAdminAccount = await fetchOwnAdminAccount()
+const isSuperAdmin = ownAdminAccount?.role === 'superAdmin'
+
+await renderEvent()
+
+if (isSuperAdmin) {
+    let adminAccountsSection = document.getElementById(
+        'admin-accounts-section'
+    )
+    if (adminAccountsSection instanceof HTMLElement) {
+        adminAccountsSection.hidden = false
+    }
+    await renderAdminAccounts()
+}
+
+async function fetchOwnAdminAccount(): Promise<AdminAccount | null> {
+    const response = await fetch('/api/v1/admin/accounts/me')
+    if (response.status === 401) {
+        window.location.href = '/admin/login'
+        return null
+    } else if (!response.ok) {
+        return null
+    }
+    const adminAccount: AdminAccount = await response.json()
+
+    let adminAccountElement = document.getElementById('admin-account')
+    if (adminAccountElement instanceof HTMLParagraphElement) {
+        adminAccountElement.textContent = t('loggedInAs', {
+            username: adminAccount.username,
+            role: t(adminRoleKeys[adminAccount.role]),
+        })
+    }
+
+    return adminAccount
+}
+
+async function renderAdminAccounts() {
+    const response = await fetch('/api/v1/admin/accounts')
+    if (!response.ok) {
+        return
+    }
+    const adminAccounts: AdminAccount[] = await response.json()
+
+    let adminAccountsList = document.getElementById('admin-accounts')
+    if (!(adminAccountsList instanceof HTMLUListElement)) {
+        return
+    }
+
+    adminAccountsList.replaceChildren(
+        ...adminAccounts.map((adminAccount) => {
+            let listItem = document.createElement('li')
+            listItem.textContent = `${adminAccount.username} (${t(adminRoleKeys[adminAccount.role])}) `
+
+            let deleteButton = document.createElement('button')
+            deleteButton.textContent = t('delete')
+            deleteButton.addEventListener('click', async () => {
+                const response = await fetch(
+                    `/api/v1/admin/accounts/${adminAccount.id}`,
+                    { method: 'DELETE' }
+                )
+
+                let message = document.getElementById(
+                    'create-admin-account-form-message'
+                )
+                if (response.ok) {
+                    await renderAdminAccounts()
+                } else if (message instanceof HTMLParagraphElement) {
+                    const apiError: ApiError = await response.json()
+                    message.textContent = t('error', { message: apiError.message })
+                }
+            })
+            listItem.appendChild(deleteButton)
+
+            return listItem
+        })
+    )
+}
+
+let createAdminAccountForm = document.getElementById(
+    'create-admin-account-form'
+)
+
+createAdminAccountForm?.addEventListener('submit', async (event) => {
+    event.preventDefault()
+
+    if (createAdminAccountForm instanceof HTMLFormElement) {
+        const formData = new FormData(createAdminAccountForm)
+
+        const response = await fetch('/api/v1/admin/accounts', {
+            method: 'POST',
+            headers: {
+                'Content-Type': 'application/json',
+            },
+            body: JSON.stringify(Object.fromEntries(formData)),
+        })
+
+        let message = document.getElementById(
+            'create-admin-account-form-message'
+        )
+
+        if (response.ok) {
+            createAdminAccountForm.reset()
+            if (message instanceof HTMLParagraphElement) {
+                message.textContent = t('createdAdminAccount')
+            }
+            await renderAdminAccounts()
+        } else if (response.status === 401) {
+            window.location.href = '/admin/login'
+        } else if (message instanceof HTMLParagraphElement) {
+            const apiError: ApiError = await response.json()
+            message.textContent = t('error', { message: apiError.message })
+        }
+    }
+})
+
+async function renderEvent() {
+    const response = await fetch('/api/v1/event')
+    if (!response.ok) {
+        return
+    }
+    const event: EventSummary = await response.json()
+
+    let eventNameElement = document.getElementById('event-name')
+    if (eventNameElement instanceof HTMLParagraphElement) {
+        eventNameElement.textContent = t('event', { name: event.name })
+    }
+
+    let eventsSection = document.getElementById('events-section')
+    if (event.isDefault && isSuperAdmin && eventsSection instanceof HTMLElement) {
+        eventsSection.hidden = false
+        await renderEvents()
+    }
+}
+
+async function renderEvents() {
+    const response = await fetch('/api/v1/admin/events')
+    if (!response.ok) {
+        return
+    }
+    const events: EventSummary[] = await response.json()
+
+    let eventsList = document.getElementById('events')
+    if (!(eventsList instanceof HTMLUListElement)) {
+        return
+    }
+
+    eventsList.replaceChildren(
+        ...events
+            .filter((event) => !event.isDefault)
+            .map((event) => {
+                let eventLink = document.createElement('a')
+                eventLink.href = `${window.location.protocol}//${event.slug}.${window.location.host}/admin/login`
+                eventLink.textContent = event.name
+
+                let listItem = document.createElement('li')
+                listItem.appendChild(eventLink)
+                return listItem
+            })
+    )
+}
+
+interface AttendanceSettings {
+    requireCheckInToVote: boolean
+    quorum: number | null
+}
+
+interface AttendanceSummary {
+    numRegistered: number
+    numPresent: number
+    numEligible: number
+    settings: AttendanceSettings
+}
+
+interface ParticipantSummary {
+    id: number
+    votedElectionIds: number[]
+    checkedInSince: string | null
+    groups: string[]
+}
+
+function parseGroups(groupsValue: string | undefined): string[] {
+    return (groupsValue ?? '')
+        .split(',')
+        .map((group) => group.trim())
+        .filter((group) => group.length > 0)
+}
+
+await renderAttendance()
+
+async function renderAttendance() {
+    const summaryResponse = await fetch('/api/v1/attendance')
+    if (summaryResponse.ok) {
+        const attendanceSummary: AttendanceSummary =
+            await summaryResponse.json()
+
+        let summaryElement = document.getElementById('attendance-summary')
+        if (summaryElement instanceof HTMLParagraphElement) {
+            summaryElement.textContent = t('attendanceSummary', {
+                present: attendanceSummary.numPresent,
+                registered: attendanceSummary.numRegistered,
+            })
+        }
+
+        let requireCheckInCheckbox = document.getElementById(
+            'require-check-in-to-vote'
+        )
+        if (requireCheckInCheckbox instanceof HTMLInputElement) {
+            requireCheckInCheckbox.checked =
+                attendanceSummary.settings.requireCheckInToVote
+        }
+
+        let quorumInput = document.getElementById('attendance-quorum')
+        if (quorumInput instanceof HTMLInputElement) {
+            quorumInput.value =
+                attendanceSummary.settings.quorum?.toString() ?? ''
+        }
+    }
+
+    // Only admins allowed to manage participants get the list.
+    const participantsResponse = await fetch('/api/v1/admin/participants')
+    if (!participantsResponse.ok) {
+        return
+    }
+    const participants: ParticipantSummary[] =
+        await participantsResponse.json()
+
+    let participantsList = document.getElementById('participants')
+    if (!(participantsList instanceof HTMLUListElement)) {
+        return
+    }
+
+    participantsList.replaceChildren(
+        ...participants.map((participant) => {
+            let listItem = document.createElement('li')
+            listItem.textContent =
+                (participant.checkedInSince === null
+                    ? t('participantNotPresent', { id: participant.id })
+                    : t('participantPresentSince', {
+                          id: participant.id,
+                          time: new Date(
+                              participant.checkedInSince
+                          ).toLocaleTimeString(locale),
+                      })) + ' '
+
+            const action =
+                participant.checkedInSince === null ? 'check-in' : 'check-out'
+
+            let attendanceButton = document.createElement('button')
+            attendanceButton.textContent =
+                participant.checkedInSince === null ? t('checkIn') : t('checkOut')
+            attendanceButton.addEventListener('click', async () => {
+                const response = await fetch(
+                    `/api/v1/admin/participants/${participant.id}/${action}`,
+                    { method: 'POST' }
+                )
+                await showRegistrationDeskResult(response)
+            })
+            listItem.appendChild(attendanceButton)
+
+            let groupsInput = document.createElement('input')
+            groupsInput.type = 'text'
+            groupsInput.value = participant.groups.join(', ')
+            groupsInput.placeholder = t('groups')
+            listItem.appendChild(groupsInput)
+
+            let groupsButton = document.createElement('button')
+            groupsButton.textContent = t('saveGroups')
+            groupsButton.addEventListener('click', async () => {
+                const response = await fetch(
+                    `/api/v1/admin/participants/${participant.id}/groups`,
+                    {
+                        method: 'PUT',
+                        headers: {
+                            'Content-Type': 'application/json',
+                        },
+                        body: JSON.stringify({
+                            groups: parseGroups(groupsInput.value),
+                        }),
+                    }
+                )
+                await showRegistrationDeskResult(response)
+            })
+            listItem.appendChild(groupsButton)
+
+            return listItem
+        })
+    )
+}
+
+async function showRegistrationDeskResult(response: Response) {
+    let message = document.getElementById('registration-desk-message')
+
+    if (response.ok) {
+        if (message instanceof HTMLParagraphElement) {
+            message.textContent = ''
+        }
+        await renderAttendance()
+    } else if (response.status === 401) {
+        window.location.href = '/admin/login'
+    } else if (message instanceof HTMLParagraphElement) {
+        const apiError: ApiError = await response.json()
+        message.textContent = t('error', { message: apiError.message })
+    }
+}
+
+let requireCheckInCheckbox = document.getElementById('require-check-in-to-vote')
+let quorumInput = document.getElementById('attendance-quorum')
+
+// The settings are replaced as a whole, so every change sends all of them.
+async function saveAttendanceSettings() {
+    if (
+        requireCheckInCheckbox instanceof HTMLInputElement &&
+        quorumInput instanceof HTMLInputElement
+    ) {
+        const settings: AttendanceSettings = {
+            requireCheckInToVote: requireCheckInCheckbox.checked,
+            quorum: quorumInput.value === '' ? null : Number(quorumInput.value),
+        }
+        const response = await fetch('/api/v1/admin/attendance/settings', {
+            method: 'PUT',
+            headers: {
+                'Content-Type': 'application/json',
+            },
+            body: JSON.stringify(settings),
+        })
+        await showRegistrationDeskResult(response)
+    }
+}
+
+requireCheckInCheckbox?.addEventListener('change', saveAttendanceSettings)
+quorumInput?.addEventListener('change', saveAttendanceSettings)
+
+let createEventForm = document.getElementById('create-event-form')
+
+createEventForm?.addEventListener('submit', async (event) => {
+    event.preventDefault()
+
+    if (createEventForm instanceof HTMLFormElement) {
+        const formData = new FormData(createEventForm)
+
+        const response = await fetch('/api/v1/admin/events', {
+            method: 'POST',
+            headers: {
+                'Content-Type': 'application/json',
+            },
+            body: JSON.stringify(Object.fromEntries(formData)),
+        })
+
+        let message = document.getElementById('create-event-form-message')
+
+        if (response.ok) {
+            createEventForm.reset()
+            if (message instanceof HTMLParagraphElement) {
+                message.textContent = t('createdEvent')
+            }
+            await renderEvents()
+        } else if (response.status === 401) {
+            window.location.href = '/admin/login'
+        } else if (message instanceof HTMLParagraphElement) {
+            const apiError: ApiError = await response.json()
+            message.textContent = t('error', { message: apiError.message })
+        }
+    }
+})
+
+let electionNameTranslationsElement = document.getElementById(
+    'election-name-translations'
+)
+
+for (const translationLocale of translationLocales) {
+    const inputId = `election-name-${translationLocale}`
+
+    let label = document.createElement('label')
+    label.htmlFor = inputId
+    label.textContent =
+        t('electionNameTranslationLabel', {
+            language: localeName(translationLocale),
+        }) + ' '
+
+    let input = document.createElement('input')
+    input.id = inputId
+    input.name = `name-${translationLocale}`
+    input.type = 'text'
+
+    electionNameTranslationsElement?.append(
+        label,
+        input,
+        document.createElement('br')
+    )
+}
+
 let addBallotItemButton = document.getElementById('add-ballot-item')
 let ballotItemsListElement = document.getElementById('ballot-items')
 let ballotItemId = 0
@@ -19,22 +444,136 @@ addBallotItemButton?.addEventListener('click', (event) => {
     let ballotItemInput = document.createElement('input')
     ballotItemInput.type = 'text'
     ballotItemInput.name = `ballot-item-${ballotItemId.toString()}`
+    ballotItemInput.placeholder = t('ballotItemName')
+
+    let affiliationInput = document.createElement('input')
+    affiliationInput.type = 'text'
+    affiliationInput.name = `affiliation-${ballotItemId.toString()}`
+    affiliationInput.placeholder = t('affiliation')
+
+    let descriptionInput = document.createElement('textarea')
+    descriptionInput.name = `description-${ballotItemId.toString()}`
+    descriptionInput.placeholder = t('ballotItemDescription')
+    descriptionInput.rows = 3
+    descriptionInput.cols = 60
+
+    let attachmentsInput = document.createElement('input')
+    attachmentsInput.type = 'file'
+    attachmentsInput.name = `attachments-${ballotItemId.toString()}`
+    attachmentsInput.multiple = true
+    attachmentsInput.accept = 'image/png,image/jpeg,image/gif,image/webp,application/pdf'
 
     let deleteBallotItemButton = document.createElement('button')
-    deleteBallotItemButton.innerText = 'Delete'
+    deleteBallotItemButton.innerText = t('delete')
     deleteBallotItemButton.addEventListener('click', (event) => {
         event.preventDefault()
         listItem.remove()
     })
 
     listItem.appendChild(ballotItemInput)
+    listItem.appendChild(affiliationInput)
     listItem.appendChild(deleteBallotItemButton)
+    listItem.appendChild(document.createElement('br'))
+    listItem.appendChild(descriptionInput)
+    listItem.appendChild(document.createElement('br'))
+
+    for (const translationLocale of translationLocales) {
+        const language = localeName(translationLocale)
+
+        let translatedNameInput = document.createElement('input')
+        translatedNameInput.type = 'text'
+        translatedNameInput.name = `translated-name-${translationLocale}-${ballotItemId.toString()}`
+        translatedNameInput.placeholder = t('ballotItemNameTranslation', { language })
+
+        let translatedDescriptionInput = document.createElement('textarea')
+        translatedDescriptionInput.name = `translated-description-${translationLocale}-${ballotItemId.toString()}`
+        translatedDescriptionInput.placeholder = t(
+            'ballotItemDescriptionTranslation',
+            { language }
+        )
+        translatedDescriptionInput.rows = 3
+        translatedDescriptionInput.cols = 60
+
+        listItem.appendChild(translatedNameInput)
+        listItem.appendChild(document.createElement('br'))
+        listItem.appendChild(translatedDescriptionInput)
+        listItem.appendChild(document.createElement('br'))
+    }
+
+    listItem.appendChild(attachmentsInput)
 
     ballotItemInput.select()
 
     ballotItemId += 1
 })
 
+interface BallotItemBody {
+    name: string
+    affiliation: string
+    description: string
+    attachmentIds: number[]
+    translations: Partial<Record<Locale, { name: string; description: string }>>
+}
+
+// Only the languages something was entered for, the others fall back to the default language.
+function translationsOf<T>(
+    translationOf: (translationLocale: Locale) => T | null
+): Partial<Record<Locale, T>> {
+    let translations: Partial<Record<Locale, T>> = {}
+    for (const translationLocale of translationLocales) {
+        const translation = translationOf(translationLocale)
+        if (translation !== null) {
+            translations[translationLocale] = translation
+        }
+    }
+    return translations
+}
+
+interface Attachment {
+    id: number
+}
+
+// Returns `null` if an upload failed, after showing the error.
+async function uploadAttachments(
+    files: FormDataEntryValue[]
+): Promise<number[] | null> {
+    let attachmentIds: number[] = []
+
+    for (const file of files) {
+        // Browsers submit an empty file if none was selected.
+        if (!(file instanceof File) || file.size === 0) {
+            continue
+        }
+
+        const response = await fetch('/api/v1/admin/attachments', {
+            method: 'POST',
+            headers: {
+                'Content-Type': file.type,
+            },
+            body: file,
+        })
+
+        if (!response.ok) {
+            let message = document.getElementById(
+                'create-election-form-message'
+            )
+            if (message instanceof HTMLParagraphElement) {
+                const apiError: ApiError = await response.json()
+                message.textContent = t('uploadError', {
+                    file: file.name,
+                    message: apiError.message,
+                })
+            }
+            return null
+        }
+
+        const attachment: Attachment = await response.json()
+        attachmentIds.push(attachment.id)
+    }
+
+    return attachmentIds
+}
+
 let addElectionForm = document.getElementById('create-election-form')
 
 addElectionForm?.addEventListener('submit', async (event) => {
@@ -43,9 +582,68 @@ addElectionForm?.addEventListener('submit', async (event) => {
     if (addElectionForm instanceof HTMLFormElement) {
         const createElectionData = new FormData(addElectionForm)
 
+        const methodValue = createElectionData.get('method')?.toString()
+        const durationValue = createElectionData.get('duration')?.toString()
+        const agendaItemValue = createElectionData
+            .get('agendaItemId')
+            ?.toString()
+        const eligibleGroups = parseGroups(
+            createElectionData.get('eligibleGroups')?.toString()
+        )
+        const motionKind = createElectionData.get('motionKind')?.toString()
+
         let createElectionDataObject = {
             name: createElectionData.get('name')?.toString(),
-            ballotItems: [] as string[],
+            translations: translationsOf((translationLocale) => {
+                const name =
+                    createElectionData.get(`name-${translationLocale}`)?.toString() ?? ''
+                return name.length > 0 ? { name } : null
+            }),
+            ballotItems: [] as BallotItemBody[],
+            method:
+                methodValue === 'plurality'
+                    ? { kind: 'plurality' }
+                    : { kind: 'majority', threshold: methodValue },
+            durationSeconds:
+                durationValue === undefined || durationValue.length === 0
+                    ? null
+                    : Number(durationValue),
+            agendaItemId:
+                agendaItemValue === undefined || agendaItemValue.length === 0
+                    ? null
+                    : Number(agendaItemValue),
+            nominations:
+                createElectionData.get('nominations') === null
+                    ? null
+                    : {
+                          requiredSeconders: Number(
+                              createElectionData.get('requiredSeconders')
+                          ),
+                      },
+            eligibility:
+                eligibleGroups.length === 0
+                    ? { kind: 'everyone' }
+                    : { kind: 'groups', groups: eligibleGroups },
+            motion:
+                motionKind === 'main'
+                    ? {
+                          kind: 'main',
+                          text: createElectionData.get('motionText')?.toString(),
+                      }
+                    : motionKind === 'amendment'
+                      ? {
+                            kind: 'amendment',
+                            parentElectionId: Number(
+                                createElectionData.get('parentElectionId')
+                            ),
+                            originalText: createElectionData
+                                .get('originalText')
+                                ?.toString(),
+                            replacementText: createElectionData
+                                .get('replacementText')
+                                ?.toString(),
+                        }
+                      : null,
         }
 
         if (createElectionDataObject.name?.length === 0) {
@@ -53,7 +651,7 @@ addElectionForm?.addEventListener('submit', async (event) => {
                 'create-election-form-message'
             )
             if (message instanceof HTMLParagraphElement) {
-                message.textContent = 'Error: Election title cannot be empty.'
+                message.textContent = t('emptyElectionName')
                 return
             }
         }
@@ -68,17 +666,48 @@ addElectionForm?.addEventListener('submit', async (event) => {
                     )
 
                     if (message instanceof HTMLParagraphElement) {
-                        message.textContent =
-                            'Error: Ballot item name cannot be empty.'
+                        message.textContent = t('emptyBallotItemName')
                         return
                     }
                 }
 
-                createElectionDataObject.ballotItems.push(valueString)
+                const index = key.slice('ballot-item-'.length)
+                const attachmentIds = await uploadAttachments(
+                    createElectionData.getAll(`attachments-${index}`)
+                )
+                if (attachmentIds === null) {
+                    return
+                }
+
+                createElectionDataObject.ballotItems.push({
+                    name: valueString,
+                    affiliation:
+                        createElectionData
+                            .get(`affiliation-${index}`)
+                            ?.toString() ?? '',
+                    description:
+                        createElectionData
+                            .get(`description-${index}`)
+                            ?.toString() ?? '',
+                    attachmentIds,
+                    translations: translationsOf((translationLocale) => {
+                        const name =
+                            createElectionData
+                                .get(`translated-name-${translationLocale}-${index}`)
+                                ?.toString() ?? ''
+                        const description =
+                            createElectionData
+                                .get(
+                                    `translated-description-${translationLocale}-${index}`
+                                )
+                                ?.toString() ?? ''
+                        return name.length > 0 ? { name, description } : null
+                    }),
+                })
             }
         }
 
-        const response = await fetch('/admin/create-election', {
+        const response = await fetch('/api/v1/admin/elections', {
             method: 'POST',
             headers: {
                 'Content-Type': 'application/json',
@@ -93,7 +722,7 @@ addElectionForm?.addEventListener('submit', async (event) => {
             )
 
             if (message instanceof HTMLParagraphElement) {
-                message.textContent = 'Created election successfully.'
+                message.textContent = t('createdElection')
             }
         } else if (response.status === 401) {
             window.location.href = '/admin/login'
@@ -103,23 +732,214 @@ addElectionForm?.addEventListener('submit', async (event) => {
             )
 
             if (message instanceof HTMLParagraphElement) {
-                message.textContent = 'Unexpected error: ' + response.status
+                message.textContent = t('unexpectedError', {
+                    status: response.status,
+                })
             }
         }
     }
 })
 
+let createAgendaSessionForm = document.getElementById(
+    'create-agenda-session-form'
+)
+
+createAgendaSessionForm?.addEventListener('submit', async (event) => {
+    event.preventDefault()
+
+    if (createAgendaSessionForm instanceof HTMLFormElement) {
+        const formData = new FormData(createAgendaSessionForm)
+
+        await submitAgendaForm(
+            createAgendaSessionForm,
+            'create-agenda-session-form-message',
+            '/api/v1/admin/agenda/sessions',
+            {
+                title: formData.get('title')?.toString() ?? '',
+                description: formData.get('description')?.toString() ?? '',
+            }
+        )
+    }
+})
+
+let createAgendaItemForm = document.getElementById('create-agenda-item-form')
+
+createAgendaItemForm?.addEventListener('submit', async (event) => {
+    event.preventDefault()
+
+    if (createAgendaItemForm instanceof HTMLFormElement) {
+        const formData = new FormData(createAgendaItemForm)
+
+        const documents = (formData.get('documents')?.toString() ?? '')
+            .split('\n')
+            .filter((line) => line.trim().length > 0)
+            .map((line) => {
+                const separatorIndex = line.lastIndexOf('|')
+                return separatorIndex === -1
+                    ? { title: line.trim(), url: line.trim() }
+                    : {
+                          title: line.slice(0, separatorIndex).trim(),
+                          url: line.slice(separatorIndex + 1).trim(),
+                      }
+            })
+
+        await submitAgendaForm(
+            createAgendaItemForm,
+            'create-agenda-item-form-message',
+            '/api/v1/admin/agenda/items',
+            {
+                sessionId: Number(formData.get('sessionId')),
+                number: formData.get('number')?.toString() ?? '',
+                title: formData.get('title')?.toString() ?? '',
+                description: formData.get('description')?.toString() ?? '',
+                documents,
+            }
+        )
+    }
+})
+
+async function submitAgendaForm(
+    form: HTMLFormElement,
+    messageElementId: string,
+    url: string,
+    body: object
+) {
+    const response = await fetch(url, {
+        method: 'POST',
+        headers: {
+            'Content-Type': 'application/json',
+        },
+        body: JSON.stringify(body),
+    })
+
+    let message = document.getElementById(messageElementId)
+
+    if (response.ok) {
+        form.reset()
+        if (message instanceof HTMLParagraphElement) {
+            message.textContent = t('addedSuccessfully')
+        }
+        await updateAndRenderElections()
+    } else if (response.status === 401) {
+        window.location.href = '/admin/login'
+    } else if (message instanceof HTMLParagraphElement) {
+        const apiError: ApiError = await response.json()
+        message.textContent = t('error', { message: apiError.message })
+    }
+}
+
 interface BallotItem {
     id: number
     name: string
+    affiliation: string
+    translations: Partial<Record<Locale, { name: string }>>
+}
+
+interface ElectionStatus {
+    kind: 'nominating' | 'awaitingAmendments' | 'open' | 'closed'
 }
 
+type Motion =
+    | {
+          kind: 'main'
+          text: string
+          consolidatedText: string | null
+          unappliedAmendmentIds: number[]
+      }
+    | {
+          kind: 'amendment'
+          parentElectionId: number
+          originalText: string
+          replacementText: string
+      }
+
+type CandidateResponse = 'pending' | 'accepted' | 'declined'
+type NominationReview = 'pending' | 'approved' | 'rejected'
+
+interface Nomination {
+    id: number
+    candidateName: string
+    candidateParticipantId: number
+    secondedBy: number[]
+    candidateResponse: CandidateResponse
+    review: NominationReview
+}
+
+const candidateResponseKeys: Record<CandidateResponse, MessageKey> = {
+    pending: 'responsePending',
+    accepted: 'responseAccepted',
+    declined: 'responseDeclined',
+}
+
+const nominationReviewKeys: Record<NominationReview, MessageKey> = {
+    pending: 'reviewPending',
+    approved: 'reviewApproved',
+    rejected: 'reviewRejected',
+}
+
+type Eligibility = { kind: 'everyone' } | { kind: 'groups'; groups: string[] }
+
 interface Election {
     id: number
     name: string
+    translations: Partial<Record<Locale, { name: string }>>
+    eligibility: Eligibility
+    status: ElectionStatus
+    motion: Motion | null
+    votingDeadline: string | null
+    resultsPublished: boolean
     ballotItemsById: Record<number, BallotItem>
 }
 
+type SpeakerStance = 'neutral' | 'pro' | 'contra'
+
+const speakerStanceKeys: Record<SpeakerStance, MessageKey> = {
+    neutral: 'stanceNeutral',
+    pro: 'stancePro',
+    contra: 'stanceContra',
+}
+
+interface SpeakerRequest {
+    id: number
+    participantId: number
+    stance: SpeakerStance
+    pointOfOrder: boolean
+    status:
+        | { kind: 'waiting' | 'withdrawn' }
+        | { kind: 'speaking'; startedAt: string; speakingDeadline: string | null }
+        | { kind: 'finished'; startedAt: string; finishedAt: string }
+}
+
+interface SpeakersList {
+    currentSpeaker: SpeakerRequest | null
+    waiting: SpeakerRequest[]
+    finished: SpeakerRequest[]
+}
+
+interface AgendaItem {
+    id: number
+    number: string
+    title: string
+    elections: Election[]
+}
+
+interface AgendaSession {
+    id: number
+    title: string
+    agendaItems: AgendaItem[]
+}
+
+interface ElectionsByAgenda {
+    sessions: AgendaSession[]
+    unscheduledElections: Election[]
+}
+
+interface ApiError {
+    code: string
+    message: string
+    details: unknown
+}
+
 const intervalId = setInterval(updateAndRenderElections, 5000)
 // AI told me to add this clean-up
 window.addEventListener('beforeunload', () => {
@@ -130,19 +950,17 @@ await updateAndRenderElections()
 
 async function updateAndRenderElections() {
     try {
-        const electionsResponse = await fetch('/elections')
+        const electionsResponse = await fetch('/api/v1/elections')
 
         if (electionsResponse.ok) {
-            const electionsById: Record<number, Election> =
+            const electionsByAgenda: ElectionsByAgenda =
                 await electionsResponse.json()
 
             let electionsDiv = document.getElementById('elections')
-            electionsDiv?.replaceChildren()
-
-            Object.entries(electionsById)
-                .sort()
-                .map(([_, value]) => value)
-                .forEach(createElectionDisplay)
+            if (electionsDiv instanceof HTMLDivElement) {
+                renderAgenda(electionsDiv, electionsByAgenda)
+            }
+            updateAgendaSelects(electionsByAgenda)
         } else if (electionsResponse.status === 401) {
             window.location.href = '/admin/login'
         } else {
@@ -151,8 +969,9 @@ async function updateAndRenderElections() {
             )
 
             if (errorMessageElement instanceof HTMLParagraphElement) {
-                errorMessageElement.innerHTML =
-                    'Unexpected error: ' + electionsResponse.status
+                errorMessageElement.textContent = t('unexpectedError', {
+                    status: electionsResponse.status,
+                })
             }
         }
     } catch (error) {
@@ -161,25 +980,107 @@ async function updateAndRenderElections() {
         )
 
         if (errorMessageElement instanceof HTMLParagraphElement) {
-            errorMessageElement.innerHTML =
-                'Error: could not load election data.'
+            errorMessageElement.textContent = t('couldNotLoadElections')
+        }
+    }
+}
+
+function renderAgenda(
+    electionsDiv: HTMLDivElement,
+    electionsByAgenda: ElectionsByAgenda
+) {
+    electionsDiv.replaceChildren()
+
+    for (const session of electionsByAgenda.sessions) {
+        let sessionHeading = document.createElement('h3')
+        sessionHeading.textContent = session.title
+        electionsDiv.appendChild(sessionHeading)
+
+        for (const agendaItem of session.agendaItems) {
+            let agendaItemHeading = document.createElement('h4')
+            agendaItemHeading.textContent = `${agendaItem.number} ${agendaItem.title}`
+            electionsDiv.appendChild(agendaItemHeading)
+
+            let speakersDiv = document.createElement('div')
+            electionsDiv.appendChild(speakersDiv)
+            renderSpeakers(speakersDiv, agendaItem.id)
+
+            agendaItem.elections.forEach((election) =>
+                createElectionDisplay(electionsDiv, election)
+            )
         }
     }
+
+    if (electionsByAgenda.unscheduledElections.length > 0) {
+        let otherHeading = document.createElement('h3')
+        otherHeading.textContent = t('notOnAgenda')
+        electionsDiv.appendChild(otherHeading)
+
+        electionsByAgenda.unscheduledElections.forEach((election) =>
+            createElectionDisplay(electionsDiv, election)
+        )
+    }
 }
 
-function createElectionDisplay(election: Election) {
-    let electionsDiv = document.getElementById('elections')
+// Keeps the current selection, since the agenda is reloaded periodically.
+function updateAgendaSelects(electionsByAgenda: ElectionsByAgenda) {
+    let sessionSelect = document.getElementById('agenda-item-session')
+    if (sessionSelect instanceof HTMLSelectElement) {
+        const selectedValue = sessionSelect.value
+        sessionSelect.replaceChildren(
+            ...electionsByAgenda.sessions.map((session) => {
+                let option = document.createElement('option')
+                option.value = session.id.toString()
+                option.textContent = session.title
+                return option
+            })
+        )
+        if (selectedValue.length > 0) {
+            sessionSelect.value = selectedValue
+        }
+    }
 
+    let agendaItemSelect = document.getElementById('election-agenda-item')
+    if (agendaItemSelect instanceof HTMLSelectElement) {
+        const selectedValue = agendaItemSelect.value
+        let noneOption = document.createElement('option')
+        noneOption.value = ''
+        noneOption.textContent = t('none')
+        agendaItemSelect.replaceChildren(
+            noneOption,
+            ...electionsByAgenda.sessions.flatMap((session) =>
+                session.agendaItems.map((agendaItem) => {
+                    let option = document.createElement('option')
+                    option.value = agendaItem.id.toString()
+                    option.textContent = `${session.title}: ${agendaItem.number} ${agendaItem.title}`
+                    return option
+                })
+            )
+        )
+        agendaItemSelect.value = selectedValue
+    }
+}
+
+async function createElectionDisplay(
+    electionsDiv: HTMLDivElement,
+    election: Election
+) {
     const electionFormId = `election-${election.id}`
 
     let electionLabel = document.createElement('label')
     electionLabel.htmlFor = electionFormId
-    electionLabel.textContent = election.name
-    electionsDiv?.appendChild(electionLabel)
+    electionLabel.textContent =
+        election.eligibility.kind === 'groups'
+            ? t('onlyGroups', {
+                  name: translatedName(election),
+                  groups: election.eligibility.groups.join(', '),
+              })
+            : translatedName(election)
+    electionsDiv.appendChild(electionLabel)
 
     let electionForm = document.createElement('form')
     electionForm.id = electionFormId
-    electionsDiv?.appendChild(electionForm)
+    electionsDiv.appendChild(electionForm)
 
     const submitButtonId = `election-${election.id}-submit`
     const radioButtonGroupName = `election-${election.id}-selected-ballot-item-id`
@@ -204,7 +1105,10 @@ function createElectionDisplay(election: Election) {
             })
 
             let label = document.createElement('label')
-            label.textContent = ballotItem.name
+            label.textContent =
+                ballotItem.affiliation.length === 0
+                    ? translatedName(ballotItem)
+                    : `${translatedName(ballotItem)} (${ballotItem.affiliation})`
             label.htmlFor = ballotItemInputId
 
             electionForm.appendChild(ballotItemInput)
@@ -217,4 +1121,331 @@ function createElectionDisplay(election: Election) {
     electionIdHiddenInput.name = 'election-id'
     electionIdHiddenInput.value = election.id.toString()
     electionForm.appendChild(electionIdHiddenInput)
+
+    if (election.motion !== null) {
+        electionForm.appendChild(createMotionText(election.motion))
+    }
+
+    if (election.status.kind === 'closed') {
+        let closedMessage = document.createElement('p')
+        closedMessage.textContent = election.resultsPublished
+            ? t('closedWithPublishedResults')
+            : t('electionClosed')
+        electionForm.appendChild(closedMessage)
+
+        if (!election.resultsPublished) {
+            electionForm.appendChild(
+                createElectionActionButton(election, 'publish', t('publishResults'))
+            )
+        }
+        return
+    }
+
+    if (election.status.kind === 'nominating') {
+        await renderNominations(electionForm, election)
+        electionForm.appendChild(
+            createElectionActionButton(election, 'open', t('openVoting'))
+        )
+        return
+    }
+
+    if (election.status.kind === 'awaitingAmendments') {
+        electionForm.appendChild(
+            createElectionActionButton(
+                election,
+                'open',
+                t('openVotingWithAmendments')
+            )
+        )
+        return
+    }
+
+    if (election.votingDeadline !== null) {
+        let deadlineMessage = document.createElement('p')
+        deadlineMessage.textContent = t('closesAt', {
+            time: new Date(election.votingDeadline).toLocaleTimeString(locale),
+        })
+        electionForm.appendChild(deadlineMessage)
+    }
+
+    electionForm.appendChild(
+        createElectionActionButton(election, 'close', t('closeElection'))
+    )
+}
+
+function createMotionText(motion: Motion): HTMLParagraphElement {
+    let motionParagraph = document.createElement('p')
+    motionParagraph.style.whiteSpace = 'pre-wrap'
+
+    if (motion.kind === 'amendment') {
+        motionParagraph.textContent = t('amendmentChange', {
+            id: motion.parentElectionId,
+            original: motion.originalText,
+            replacement: motion.replacementText,
+        })
+    } else if (motion.consolidatedText === null) {
+        motionParagraph.textContent = motion.text
+    } else {
+        motionParagraph.textContent = motion.consolidatedText
+
+        if (motion.unappliedAmendmentIds.length > 0) {
+            motionParagraph.textContent +=
+                '\n\n' +
+                t('unappliedAmendments', {
+                    ids: motion.unappliedAmendmentIds.join(', '),
+                })
+        }
+    }
+
+    return motionParagraph
+}
+
+function createElectionActionButton(
+    election: Election,
+    action: 'open' | 'close' | 'publish',
+    text: string
+): HTMLButtonElement {
+    let button = document.createElement('button')
+    button.textContent = text
+    button.addEventListener('click', async (event) => {
+        event.preventDefault()
+
+        const response = await fetch(
+            `/api/v1/admin/elections/${election.id}/${action}`,
+            { method: 'POST' }
+        )
+
+        if (response.ok) {
+            await updateAndRenderElections()
+        } else if (response.status === 401) {
+            window.location.href = '/admin/login'
+        } else {
+            let errorMessageElement = document.getElementById(
+                'elections-error-message'
+            )
+
+            if (errorMessageElement instanceof HTMLParagraphElement) {
+                const apiError: ApiError = await response.json()
+                errorMessageElement.textContent = t('error', {
+                    message: apiError.message,
+                })
+            }
+        }
+    })
+
+    return button
+}
+
+async function renderNominations(
+    electionForm: HTMLFormElement,
+    election: Election
+) {
+    const response = await fetch(`/api/v1/elections/${election.id}/nominations`)
+    if (!response.ok) {
+        return
+    }
+    const nominations: Nomination[] = await response.json()
+
+    let nominationsList = document.createElement('ul')
+    for (const nomination of nominations) {
+        let listItem = document.createElement('li')
+        listItem.textContent =
+            t('adminNominationSummary', {
+                name: nomination.candidateName,
+                id: nomination.candidateParticipantId,
+                seconders: nomination.secondedBy.length,
+                response: t(candidateResponseKeys[nomination.candidateResponse]),
+                review: t(nominationReviewKeys[nomination.review]),
+            }) + ' '
+
+        for (const [action, text] of [
+            ['approve', t('approve')],
+            ['reject', t('reject')],
+        ]) {
+            let reviewButton = document.createElement('button')
+            reviewButton.textContent = text
+            reviewButton.addEventListener('click', async (event) => {
+                event.preventDefault()
+
+                const response = await fetch(
+                    `/api/v1/admin/nominations/${nomination.id}/${action}`,
+                    { method: 'POST' }
+                )
+                if (response.ok) {
+                    await updateAndRenderElections()
+                }
+            })
+            listItem.appendChild(reviewButton)
+        }
+
+        nominationsList.appendChild(listItem)
+    }
+
+    electionForm.appendChild(nominationsList)
+}
+
+function describeSpeaker(speakerRequest: SpeakerRequest): string {
+    let description = t('participant', { id: speakerRequest.participantId })
+    if (speakerRequest.stance !== 'neutral') {
+        description += ', ' + t(speakerStanceKeys[speakerRequest.stance])
+    }
+    if (speakerRequest.pointOfOrder) {
+        description += ', ' + t('speakerPointOfOrder')
+    }
+    return description
+}
+
+async function renderSpeakers(speakersDiv: HTMLDivElement, agendaItemId: number) {
+    const response = await fetch(`/api/v1/agenda/items/${agendaItemId}/speakers`)
+    if (!response.ok) {
+        return
+    }
+    const speakersList: SpeakersList = await response.json()
+
+    let currentSpeakerParagraph = document.createElement('p')
+    const currentSpeaker = speakersList.currentSpeaker
+    if (currentSpeaker === null || currentSpeaker.status.kind !== 'speaking') {
+        currentSpeakerParagraph.textContent = t('nobodyHasTheFloor')
+    } else {
+        const spokenSeconds = Math.round(
+            (Date.now() - new Date(currentSpeaker.status.startedAt).getTime()) /
+                1000
+        )
+        currentSpeakerParagraph.textContent = t('speakingFor', {
+            speaker: describeSpeaker(currentSpeaker),
+            seconds: spokenSeconds,
+        })
+
+        if (currentSpeaker.status.speakingDeadline !== null) {
+            const remainingSeconds = Math.round(
+                (new Date(currentSpeaker.status.speakingDeadline).getTime() -
+                    Date.now()) /
+                    1000
+            )
+            currentSpeakerParagraph.textContent +=
+                ' ' +
+                (remainingSeconds >= 0
+                    ? t('secondsLeft', { seconds: remainingSeconds })
+                    : t('secondsOverTime', { seconds: -remainingSeconds }))
+        }
+
+        currentSpeakerParagraph.append(
+            ' ',
+            createSpeakerActionButton(currentSpeaker.id, 'finish', t('finish'))
+        )
+    }
+    speakersDiv.appendChild(currentSpeakerParagraph)
+
+    const waitingIds = speakersList.waiting.map(
+        (speakerRequest) => speakerRequest.id
+    )
+
+    let waitingList = document.createElement('ol')
+    speakersList.waiting.forEach((speakerRequest, index) => {
+        let listItem = document.createElement('li')
+        listItem.textContent = describeSpeaker(speakerRequest) + ' '
+        listItem.append(
+            createSpeakerActionButton(speakerRequest.id, 'call', t('call')),
+            createMoveSpeakerButton(agendaItemId, waitingIds, index, -1, t('up')),
+            createMoveSpeakerButton(agendaItemId, waitingIds, index, 1, t('down')),
+            createSpeakerActionButton(speakerRequest.id, 'remove', t('remove'))
+        )
+        waitingList.appendChild(listItem)
+    })
+    speakersDiv.appendChild(waitingList)
+
+    if (speakersList.finished.length > 0) {
+        let finishedParagraph = document.createElement('p')
+        finishedParagraph.textContent = t('alreadySpoke', {
+            ids: speakersList.finished
+                .map((speakerRequest) => speakerRequest.participantId)
+                .join(', '),
+        })
+        speakersDiv.appendChild(finishedParagraph)
+    }
+}
+
+function createSpeakerActionButton(
+    speakerRequestId: number,
+    action: 'call' | 'finish' | 'remove',
+    text: string
+): HTMLButtonElement {
+    let button = document.createElement('button')
+    button.textContent = text
+    button.addEventListener('click', async () => {
+        let request: RequestInit = { method: 'POST' }
+        let url = `/api/v1/admin/speaker-requests/${speakerRequestId}/${action}`
+
+        if (action === 'remove') {
+            request = { method: 'DELETE' }
+            url = `/api/v1/admin/speaker-requests/${speakerRequestId}`
+        } else if (action === 'call') {
+            let speakingTimeInput = document.getElementById('speaking-time')
+            const speakingTime =
+                speakingTimeInput instanceof HTMLInputElement &&
+                speakingTimeInput.value.length > 0
+                    ? Number(speakingTimeInput.value)
+                    : null
+            request = {
+                method: 'POST',
+                headers: {
+                    'Content-Type': 'application/json',
+                },
+                body: JSON.stringify({ speakingTimeSeconds: speakingTime }),
+            }
+        }
+
+        await sendSpeakersRequest(url, request)
+    })
+
+    return button
+}
+
+function createMoveSpeakerButton(
+    agendaItemId: number,
+    waitingIds: number[],
+    index: number,
+    offset: -1 | 1,
+    text: string
+): HTMLButtonElement {
+    let button = document.createElement('button')
+    button.textContent = text
+    button.disabled = index + offset < 0 || index + offset >= waitingIds.length
+    button.addEventListener('click', async () => {
+        let newOrder = [...waitingIds]
+        const [moved] = newOrder.splice(index, 1)
+        if (moved === undefined) {
+            return
+        }
+        newOrder.splice(index + offset, 0, moved)
+
+        await sendSpeakersRequest(
+            `/api/v1/admin/agenda/items/${agendaItemId}/speakers/order`,
+            {
+                method: 'PUT',
+                headers: {
+                    'Content-Type': 'application/json',
+                },
+                body: JSON.stringify({ speakerRequestIds: newOrder }),
+            }
+        )
+    })
+
+    return button
+}
+
+async function sendSpeakersRequest(url: string, request: RequestInit) {
+    const response = await fetch(url, request)
+
+    if (response.ok) {
+        await updateAndRenderElections()
+    } else if (response.status === 401) {
+        window.location.href = '/admin/login'
+    } else {
+        let message = document.getElementById('speakers-message')
+        if (message instanceof HTMLParagraphElement) {
+            const apiError: ApiError = await response.json()
+            message.textContent = t('error', { message: apiError.message })
+        }
+    }
 }
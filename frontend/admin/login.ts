@@ -1,10 +1,14 @@
+import { localizePage, t } from '../i18n.js'
+
+localizePage()
+
 const form = document.forms.namedItem('admin-login-form')
 
 form?.addEventListener('submit', async (event) => {
     event.preventDefault()
     const data = new FormData(form)
 
-    const response = await fetch('/admin/start-session', {
+    const response = await fetch('/api/v1/admin/sessions', {
         method: 'POST',
         headers: {
             'Content-Type': 'application/json',
@@ -17,15 +21,16 @@ form?.addEventListener('submit', async (event) => {
             'admin-login-form-error-message'
         )
         if (errorMessageElement instanceof HTMLParagraphElement) {
-            errorMessageElement.innerHTML = 'Wrong password.'
+            errorMessageElement.textContent = t('wrongUsernameOrPassword')
         }
     } else if (!response.ok) {
         let errorMessageElement = document.getElementById(
             'admin-login-form-error-message'
         )
         if (errorMessageElement instanceof HTMLParagraphElement) {
-            errorMessageElement.innerHTML =
-                'Unexpected error: ' + response.status
+            errorMessageElement.textContent = t('unexpectedError', {
+                status: response.status,
+            })
         }
     } else {
         window.location.href = '/admin'
@@ -0,0 +1,490 @@
+// Texts of the user interface in every supported language. Names of elections and ballot
+// items are entered by admins in the default language and may be translated, see
+// `translatedName`.
+
+export type Locale = 'en' | 'de'
+
+const defaultLocale: Locale = 'en'
+const localeCookieKey = 'coco_locale'
+
+const localeNames: Record<Locale, string> = {
+    en: 'English',
+    de: 'Deutsch',
+}
+
+// The languages admins can translate names and descriptions into.
+export const translationLocales: Locale[] = ['de']
+
+export function localeName(locale: Locale): string {
+    return localeNames[locale]
+}
+
+const en = {
+    language: 'Language',
+    error: 'Error: {message}',
+    unexpectedError: 'Unexpected error: {status}',
+    couldNotLoadElections: 'Error: could not load election data.',
+    submit: 'Submit',
+    delete: 'Delete',
+    login: 'Login',
+    usernameLabel: 'Username:',
+    passwordLabel: 'Password:',
+    descriptionLabel: 'Description:',
+    none: 'None',
+    electionClosed: 'This election is closed.',
+    amendsElection: 'Amends election {id}:',
+    participant: 'Participant {id}',
+    stanceNeutral: 'Neutral',
+    stancePro: 'Pro',
+    stanceContra: 'Contra',
+    pointOfOrder: 'Point of order',
+    speakerPointOfOrder: 'point of order',
+    nobodyHasTheFloor: 'Nobody has the floor.',
+    speaking: 'Speaking: {speaker}',
+    secondsLeft: '({seconds} s left)',
+    secondsOverTime: '({seconds} s over time)',
+    responsePending: 'pending',
+    responseAccepted: 'accepted',
+    responseDeclined: 'declined',
+
+    loginTitle: 'coco - Login',
+    welcome: 'Welcome to coco!',
+    enterPassword: 'Please enter the password:',
+    wrongPassword: 'Wrong password.',
+
+    electionsTitle: 'coco - Elections',
+    elections: 'Elections',
+    ownParticipantNumber:
+        'Your participant number is {id}. Others need it to nominate you.',
+    otherElections: 'Other elections',
+    awaitingAmendments:
+        'Voting starts once all amendments to this motion have been decided.',
+    votingSuccessful: 'Voting successful.',
+    candidatesBeingNominated: 'Candidates are being nominated.',
+    nominationSummary: '{name} ({seconders} seconders, candidate {response})',
+    accept: 'Accept',
+    decline: 'Decline',
+    second: 'Second',
+    candidateName: 'Candidate name',
+    participantNumber: 'Participant number',
+    nominate: 'Nominate',
+    finalMotionText: 'Final text with all adopted amendments:',
+    document: 'Document ({size} KiB)',
+    ownSpeakerRequest: '(you)',
+    withdraw: 'Withdraw',
+    requestToSpeak: 'Request to speak',
+
+    presentationTitle: 'coco - Presentation',
+    noElectionYet: 'No election yet',
+    votingOpen: 'Voting is open',
+    votingClosed: 'Voting is closed',
+    turnout: '{cast} of {eligible} ballots cast',
+    outcomeDecided: 'Decided: {name}',
+    outcomeTie: 'Tie: {names}',
+    outcomeNoMajority: 'No ballot item reached the required majority.',
+    outcomeNoVotes: 'No votes were cast.',
+    couldNotLoadPresentation: 'Error: could not load presentation data.',
+
+    adminLoginTitle: 'coco - Admin Login',
+    adminWelcome: 'Welcome to the coco admin page!',
+    wrongUsernameOrPassword: 'Wrong username or password.',
+
+    adminTitle: 'coco - Admin',
+    adminHeading: 'coco admin page',
+    loggedInAs: 'Logged in as {username} ({role})',
+    roleSuperAdmin: 'Super admin',
+    roleElectionOfficer: 'Election officer',
+    roleRegistrationDesk: 'Registration desk',
+    roleObserver: 'Observer',
+    adminAccountsHeading: 'Admin accounts:',
+    roleLabel: 'Role:',
+    createAdminAccount: 'Create admin account',
+    createdAdminAccount: 'Created admin account successfully.',
+    eventsHeading: 'Events:',
+    event: 'Event: {name}',
+    subdomainLabel: 'Subdomain:',
+    nameLabel: 'Name:',
+    participantPasswordLabel: 'Participant password:',
+    adminPasswordLabel: 'Admin password:',
+    createEvent: 'Create event',
+    createdEvent: 'Created event successfully.',
+    registrationDeskHeading: 'Registration desk:',
+    requireCheckInToVote: 'Only checked-in participants may vote',
+    quorumLabel: 'Quorum (present participants, empty for none):',
+    attendanceSummary:
+        '{present} of {registered} registered participants are present.',
+    participantNotPresent: 'Participant {id}, not present',
+    participantPresentSince: 'Participant {id}, present since {time}',
+    checkIn: 'Check in',
+    checkOut: 'Check out',
+    groups: 'Groups, comma separated',
+    saveGroups: 'Save groups',
+    createElectionHeading: 'Create a new election:',
+    electionNameLabel: 'Election name:',
+    electionNameTranslationLabel: 'Election name in {language} (optional):',
+    methodLabel: 'Method:',
+    methodPlurality: 'Plurality',
+    methodMoreThanHalf: 'Majority, more than half',
+    methodAtLeastTwoThirds: 'Majority, at least two thirds',
+    agendaItemLabel: 'Agenda item:',
+    votingTimeLabel: 'Voting time in seconds (optional):',
+    nominationPhaseLabel: 'Nomination phase before voting:',
+    requiredSecondersLabel: 'Required seconders:',
+    eligibleGroupsLabel: 'Eligible groups, comma separated (empty for everyone):',
+    motionLabel: 'Motion:',
+    mainMotion: 'Main motion, amendments are voted on first',
+    amendment: 'Amendment to a main motion',
+    motionTextLabel: 'Motion text (main motion only):',
+    amendedElectionLabel: 'Amended election number:',
+    originalTextLabel: 'Text to replace:',
+    replacementTextLabel: 'Replacement:',
+    defaultBallotItems:
+        'Without ballot items, motions and amendments are voted on with "Yes" and "No". The first ballot item adopts them.',
+    addBallotItem: 'Add ballot item',
+    ballotItemName: 'Name',
+    ballotItemNameTranslation: 'Name in {language} (optional)',
+    affiliation: 'Affiliation (optional)',
+    ballotItemDescription: 'Statement or motion text, Markdown (optional)',
+    ballotItemDescriptionTranslation:
+        'Statement or motion text in {language}, Markdown (optional)',
+    uploadError: 'Error uploading {file}: {message}',
+    emptyElectionName: 'Error: Election title cannot be empty.',
+    emptyBallotItemName: 'Error: Ballot item name cannot be empty.',
+    createdElection: 'Created election successfully.',
+    agendaHeading: 'Agenda:',
+    sessionTitleLabel: 'Session title:',
+    addSession: 'Add session',
+    sessionLabel: 'Session:',
+    numberLabel: 'Number:',
+    titleLabel: 'Title:',
+    documentsLabel: 'Documents, one "title | URL" per line:',
+    addAgendaItem: 'Add agenda item',
+    addedSuccessfully: 'Added successfully.',
+    resultsHeading: 'Results:',
+    openPresentation: 'Open the presentation on the projector:',
+    presentationToken:
+        'Outside of this browser, append ?token= with an API token that has the readElections scope.',
+    minutes: 'Minutes (printable)',
+    speakersListsHeading: 'Speakers lists:',
+    speakersListsHint: 'Each agenda item below shows its speakers list.',
+    speakingTimeLabel: 'Speaking time in seconds for called speakers (optional):',
+    currentElectionsHeading: 'Current Elections:',
+    notOnAgenda: 'Not on the agenda',
+    onlyGroups: '{name} (only {groups})',
+    closedWithPublishedResults:
+        'This election is closed. Its results are shown in the presentation.',
+    publishResults: 'Publish results',
+    openVoting: 'Open voting',
+    openVotingWithAmendments: 'Open voting with adopted amendments',
+    closesAt: 'Closes automatically at {time}',
+    closeElection: 'Close election',
+    amendmentChange:
+        'Amends election {id}: replace "{original}" by "{replacement}"',
+    unappliedAmendments:
+        'Adopted but not applicable, the replaced text was already changed: elections {ids}',
+    adminNominationSummary:
+        '{name} (participant {id}, {seconders} seconders, candidate {response}, review {review})',
+    reviewPending: 'pending',
+    reviewApproved: 'approved',
+    reviewRejected: 'rejected',
+    approve: 'Approve',
+    reject: 'Reject',
+    speakingFor: 'Speaking: {speaker}, for {seconds} s',
+    finish: 'Finish',
+    call: 'Call',
+    up: 'Up',
+    down: 'Down',
+    remove: 'Remove',
+    alreadySpoke: 'Already spoke: {ids}',
+}
+
+export type MessageKey = keyof typeof en
+
+const de: Record<MessageKey, string> = {
+    language: 'Sprache',
+    error: 'Fehler: {message}',
+    unexpectedError: 'Unerwarteter Fehler: {status}',
+    couldNotLoadElections: 'Fehler: Die Wahldaten konnten nicht geladen werden.',
+    submit: 'Absenden',
+    delete: 'Löschen',
+    login: 'Anmelden',
+    usernameLabel: 'Benutzername:',
+    passwordLabel: 'Passwort:',
+    descriptionLabel: 'Beschreibung:',
+    none: 'Keiner',
+    electionClosed: 'Diese Wahl ist beendet.',
+    amendsElection: 'Ändert Wahl {id}:',
+    participant: 'Teilnehmende Person {id}',
+    stanceNeutral: 'Neutral',
+    stancePro: 'Dafür',
+    stanceContra: 'Dagegen',
+    pointOfOrder: 'Geschäftsordnungsantrag',
+    speakerPointOfOrder: 'Geschäftsordnungsantrag',
+    nobodyHasTheFloor: 'Niemand hat das Wort.',
+    speaking: 'Es spricht: {speaker}',
+    secondsLeft: '(noch {seconds} s)',
+    secondsOverTime: '({seconds} s überzogen)',
+    responsePending: 'offen',
+    responseAccepted: 'angenommen',
+    responseDeclined: 'abgelehnt',
+
+    loginTitle: 'coco - Anmeldung',
+    welcome: 'Willkommen bei coco!',
+    enterPassword: 'Bitte gib das Passwort ein:',
+    wrongPassword: 'Falsches Passwort.',
+
+    electionsTitle: 'coco - Wahlen',
+    elections: 'Wahlen',
+    ownParticipantNumber:
+        'Deine Teilnahmenummer ist {id}. Andere brauchen sie, um dich zu nominieren.',
+    otherElections: 'Weitere Wahlen',
+    awaitingAmendments:
+        'Die Abstimmung beginnt, sobald über alle Änderungsanträge zu diesem Antrag entschieden ist.',
+    votingSuccessful: 'Stimme erfolgreich abgegeben.',
+    candidatesBeingNominated: 'Kandidierende werden nominiert.',
+    nominationSummary: '{name} ({seconders} Unterstützende, Kandidatur {response})',
+    accept: 'Annehmen',
+    decline: 'Ablehnen',
+    second: 'Unterstützen',
+    candidateName: 'Name der Kandidatur',
+    participantNumber: 'Teilnahmenummer',
+    nominate: 'Nominieren',
+    finalMotionText: 'Endgültiger Text mit allen angenommenen Änderungsanträgen:',
+    document: 'Dokument ({size} KiB)',
+    ownSpeakerRequest: '(du)',
+    withdraw: 'Zurückziehen',
+    requestToSpeak: 'Wortmeldung',
+
+    presentationTitle: 'coco - Präsentation',
+    noElectionYet: 'Noch keine Wahl',
+    votingOpen: 'Die Abstimmung läuft',
+    votingClosed: 'Die Abstimmung ist beendet',
+    turnout: '{cast} von {eligible} Stimmen abgegeben',
+    outcomeDecided: 'Entschieden: {name}',
+    outcomeTie: 'Gleichstand: {names}',
+    outcomeNoMajority: 'Keine Option hat die erforderliche Mehrheit erreicht.',
+    outcomeNoVotes: 'Es wurden keine Stimmen abgegeben.',
+    couldNotLoadPresentation:
+        'Fehler: Die Präsentationsdaten konnten nicht geladen werden.',
+
+    adminLoginTitle: 'coco - Admin-Anmeldung',
+    adminWelcome: 'Willkommen auf der coco-Admin-Seite!',
+    wrongUsernameOrPassword: 'Falscher Benutzername oder falsches Passwort.',
+
+    adminTitle: 'coco - Admin',
+    adminHeading: 'coco-Admin-Seite',
+    loggedInAs: 'Angemeldet als {username} ({role})',
+    roleSuperAdmin: 'Super-Admin',
+    roleElectionOfficer: 'Wahlleitung',
+    roleRegistrationDesk: 'Empfang',
+    roleObserver: 'Beobachtung',
+    adminAccountsHeading: 'Admin-Konten:',
+    roleLabel: 'Rolle:',
+    createAdminAccount: 'Admin-Konto anlegen',
+    createdAdminAccount: 'Admin-Konto erfolgreich angelegt.',
+    eventsHeading: 'Veranstaltungen:',
+    event: 'Veranstaltung: {name}',
+    subdomainLabel: 'Subdomain:',
+    nameLabel: 'Name:',
+    participantPasswordLabel: 'Passwort für Teilnehmende:',
+    adminPasswordLabel: 'Admin-Passwort:',
+    createEvent: 'Veranstaltung anlegen',
+    createdEvent: 'Veranstaltung erfolgreich angelegt.',
+    registrationDeskHeading: 'Empfang:',
+    requireCheckInToVote: 'Nur eingecheckte Teilnehmende dürfen abstimmen',
+    quorumLabel: 'Quorum (anwesende Teilnehmende, leer für keins):',
+    attendanceSummary:
+        '{present} von {registered} registrierten Teilnehmenden sind anwesend.',
+    participantNotPresent: 'Teilnehmende Person {id}, nicht anwesend',
+    participantPresentSince: 'Teilnehmende Person {id}, anwesend seit {time}',
+    checkIn: 'Einchecken',
+    checkOut: 'Auschecken',
+    groups: 'Gruppen, durch Kommas getrennt',
+    saveGroups: 'Gruppen speichern',
+    createElectionHeading: 'Neue Wahl anlegen:',
+    electionNameLabel: 'Name der Wahl:',
+    electionNameTranslationLabel: 'Name der Wahl auf {language} (optional):',
+    methodLabel: 'Verfahren:',
+    methodPlurality: 'Relative Mehrheit',
+    methodMoreThanHalf: 'Mehrheit, mehr als die Hälfte',
+    methodAtLeastTwoThirds: 'Mehrheit, mindestens zwei Drittel',
+    agendaItemLabel: 'Tagesordnungspunkt:',
+    votingTimeLabel: 'Abstimmungsdauer in Sekunden (optional):',
+    nominationPhaseLabel: 'Nominierungsphase vor der Abstimmung:',
+    requiredSecondersLabel: 'Nötige Unterstützende:',
+    eligibleGroupsLabel:
+        'Stimmberechtigte Gruppen, durch Kommas getrennt (leer für alle):',
+    motionLabel: 'Antrag:',
+    mainMotion: 'Hauptantrag, über Änderungsanträge wird zuerst abgestimmt',
+    amendment: 'Änderungsantrag zu einem Hauptantrag',
+    motionTextLabel: 'Antragstext (nur Hauptantrag):',
+    amendedElectionLabel: 'Nummer der geänderten Wahl:',
+    originalTextLabel: 'Zu ersetzender Text:',
+    replacementTextLabel: 'Ersetzung:',
+    defaultBallotItems:
+        'Ohne Optionen wird über Anträge und Änderungsanträge mit „Ja“ und „Nein“ abgestimmt. Die erste Option nimmt sie an.',
+    addBallotItem: 'Option hinzufügen',
+    ballotItemName: 'Name',
+    ballotItemNameTranslation: 'Name auf {language} (optional)',
+    affiliation: 'Zugehörigkeit (optional)',
+    ballotItemDescription: 'Vorstellung oder Antragstext, Markdown (optional)',
+    ballotItemDescriptionTranslation:
+        'Vorstellung oder Antragstext auf {language}, Markdown (optional)',
+    uploadError: 'Fehler beim Hochladen von {file}: {message}',
+    emptyElectionName: 'Fehler: Der Name der Wahl darf nicht leer sein.',
+    emptyBallotItemName: 'Fehler: Der Name einer Option darf nicht leer sein.',
+    createdElection: 'Wahl erfolgreich angelegt.',
+    agendaHeading: 'Tagesordnung:',
+    sessionTitleLabel: 'Titel der Sitzung:',
+    addSession: 'Sitzung hinzufügen',
+    sessionLabel: 'Sitzung:',
+    numberLabel: 'Nummer:',
+    titleLabel: 'Titel:',
+    documentsLabel: 'Dokumente, ein „Titel | URL“ pro Zeile:',
+    addAgendaItem: 'Tagesordnungspunkt hinzufügen',
+    addedSuccessfully: 'Erfolgreich hinzugefügt.',
+    resultsHeading: 'Ergebnisse:',
+    openPresentation: 'Öffne die Präsentation auf dem Projektor:',
+    presentationToken:
+        'Außerhalb dieses Browsers hänge ?token= mit einem API-Token an, das die Berechtigung readElections hat.',
+    minutes: 'Protokoll (druckbar)',
+    speakersListsHeading: 'Redelisten:',
+    speakersListsHint: 'Jeder Tagesordnungspunkt unten zeigt seine Redeliste.',
+    speakingTimeLabel:
+        'Redezeit in Sekunden für aufgerufene Personen (optional):',
+    currentElectionsHeading: 'Aktuelle Wahlen:',
+    notOnAgenda: 'Nicht auf der Tagesordnung',
+    onlyGroups: '{name} (nur {groups})',
+    closedWithPublishedResults:
+        'Diese Wahl ist beendet. Ihre Ergebnisse werden in der Präsentation gezeigt.',
+    publishResults: 'Ergebnisse veröffentlichen',
+    openVoting: 'Abstimmung öffnen',
+    openVotingWithAmendments:
+        'Abstimmung mit angenommenen Änderungsanträgen öffnen',
+    closesAt: 'Endet automatisch um {time}',
+    closeElection: 'Wahl beenden',
+    amendmentChange: 'Ändert Wahl {id}: ersetze „{original}“ durch „{replacement}“',
+    unappliedAmendments:
+        'Angenommen, aber nicht anwendbar, da der ersetzte Text bereits geändert wurde: Wahlen {ids}',
+    adminNominationSummary:
+        '{name} (Teilnehmende Person {id}, {seconders} Unterstützende, Kandidatur {response}, Prüfung {review})',
+    reviewPending: 'offen',
+    reviewApproved: 'bestätigt',
+    reviewRejected: 'zurückgewiesen',
+    approve: 'Bestätigen',
+    reject: 'Zurückweisen',
+    speakingFor: 'Es spricht: {speaker}, seit {seconds} s',
+    finish: 'Beenden',
+    call: 'Aufrufen',
+    up: 'Hoch',
+    down: 'Runter',
+    remove: 'Entfernen',
+    alreadySpoke: 'Bereits gesprochen: {ids}',
+}
+
+const catalogs: Record<Locale, Record<MessageKey, string>> = { en, de }
+
+function isLocale(value: string | undefined): value is Locale {
+    return value === 'en' || value === 'de'
+}
+
+function isMessageKey(value: string | undefined): value is MessageKey {
+    return value !== undefined && Object.hasOwn(en, value)
+}
+
+// Follows the same rules as the server, so that its error messages are in the language of the
+// page: the language switcher's cookie first, then the browser's preferred languages.
+function preferredLocale(): Locale {
+    const cookieLocale = document.cookie
+        .split(';')
+        .map((c) => c.trim())
+        .find((c) => c.startsWith(localeCookieKey + '='))
+        ?.split('=')[1]
+    if (isLocale(cookieLocale)) {
+        return cookieLocale
+    }
+
+    for (const language of navigator.languages) {
+        const primaryLanguage = language.split('-')[0]?.toLowerCase()
+        if (isLocale(primaryLanguage)) {
+            return primaryLanguage
+        }
+    }
+
+    return defaultLocale
+}
+
+export const locale: Locale = preferredLocale()
+
+// The text for `key`, with `{name}` placeholders replaced by `params`.
+export function t(
+    key: MessageKey,
+    params: Record<string, string | number> = {}
+): string {
+    return catalogs[locale][key].replace(
+        /\{(\w+)\}/g,
+        (placeholder, name: string) => params[name]?.toString() ?? placeholder
+    )
+}
+
+interface Translatable {
+    name: string
+    translations: Partial<Record<Locale, { name: string }>>
+}
+
+export function translatedName(item: Translatable): string {
+    const name = item.translations[locale]?.name ?? ''
+    return name.length > 0 ? name : item.name
+}
+
+export function translatedDescription(ballotItem: {
+    description: string
+    translations: Partial<Record<Locale, { description: string }>>
+}): string {
+    const description = ballotItem.translations[locale]?.description ?? ''
+    return description.length > 0 ? description : ballotItem.description
+}
+
+// Replaces the texts of elements with a `data-i18n` attribute, the value of submit buttons and
+// the content of everything else, and adds the language switcher to `#locale-switcher`.
+export function localizePage() {
+    document.documentElement.lang = locale
+
+    for (const element of document.querySelectorAll<HTMLElement>('[data-i18n]')) {
+        const key = element.dataset['i18n']
+        if (!isMessageKey(key)) {
+            continue
+        }
+
+        if (element instanceof HTMLInputElement) {
+            element.value = t(key)
+        } else {
+            element.textContent = t(key)
+        }
+    }
+
+    let localeSwitcher = document.getElementById('locale-switcher')
+    if (localeSwitcher instanceof HTMLElement) {
+        localeSwitcher.replaceChildren(createLocaleSelect())
+    }
+}
+
+function createLocaleSelect(): HTMLSelectElement {
+    let localeSelect = document.createElement('select')
+    localeSelect.ariaLabel = t('language')
+
+    for (const [value, name] of Object.entries(localeNames)) {
+        let option = document.createElement('option')
+        option.value = value
+        option.textContent = name
+        option.selected = value === locale
+        localeSelect.appendChild(option)
+    }
+
+    localeSelect.addEventListener('change', () => {
+        document.cookie = `${localeCookieKey}=${localeSelect.value}; Path=/; Max-Age=31536000; SameSite=Lax`
+        window.location.reload()
+    })
+
+    return localeSelect
+}
@@ -1,3 +1,14 @@
+import {
+    type Locale,
+    type MessageKey,
+    localizePage,
+    t,
+    translatedDescription,
+    translatedName,
+} from './i18n.js'
+
+localizePage()
+
 const isLoggedIn = document.cookie
     .split(';')
     .some((c) => c.trim().startsWith('coco_token'))
@@ -6,17 +17,158 @@ if (!isLoggedIn) {
     window.location.href = '/login'
 }
 
+interface Attachment {
+    id: number
+    contentType: string
+    size: number
+    url: string
+}
+
+interface BallotItemTranslation {
+    name: string
+    description: string
+}
+
 interface BallotItem {
     id: number
     name: string
+    description: string
+    affiliation: string
+    attachments: Attachment[]
+    translations: Partial<Record<Locale, BallotItemTranslation>>
+}
+
+interface ElectionStatus {
+    kind: 'nominating' | 'awaitingAmendments' | 'open' | 'closed'
+}
+
+type Motion =
+    | {
+          kind: 'main'
+          text: string
+          consolidatedText: string | null
+          unappliedAmendmentIds: number[]
+      }
+    | {
+          kind: 'amendment'
+          parentElectionId: number
+          originalText: string
+          replacementText: string
+      }
+
+type CandidateResponse = 'pending' | 'accepted' | 'declined'
+
+interface Nomination {
+    id: number
+    candidateName: string
+    candidateParticipantId: number
+    nominatedBy: number
+    secondedBy: number[]
+    candidateResponse: CandidateResponse
+    review: 'pending' | 'approved' | 'rejected'
+}
+
+const candidateResponseKeys: Record<CandidateResponse, MessageKey> = {
+    pending: 'responsePending',
+    accepted: 'responseAccepted',
+    declined: 'responseDeclined',
 }
 
+const ownParticipantId = Number(
+    document.cookie
+        .split(';')
+        .map((c) => c.trim())
+        .find((c) => c.startsWith('coco_participant_id='))
+        ?.split('=')[1]
+)
+
 interface Election {
     id: number
     name: string
+    translations: Partial<Record<Locale, { name: string }>>
+    status: ElectionStatus
+    motion: Motion | null
     ballotItemsById: Record<number, BallotItem>
 }
 
+type SpeakerStance = 'neutral' | 'pro' | 'contra'
+
+const speakerStanceKeys: Record<SpeakerStance, MessageKey> = {
+    neutral: 'stanceNeutral',
+    pro: 'stancePro',
+    contra: 'stanceContra',
+}
+
+interface SpeakerRequest {
+    id: number
+    participantId: number
+    stance: SpeakerStance
+    pointOfOrder: boolean
+    status:
+        | { kind: 'waiting' | 'withdrawn' }
+        | { kind: 'speaking'; startedAt: string; speakingDeadline: string | null }
+        | { kind: 'finished'; startedAt: string; finishedAt: string }
+}
+
+interface SpeakersList {
+    currentSpeaker: SpeakerRequest | null
+    waiting: SpeakerRequest[]
+    finished: SpeakerRequest[]
+}
+
+interface AgendaDocument {
+    title: string
+    url: string
+}
+
+interface AgendaItem {
+    id: number
+    number: string
+    title: string
+    description: string
+    documents: AgendaDocument[]
+    elections: Election[]
+}
+
+interface AgendaSession {
+    id: number
+    title: string
+    description: string
+    agendaItems: AgendaItem[]
+}
+
+interface ElectionsByAgenda {
+    sessions: AgendaSession[]
+    unscheduledElections: Election[]
+}
+
+interface ApiError {
+    code: string
+    message: string
+    details: unknown
+}
+
+interface EventSummary {
+    slug: string
+    name: string
+}
+
+let participantIdElement = document.getElementById('participant-id')
+if (participantIdElement instanceof HTMLParagraphElement) {
+    participantIdElement.textContent = t('ownParticipantNumber', {
+        id: ownParticipantId,
+    })
+}
+
+fetch('/api/v1/event')
+    .then((response) => (response.ok ? response.json() : null))
+    .then((event: EventSummary | null) => {
+        let eventNameElement = document.getElementById('event-name')
+        if (event !== null && eventNameElement instanceof HTMLParagraphElement) {
+            eventNameElement.textContent = event.name
+        }
+    })
+
 const intervalId = setInterval(updateAndRenderElections, 5000)
 // AI told me to add this clean-up
 window.addEventListener('beforeunload', () => {
@@ -27,19 +179,22 @@ await updateAndRenderElections()
 
 async function updateAndRenderElections() {
     try {
-        const electionsResponse = await fetch('/elections')
+        const electionsResponse = await fetch('/api/v1/elections')
 
         if (electionsResponse.ok) {
-            const electionsById: Record<number, Election> =
+            const electionsByAgenda: ElectionsByAgenda =
                 await electionsResponse.json()
 
             let electionsDiv = document.getElementById('elections')
-            electionsDiv?.replaceChildren()
-
-            Object.entries(electionsById)
-                .sort()
-                .map(([_, value]) => value)
-                .forEach(createElectionForm)
+            // Re-rendering would throw away what is being typed into a nomination or speaker form.
+            const isTyping =
+                ((document.activeElement instanceof HTMLInputElement &&
+                    document.activeElement.type !== 'radio') ||
+                    document.activeElement instanceof HTMLSelectElement) &&
+                electionsDiv?.contains(document.activeElement)
+            if (electionsDiv instanceof HTMLDivElement && !isTyping) {
+                renderAgenda(electionsDiv, electionsByAgenda)
+            }
         } else if (electionsResponse.status === 401) {
             window.location.href = '/login'
         } else {
@@ -47,8 +202,9 @@ async function updateAndRenderElections() {
                 'elections-error-message'
             )
             if (errorMessageElement instanceof HTMLParagraphElement) {
-                errorMessageElement.innerHTML =
-                    'Unexpected error: ' + electionsResponse.status
+                errorMessageElement.textContent = t('unexpectedError', {
+                    status: electionsResponse.status,
+                })
             }
         }
     } catch (error) {
@@ -56,25 +212,103 @@ async function updateAndRenderElections() {
             'elections-error-message'
         )
         if (errorMessageElement instanceof HTMLParagraphElement) {
-            errorMessageElement.innerHTML =
-                'Error: could not load election data.'
+            errorMessageElement.textContent = t('couldNotLoadElections')
         }
     }
 }
 
-function createElectionForm(election: Election) {
-    let electionsDiv = document.getElementById('elections')
+function renderAgenda(
+    electionsDiv: HTMLDivElement,
+    electionsByAgenda: ElectionsByAgenda
+) {
+    electionsDiv.replaceChildren()
+
+    for (const session of electionsByAgenda.sessions) {
+        let sessionHeading = document.createElement('h2')
+        sessionHeading.textContent = session.title
+        electionsDiv.appendChild(sessionHeading)
 
+        if (session.description.length > 0) {
+            let sessionDescription = document.createElement('p')
+            sessionDescription.textContent = session.description
+            electionsDiv.appendChild(sessionDescription)
+        }
+
+        for (const agendaItem of session.agendaItems) {
+            let agendaItemHeading = document.createElement('h3')
+            agendaItemHeading.textContent = `${agendaItem.number} ${agendaItem.title}`
+            electionsDiv.appendChild(agendaItemHeading)
+
+            if (agendaItem.description.length > 0) {
+                let agendaItemDescription = document.createElement('p')
+                agendaItemDescription.textContent = agendaItem.description
+                electionsDiv.appendChild(agendaItemDescription)
+            }
+
+            if (agendaItem.documents.length > 0) {
+                let documentList = document.createElement('ul')
+                for (const agendaDocument of agendaItem.documents) {
+                    let documentLink = document.createElement('a')
+                    documentLink.href = agendaDocument.url
+                    documentLink.target = '_blank'
+                    documentLink.rel = 'noopener noreferrer'
+                    documentLink.textContent = agendaDocument.title
+
+                    let documentListItem = document.createElement('li')
+                    documentListItem.appendChild(documentLink)
+                    documentList.appendChild(documentListItem)
+                }
+                electionsDiv.appendChild(documentList)
+            }
+
+            let speakersDiv = document.createElement('div')
+            electionsDiv.appendChild(speakersDiv)
+            renderSpeakers(speakersDiv, agendaItem.id)
+
+            agendaItem.elections.forEach((election) =>
+                createElectionForm(electionsDiv, election)
+            )
+        }
+    }
+
+    if (electionsByAgenda.unscheduledElections.length > 0) {
+        let otherHeading = document.createElement('h2')
+        otherHeading.textContent = t('otherElections')
+        electionsDiv.appendChild(otherHeading)
+
+        electionsByAgenda.unscheduledElections.forEach((election) =>
+            createElectionForm(electionsDiv, election)
+        )
+    }
+}
+
+function createElectionForm(electionsDiv: HTMLDivElement, election: Election) {
     const electionFormId = `election-${election.id}`
 
     let electionLabel = document.createElement('label')
     electionLabel.htmlFor = electionFormId
-    electionLabel.textContent = election.name
-    electionsDiv?.appendChild(electionLabel)
+    electionLabel.textContent = translatedName(election)
+    electionsDiv.appendChild(electionLabel)
 
     let electionForm = document.createElement('form')
     electionForm.id = electionFormId
-    electionsDiv?.appendChild(electionForm)
+    electionsDiv.appendChild(electionForm)
+
+    if (election.motion !== null) {
+        electionForm.appendChild(createMotionText(election.motion))
+    }
+
+    if (election.status.kind === 'nominating') {
+        renderNominations(electionForm, election)
+        return
+    }
+
+    if (election.status.kind === 'awaitingAmendments') {
+        let waitingMessage = document.createElement('p')
+        waitingMessage.textContent = t('awaitingAmendments')
+        electionForm.appendChild(waitingMessage)
+        return
+    }
 
     const submitButtonId = `election-${election.id}-submit`
     const radioButtonGroupName = `election-${election.id}-selected-ballot-item-id`
@@ -99,12 +333,22 @@ function createElectionForm(election: Election) {
             })
 
             let label = document.createElement('label')
-            label.textContent = ballotItem.name
+            label.textContent =
+                ballotItem.affiliation.length > 0
+                    ? `${translatedName(ballotItem)} (${ballotItem.affiliation})`
+                    : translatedName(ballotItem)
             label.htmlFor = ballotItemInputId
 
             electionForm.appendChild(ballotItemInput)
             electionForm.appendChild(label)
             electionForm.appendChild(document.createElement('br'))
+
+            if (
+                ballotItem.description.length > 0 ||
+                ballotItem.attachments.length > 0
+            ) {
+                electionForm.appendChild(createBallotItemDetails(ballotItem))
+            }
         })
 
     let electionIdHiddenInput = document.createElement('input')
@@ -116,7 +360,7 @@ function createElectionForm(election: Election) {
     let submitButton = document.createElement('input')
     submitButton.id = submitButtonId
     submitButton.type = 'submit'
-    submitButton.value = 'Submit'
+    submitButton.value = t('submit')
     submitButton.disabled = true
     electionForm.appendChild(submitButton)
 
@@ -125,6 +369,13 @@ function createElectionForm(election: Election) {
     electionMessageElement.id = electionMessageElementId
     electionForm.appendChild(electionMessageElement)
 
+    if (election.status.kind === 'closed') {
+        for (let inputElement of electionForm.querySelectorAll('input')) {
+            inputElement.disabled = true
+        }
+        electionMessageElement.textContent = t('electionClosed')
+    }
+
     electionForm.addEventListener('submit', async (event) => {
         event.preventDefault()
 
@@ -135,7 +386,7 @@ function createElectionForm(election: Election) {
             selectedBallotItemId: Number(dataObject[radioButtonGroupName]),
         }
 
-        const response = await fetch('/elections/vote', {
+        const response = await fetch('/api/v1/votes', {
             method: 'POST',
             headers: {
                 'Content-Type': 'application/json',
@@ -157,7 +408,7 @@ function createElectionForm(election: Election) {
                 electionMessageElementId
             )
             if (errorMessageElement instanceof HTMLParagraphElement) {
-                errorMessageElement.innerHTML = 'Voting successful.'
+                errorMessageElement.textContent = t('votingSuccessful')
             }
         } else if (response.status === 401) {
             window.location.href = '/login'
@@ -166,17 +417,425 @@ function createElectionForm(election: Election) {
                 electionMessageElementId
             )
             if (errorMessageElement instanceof HTMLParagraphElement) {
-                const responseText = await response.text()
-                errorMessageElement.innerHTML = 'Error: ' + responseText
+                const apiError: ApiError = await response.json()
+                errorMessageElement.textContent = t('error', {
+                    message: apiError.message,
+                })
             }
         } else if (!response.ok) {
             let errorMessageElement = document.getElementById(
                 electionMessageElementId
             )
             if (errorMessageElement instanceof HTMLParagraphElement) {
-                errorMessageElement.innerHTML =
-                    'Unexpected error: ' + response.status
+                errorMessageElement.textContent = t('unexpectedError', {
+                    status: response.status,
+                })
+            }
+        }
+    })
+}
+
+async function renderNominations(
+    electionForm: HTMLFormElement,
+    election: Election
+) {
+    let messageElement = document.createElement('p')
+    messageElement.textContent = t('candidatesBeingNominated')
+    electionForm.appendChild(messageElement)
+
+    const response = await fetch(`/api/v1/elections/${election.id}/nominations`)
+    if (!response.ok) {
+        return
+    }
+    const nominations: Nomination[] = await response.json()
+
+    let nominationsList = document.createElement('ul')
+    for (const nomination of nominations) {
+        let listItem = document.createElement('li')
+        listItem.textContent =
+            t('nominationSummary', {
+                name: nomination.candidateName,
+                seconders: nomination.secondedBy.length,
+                response: t(candidateResponseKeys[nomination.candidateResponse]),
+            }) + ' '
+
+        if (nomination.candidateParticipantId === ownParticipantId) {
+            listItem.appendChild(
+                createNominationActionButton(nomination, 'accept', t('accept'), messageElement)
+            )
+            listItem.appendChild(
+                createNominationActionButton(nomination, 'decline', t('decline'), messageElement)
+            )
+        }
+
+        if (
+            nomination.nominatedBy !== ownParticipantId &&
+            !nomination.secondedBy.includes(ownParticipantId)
+        ) {
+            listItem.appendChild(
+                createNominationActionButton(nomination, 'second', t('second'), messageElement)
+            )
+        }
+
+        nominationsList.appendChild(listItem)
+    }
+    electionForm.appendChild(nominationsList)
+
+    let candidateNameInput = document.createElement('input')
+    candidateNameInput.type = 'text'
+    candidateNameInput.placeholder = t('candidateName')
+    electionForm.appendChild(candidateNameInput)
+
+    let candidateIdInput = document.createElement('input')
+    candidateIdInput.type = 'number'
+    candidateIdInput.min = '0'
+    candidateIdInput.placeholder = t('participantNumber')
+    electionForm.appendChild(candidateIdInput)
+
+    let nominateButton = document.createElement('input')
+    nominateButton.type = 'submit'
+    nominateButton.value = t('nominate')
+    electionForm.appendChild(nominateButton)
+
+    electionForm.addEventListener('submit', async (event) => {
+        event.preventDefault()
+
+        const response = await fetch(
+            `/api/v1/elections/${election.id}/nominations`,
+            {
+                method: 'POST',
+                headers: {
+                    'Content-Type': 'application/json',
+                },
+                body: JSON.stringify({
+                    candidateName: candidateNameInput.value,
+                    candidateParticipantId: Number(candidateIdInput.value),
+                }),
             }
+        )
+        await showNominationResult(response, messageElement)
+    })
+}
+
+function createNominationActionButton(
+    nomination: Nomination,
+    action: 'second' | 'accept' | 'decline',
+    text: string,
+    messageElement: HTMLParagraphElement
+): HTMLButtonElement {
+    let button = document.createElement('button')
+    button.textContent = text
+    button.addEventListener('click', async (event) => {
+        event.preventDefault()
+
+        const response = await fetch(
+            `/api/v1/nominations/${nomination.id}/${action}`,
+            { method: 'POST' }
+        )
+        await showNominationResult(response, messageElement)
+    })
+
+    return button
+}
+
+async function showNominationResult(
+    response: Response,
+    messageElement: HTMLParagraphElement
+) {
+    if (response.ok) {
+        if (document.activeElement instanceof HTMLElement) {
+            document.activeElement.blur()
+        }
+        await updateAndRenderElections()
+    } else if (response.status === 401) {
+        window.location.href = '/login'
+    } else {
+        const apiError: ApiError = await response.json()
+        messageElement.textContent = t('error', { message: apiError.message })
+    }
+}
+
+function createMotionText(motion: Motion): HTMLDivElement {
+    let motionDiv = document.createElement('div')
+    motionDiv.className = 'motion'
+
+    if (motion.kind === 'main') {
+        if (motion.consolidatedText === null) {
+            motionDiv.append(...renderMarkdown(motion.text))
+        } else {
+            let heading = document.createElement('p')
+            heading.textContent = t('finalMotionText')
+            motionDiv.appendChild(heading)
+            motionDiv.append(...renderMarkdown(motion.consolidatedText))
+        }
+        return motionDiv
+    }
+
+    let amendmentDescription = document.createElement('p')
+    amendmentDescription.textContent = t('amendsElection', {
+        id: motion.parentElectionId,
+    })
+    motionDiv.appendChild(amendmentDescription)
+
+    let originalText = document.createElement('del')
+    originalText.textContent = motion.originalText
+    let replacementText = document.createElement('ins')
+    replacementText.textContent = motion.replacementText
+
+    let change = document.createElement('p')
+    change.append(originalText, ' ', replacementText)
+    motionDiv.appendChild(change)
+
+    return motionDiv
+}
+
+function createBallotItemDetails(ballotItem: BallotItem): HTMLDivElement {
+    let detailsDiv = document.createElement('div')
+    detailsDiv.className = 'ballot-item-details'
+
+    for (const attachment of ballotItem.attachments) {
+        if (attachment.contentType.startsWith('image/')) {
+            let image = document.createElement('img')
+            image.src = attachment.url
+            image.alt = translatedName(ballotItem)
+            image.style.maxWidth = '10em'
+            detailsDiv.appendChild(image)
+        } else {
+            let attachmentLink = document.createElement('a')
+            attachmentLink.href = attachment.url
+            attachmentLink.target = '_blank'
+            attachmentLink.rel = 'noopener noreferrer'
+            attachmentLink.textContent = t('document', {
+                size: Math.ceil(attachment.size / 1024),
+            })
+            detailsDiv.appendChild(attachmentLink)
+        }
+    }
+
+    detailsDiv.append(...renderMarkdown(translatedDescription(ballotItem)))
+
+    return detailsDiv
+}
+
+// A small Markdown subset, built from DOM nodes so that no HTML in the text is ever interpreted:
+// headings, bullet and numbered lists, paragraphs, **bold**, *italic*, `code` and links.
+function renderMarkdown(markdown: string): HTMLElement[] {
+    const blocks = markdown
+        .replace(/\r\n/g, '\n')
+        .split(/\n\s*\n/)
+        .map((block) => block.trim())
+        .filter((block) => block.length > 0)
+
+    return blocks.map((block) => {
+        const lines = block.split('\n')
+
+        const heading = /^(#{1,3})\s+(.*)$/.exec(block)
+        if (heading !== null && lines.length === 1) {
+            let headingElement = document.createElement(
+                `h${(heading[1]?.length ?? 1) + 3}`
+            )
+            headingElement.append(...renderInlineMarkdown(heading[2] ?? ''))
+            return headingElement
+        }
+
+        if (lines.every((line) => /^[-*]\s+/.test(line))) {
+            return renderMarkdownList(document.createElement('ul'), lines, /^[-*]\s+/)
+        }
+
+        if (lines.every((line) => /^\d+\.\s+/.test(line))) {
+            return renderMarkdownList(document.createElement('ol'), lines, /^\d+\.\s+/)
+        }
+
+        let paragraph = document.createElement('p')
+        lines.forEach((line, index) => {
+            if (index > 0) {
+                paragraph.appendChild(document.createElement('br'))
+            }
+            paragraph.append(...renderInlineMarkdown(line))
+        })
+        return paragraph
+    })
+}
+
+function renderMarkdownList(
+    list: HTMLUListElement | HTMLOListElement,
+    lines: string[],
+    marker: RegExp
+): HTMLElement {
+    for (const line of lines) {
+        let listItem = document.createElement('li')
+        listItem.append(...renderInlineMarkdown(line.replace(marker, '')))
+        list.appendChild(listItem)
+    }
+    return list
+}
+
+function renderInlineMarkdown(text: string): Node[] {
+    const tokens = text.split(
+        /(\*\*[^*]+\*\*|\*[^*]+\*|`[^`]+`|\[[^\]]+\]\([^)\s]+\))/
+    )
+
+    return tokens
+        .filter((token) => token.length > 0)
+        .map((token) => {
+            if (token.startsWith('**') && token.endsWith('**') && token.length > 4) {
+                let strong = document.createElement('strong')
+                strong.textContent = token.slice(2, -2)
+                return strong
+            }
+
+            if (token.startsWith('`') && token.endsWith('`') && token.length > 2) {
+                let code = document.createElement('code')
+                code.textContent = token.slice(1, -1)
+                return code
+            }
+
+            if (token.startsWith('*') && token.endsWith('*') && token.length > 2) {
+                let emphasis = document.createElement('em')
+                emphasis.textContent = token.slice(1, -1)
+                return emphasis
+            }
+
+            const link = /^\[([^\]]+)\]\(([^)\s]+)\)$/.exec(token)
+            const url = link?.[2] ?? ''
+            // Only web links, never `javascript:` or the like.
+            if (link !== null && /^(https?:\/\/|\/(?!\/))/.test(url)) {
+                let anchor = document.createElement('a')
+                anchor.href = url
+                anchor.target = '_blank'
+                anchor.rel = 'noopener noreferrer'
+                anchor.textContent = link[1] ?? url
+                return anchor
+            }
+
+            return document.createTextNode(token)
+        })
+}
+
+function describeSpeaker(speakerRequest: SpeakerRequest): string {
+    let description = t('participant', { id: speakerRequest.participantId })
+    if (speakerRequest.participantId === ownParticipantId) {
+        description += ' ' + t('ownSpeakerRequest')
+    }
+    if (speakerRequest.stance !== 'neutral') {
+        description += ', ' + t(speakerStanceKeys[speakerRequest.stance])
+    }
+    if (speakerRequest.pointOfOrder) {
+        description += ', ' + t('speakerPointOfOrder')
+    }
+    return description
+}
+
+async function renderSpeakers(speakersDiv: HTMLDivElement, agendaItemId: number) {
+    const response = await fetch(`/api/v1/agenda/items/${agendaItemId}/speakers`)
+    if (!response.ok) {
+        return
+    }
+    const speakersList: SpeakersList = await response.json()
+
+    let currentSpeakerParagraph = document.createElement('p')
+    const currentSpeaker = speakersList.currentSpeaker
+    if (currentSpeaker === null) {
+        currentSpeakerParagraph.textContent = t('nobodyHasTheFloor')
+    } else {
+        currentSpeakerParagraph.textContent = t('speaking', {
+            speaker: describeSpeaker(currentSpeaker),
+        })
+        if (
+            currentSpeaker.status.kind === 'speaking' &&
+            currentSpeaker.status.speakingDeadline !== null
+        ) {
+            const remainingSeconds = Math.round(
+                (new Date(currentSpeaker.status.speakingDeadline).getTime() -
+                    Date.now()) /
+                    1000
+            )
+            currentSpeakerParagraph.textContent +=
+                ' ' +
+                (remainingSeconds >= 0
+                    ? t('secondsLeft', { seconds: remainingSeconds })
+                    : t('secondsOverTime', { seconds: -remainingSeconds }))
+        }
+    }
+    speakersDiv.appendChild(currentSpeakerParagraph)
+
+    let waitingList = document.createElement('ol')
+    let ownWaitingRequest: SpeakerRequest | undefined
+    for (const speakerRequest of speakersList.waiting) {
+        let listItem = document.createElement('li')
+        listItem.textContent = describeSpeaker(speakerRequest)
+
+        if (speakerRequest.participantId === ownParticipantId) {
+            ownWaitingRequest = speakerRequest
+            let withdrawButton = document.createElement('button')
+            withdrawButton.textContent = t('withdraw')
+            withdrawButton.addEventListener('click', async () => {
+                await fetch(`/api/v1/speaker-requests/${speakerRequest.id}`, {
+                    method: 'DELETE',
+                })
+                await updateAndRenderElections()
+            })
+            listItem.append(' ', withdrawButton)
+        }
+
+        waitingList.appendChild(listItem)
+    }
+    speakersDiv.appendChild(waitingList)
+
+    if (
+        ownWaitingRequest !== undefined ||
+        currentSpeaker?.participantId === ownParticipantId
+    ) {
+        return
+    }
+
+    let requestForm = document.createElement('form')
+
+    let stanceSelect = document.createElement('select')
+    stanceSelect.name = 'stance'
+    for (const [value, key] of Object.entries(speakerStanceKeys)) {
+        let option = document.createElement('option')
+        option.value = value
+        option.textContent = t(key)
+        stanceSelect.appendChild(option)
+    }
+
+    let pointOfOrderLabel = document.createElement('label')
+    let pointOfOrderCheckbox = document.createElement('input')
+    pointOfOrderCheckbox.type = 'checkbox'
+    pointOfOrderCheckbox.name = 'pointOfOrder'
+    pointOfOrderLabel.append(pointOfOrderCheckbox, ` ${t('pointOfOrder')} `)
+
+    let submitButton = document.createElement('input')
+    submitButton.type = 'submit'
+    submitButton.value = t('requestToSpeak')
+
+    let message = document.createElement('p')
+
+    requestForm.append(stanceSelect, ' ', pointOfOrderLabel, submitButton, message)
+    requestForm.addEventListener('submit', async (event) => {
+        event.preventDefault()
+
+        const requestResponse = await fetch(
+            `/api/v1/agenda/items/${agendaItemId}/speakers`,
+            {
+                method: 'POST',
+                headers: {
+                    'Content-Type': 'application/json',
+                },
+                body: JSON.stringify({
+                    stance: stanceSelect.value,
+                    pointOfOrder: pointOfOrderCheckbox.checked,
+                }),
+            }
+        )
+
+        if (requestResponse.ok) {
+            await updateAndRenderElections()
+        } else {
+            const apiError: ApiError = await requestResponse.json()
+            message.textContent = t('error', { message: apiError.message })
         }
     })
+    speakersDiv.appendChild(requestForm)
 }
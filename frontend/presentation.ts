@@ -0,0 +1,249 @@
+import {
+    type Locale,
+    localizePage,
+    t,
+    translatedName,
+} from './i18n.js'
+
+localizePage()
+
+// The projector is not logged in as a participant. Instead, an admin opens
+// /presentation?token=<api token with the readElections scope>.
+const bearerToken = new URLSearchParams(window.location.search).get('token')
+
+interface PresentedBallotItem {
+    id: number
+    name: string
+    translations: Partial<Record<Locale, { name: string }>>
+    numVotes: number | null
+}
+
+interface ElectionStatus {
+    kind: 'open' | 'closed'
+}
+
+interface Outcome {
+    kind: 'decided' | 'tie' | 'noMajority' | 'noVotes'
+    ballotItemId?: number
+    ballotItemIds?: number[]
+}
+
+interface PresentedElection {
+    id: number
+    name: string
+    translations: Partial<Record<Locale, { name: string }>>
+    status: ElectionStatus
+    votingDeadline: string | null
+    numVotesCast: number
+    numEligibleParticipants: number
+    ballotItems: PresentedBallotItem[]
+    results: { outcome: Outcome } | null
+}
+
+interface Presentation {
+    election: PresentedElection | null
+}
+
+interface ApiError {
+    code: string
+    message: string
+    details: unknown
+}
+
+let votingDeadline: Date | null = null
+let renderedElectionKey: string | null = null
+
+const pollIntervalId = setInterval(updateAndRenderPresentation, 1000)
+const countdownIntervalId = setInterval(renderCountdown, 250)
+window.addEventListener('beforeunload', () => {
+    clearInterval(pollIntervalId)
+    clearInterval(countdownIntervalId)
+})
+
+await updateAndRenderPresentation()
+
+async function updateAndRenderPresentation() {
+    let errorMessageElement = document.getElementById(
+        'presentation-error-message'
+    )
+
+    try {
+        const headers: Record<string, string> = {}
+        if (bearerToken !== null) {
+            headers['Authorization'] = 'Bearer ' + bearerToken
+        }
+
+        const response = await fetch('/api/v1/presentation', { headers })
+
+        if (response.ok) {
+            const presentation: Presentation = await response.json()
+            renderPresentation(presentation)
+            if (errorMessageElement instanceof HTMLParagraphElement) {
+                errorMessageElement.textContent = ''
+            }
+        } else if (errorMessageElement instanceof HTMLParagraphElement) {
+            const apiError: ApiError = await response.json()
+            errorMessageElement.textContent = t('error', {
+                message: apiError.message,
+            })
+        }
+    } catch (error) {
+        if (errorMessageElement instanceof HTMLParagraphElement) {
+            errorMessageElement.textContent = t('couldNotLoadPresentation')
+        }
+    }
+}
+
+function renderPresentation(presentation: Presentation) {
+    const election = presentation.election
+
+    let nameElement = document.getElementById('election-name')
+    let statusElement = document.getElementById('election-status')
+    let turnoutElement = document.getElementById('turnout')
+    let outcomeElement = document.getElementById('outcome')
+    let ballotItemsList = document.getElementById('ballot-items')
+
+    if (
+        !(nameElement instanceof HTMLHeadingElement) ||
+        !(statusElement instanceof HTMLParagraphElement) ||
+        !(turnoutElement instanceof HTMLParagraphElement) ||
+        !(outcomeElement instanceof HTMLParagraphElement) ||
+        !(ballotItemsList instanceof HTMLUListElement)
+    ) {
+        return
+    }
+
+    if (election === null) {
+        nameElement.textContent = t('noElectionYet')
+        statusElement.textContent = ''
+        turnoutElement.textContent = ''
+        outcomeElement.textContent = ''
+        ballotItemsList.replaceChildren()
+        votingDeadline = null
+        renderedElectionKey = null
+        return
+    }
+
+    nameElement.textContent = translatedName(election)
+    statusElement.textContent =
+        election.status.kind === 'open' ? t('votingOpen') : t('votingClosed')
+    turnoutElement.textContent = t('turnout', {
+        cast: election.numVotesCast,
+        eligible: election.numEligibleParticipants,
+    })
+    votingDeadline =
+        election.status.kind === 'open' && election.votingDeadline !== null
+            ? new Date(election.votingDeadline)
+            : null
+    renderCountdown()
+
+    // Only rebuild the list when the election or the result visibility
+    // changes, so that the bars can animate from zero to their final width.
+    const electionKey = `${election.id}-${election.results !== null}`
+    if (electionKey !== renderedElectionKey) {
+        renderedElectionKey = electionKey
+        renderBallotItems(ballotItemsList, election)
+    }
+
+    outcomeElement.textContent =
+        election.results === null
+            ? ''
+            : describeOutcome(election.results.outcome, election.ballotItems)
+}
+
+function renderBallotItems(
+    ballotItemsList: HTMLUListElement,
+    election: PresentedElection
+) {
+    ballotItemsList.replaceChildren()
+
+    const winnerIds = winningBallotItemIds(election.results?.outcome)
+    const numVotesCast = Math.max(election.numVotesCast, 1)
+
+    for (const ballotItem of election.ballotItems) {
+        let listItem = document.createElement('li')
+
+        let label = document.createElement('div')
+        label.textContent =
+            ballotItem.numVotes === null
+                ? translatedName(ballotItem)
+                : `${translatedName(ballotItem)}: ${ballotItem.numVotes}`
+        listItem.appendChild(label)
+
+        if (ballotItem.numVotes !== null) {
+            let barTrack = document.createElement('div')
+            barTrack.className = 'bar-track'
+
+            let bar = document.createElement('div')
+            bar.className = winnerIds.includes(ballotItem.id)
+                ? 'bar winner'
+                : 'bar'
+            barTrack.appendChild(bar)
+            listItem.appendChild(barTrack)
+
+            const widthPercent = (100 * ballotItem.numVotes) / numVotesCast
+            requestAnimationFrame(() => {
+                requestAnimationFrame(() => {
+                    bar.style.width = `${widthPercent}%`
+                })
+            })
+        }
+
+        ballotItemsList.appendChild(listItem)
+    }
+}
+
+function winningBallotItemIds(outcome: Outcome | undefined): number[] {
+    if (outcome?.kind === 'decided' && outcome.ballotItemId !== undefined) {
+        return [outcome.ballotItemId]
+    }
+    if (outcome?.kind === 'tie' && outcome.ballotItemIds !== undefined) {
+        return outcome.ballotItemIds
+    }
+    return []
+}
+
+function describeOutcome(
+    outcome: Outcome,
+    ballotItems: PresentedBallotItem[]
+): string {
+    const nameOf = (id: number) => {
+        const ballotItem = ballotItems.find((ballotItem) => ballotItem.id === id)
+        return ballotItem === undefined ? '?' : translatedName(ballotItem)
+    }
+
+    switch (outcome.kind) {
+        case 'decided':
+            return t('outcomeDecided', {
+                name: nameOf(outcome.ballotItemId ?? -1),
+            })
+        case 'tie':
+            return t('outcomeTie', {
+                names: (outcome.ballotItemIds ?? []).map(nameOf).join(', '),
+            })
+        case 'noMajority':
+            return t('outcomeNoMajority')
+        case 'noVotes':
+            return t('outcomeNoVotes')
+    }
+}
+
+function renderCountdown() {
+    let countdownElement = document.getElementById('countdown')
+    if (!(countdownElement instanceof HTMLParagraphElement)) {
+        return
+    }
+
+    if (votingDeadline === null) {
+        countdownElement.textContent = ''
+        return
+    }
+
+    const remainingSeconds = Math.max(
+        0,
+        Math.ceil((votingDeadline.getTime() - Date.now()) / 1000)
+    )
+    const minutes = Math.floor(remainingSeconds / 60)
+    const seconds = remainingSeconds % 60
+    countdownElement.textContent = `${minutes}:${seconds.toString().padStart(2, '0')}`
+}
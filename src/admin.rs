@@ -1,20 +1,26 @@
-use http_body_util::{BodyExt, Full};
-use hyper::{Request, Response, StatusCode, body::Bytes, header::SET_COOKIE};
-use tokio::sync::{mpsc, oneshot};
-use tracing::{error, info, warn};
+use std::fmt;
+
+use http_body_util::Full;
+use hyper::{Response, StatusCode, body::Bytes, header::SET_COOKIE};
 
 use crate::{
-    common::{
-        ResponseResult, bad_request_response, extract_requesting_admin_session,
-        internal_error_response, ok_response, unauthorized_response,
-    },
-    state::Message,
+    agenda::AgendaItemId,
+    api_token::ApiTokenScope,
+    attachment::AttachmentId,
+    common::{HandlerResult, json_response, ok_response},
+    election::{ElectionId, Eligibility, VotingMethod},
+    error::{AdminAccountError, ElectionsManageError},
+    i18n::{BallotItemTranslation, ElectionTranslation, Locale, Translations},
+    nomination::NominationSettings,
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
 };
 
 pub const ADMIN_SESSION_ID_COOKIE_KEY: &str = "coco_admin_session_id";
 pub const ADMIN_TOKEN_COOKIE_KEY: &str = "coco_admin_token";
 
 pub type AdminSessionId = usize;
+pub type AdminAccountId = usize;
 
 #[derive(Debug, Clone)]
 pub struct AdminSession {
@@ -22,64 +28,219 @@ pub struct AdminSession {
     pub token: String,
 }
 
-#[derive(Debug, serde::Deserialize)]
-struct AdminLoginAttemptBody {
-    password: String,
+/// What the admins of an account may do.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum AdminRole {
+    /// Everything, including managing admin accounts, API tokens and events.
+    SuperAdmin,
+    /// Creating, closing and publishing elections and managing the agenda.
+    ElectionOfficer,
+    /// Managing participants at the registration desk.
+    RegistrationDesk,
+    /// Watching elections and their results without changing anything.
+    Observer,
 }
 
-#[derive(Debug, serde::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct AdminCreateElectionBody {
-    pub name: String,
-    pub ballot_items: Vec<String>,
-}
-
-pub async fn start_session(
-    request: Request<hyper::body::Incoming>,
-    to_central_state_authority_sender: mpsc::Sender<Message>,
-) -> ResponseResult {
-    let body_bytes = match request.into_body().collect().await {
-        Ok(collected) => collected.to_bytes(),
-        Err(e) => {
-            error!("{e:?}");
-            return internal_error_response();
+impl AdminRole {
+    pub fn grants(self, scope: ApiTokenScope) -> bool {
+        match self {
+            AdminRole::SuperAdmin => true,
+            AdminRole::ElectionOfficer => matches!(
+                scope,
+                ApiTokenScope::ReadElections
+                    | ApiTokenScope::ReadResults
+                    | ApiTokenScope::ManageElections
+            ),
+            AdminRole::RegistrationDesk => matches!(
+                scope,
+                ApiTokenScope::ReadElections | ApiTokenScope::ManageParticipants
+            ),
+            AdminRole::Observer => matches!(
+                scope,
+                ApiTokenScope::ReadElections | ApiTokenScope::ReadResults
+            ),
         }
-    };
+    }
+}
 
-    let body: AdminLoginAttemptBody = match serde_json::from_slice(&body_bytes) {
-        Ok(body) => body,
-        Err(e) => {
-            warn!("Bad request: {e:?}");
-            return bad_request_response();
-        }
-    };
+#[derive(Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminAccount {
+    pub id: AdminAccountId,
+    pub username: String,
+    pub role: AdminRole,
+    /// Argon2 hash in the PHC string format, with its salt.
+    #[serde(skip)]
+    pub password_hash: String,
+}
+
+impl fmt::Debug for AdminAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdminAccount")
+            .field("id", &self.id)
+            .field("username", &self.username)
+            .field("role", &self.role)
+            .finish_non_exhaustive()
+    }
+}
 
-    info!("{body:?}");
+#[derive(Clone, serde::Deserialize, schemars::JsonSchema)]
+pub struct AdminLoginAttemptBody {
+    pub username: String,
+    pub password: String,
+}
+
+/// Request bodies are logged, passwords must not be.
+impl fmt::Debug for AdminLoginAttemptBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdminLoginAttemptBody")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, serde::Deserialize, schemars::JsonSchema)]
+pub struct AdminCreateAccountBody {
+    pub username: String,
+    pub password: String,
+    pub role: AdminRole,
+}
 
-    const ADMIN_PASSWORD: &str = "abcd";
-    if body.password != ADMIN_PASSWORD {
-        return unauthorized_response();
+impl fmt::Debug for AdminCreateAccountBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdminCreateAccountBody")
+            .field("username", &self.username)
+            .field("role", &self.role)
+            .finish_non_exhaustive()
     }
+}
 
-    let (answer_sender, answer_receiver) = oneshot::channel();
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminCreateElectionBody {
+    pub name: String,
+    /// The name in other languages than the default one.
+    #[serde(default)]
+    pub translations: Translations<ElectionTranslation>,
+    /// Their ids are assigned in the given order, starting at 0.
+    pub ballot_items: Vec<AdminBallotItemBody>,
+    #[serde(default)]
+    pub method: VotingMethod,
+    #[serde(default)]
+    pub eligibility: Eligibility,
+    /// If set, voting closes automatically after this many seconds.
+    #[serde(default)]
+    pub duration_seconds: Option<u64>,
+    /// The agenda item the election is listed under.
+    #[serde(default)]
+    pub agenda_item_id: Option<AgendaItemId>,
+    /// If set, the election starts with a nomination phase. Accepted, approved and seconded
+    /// nominations are added to `ballot_items` when an admin opens the voting.
+    #[serde(default)]
+    pub nominations: Option<NominationSettings>,
+    /// If set, the election decides on a motion or an amendment to one. Its first ballot
+    /// item approves it; without ballot items, "Yes" and "No" are used.
+    #[serde(default)]
+    pub motion: Option<AdminMotionBody>,
+}
 
-    if let Err(e) = to_central_state_authority_sender
-        .send(Message::AdminStartSession { answer_sender })
-        .await
-    {
-        error!("{e:?}");
-        return internal_error_response();
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum AdminMotionBody {
+    /// Voting is opened by an admin once all amendments are closed.
+    Main { text: String },
+    /// `original_text` has to be part of the text of the parent motion.
+    Amendment {
+        parent_election_id: ElectionId,
+        original_text: String,
+        replacement_text: String,
+    },
+}
+
+/// A ballot item is given either by its name alone or with details.
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum AdminBallotItemBody {
+    Name(String),
+    Detailed(AdminDetailedBallotItemBody),
+}
+
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminDetailedBallotItemBody {
+    pub name: String,
+    /// Markdown.
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub affiliation: String,
+    /// Previously uploaded attachments.
+    #[serde(default)]
+    pub attachment_ids: Vec<AttachmentId>,
+    /// The name and description in other languages than the default one.
+    #[serde(default)]
+    pub translations: Translations<BallotItemTranslation>,
+}
+
+impl AdminBallotItemBody {
+    /// A ballot item with nothing but a name and its translations.
+    pub fn translated(name: &str, translated_names: &[(Locale, &str)]) -> Self {
+        AdminBallotItemBody::Detailed(AdminDetailedBallotItemBody {
+            name: String::from(name),
+            description: String::new(),
+            affiliation: String::new(),
+            attachment_ids: Vec::new(),
+            translations: translated_names
+                .iter()
+                .map(|(locale, translated_name)| {
+                    (
+                        *locale,
+                        BallotItemTranslation {
+                            name: String::from(*translated_name),
+                            description: String::new(),
+                        },
+                    )
+                })
+                .collect(),
+        })
     }
+}
 
-    let new_admin_session = match answer_receiver.await {
-        Ok(new_admin_session) => new_admin_session,
-        Err(e) => {
-            error!("{e:?}");
-            return internal_error_response();
+impl From<AdminBallotItemBody> for AdminDetailedBallotItemBody {
+    fn from(admin_ballot_item_body: AdminBallotItemBody) -> Self {
+        match admin_ballot_item_body {
+            AdminBallotItemBody::Name(name) => Self {
+                name,
+                description: String::new(),
+                affiliation: String::new(),
+                attachment_ids: Vec::new(),
+                translations: Translations::new(),
+            },
+            AdminBallotItemBody::Detailed(detailed) => detailed,
         }
-    };
+    }
+}
 
-    Response::builder()
+pub async fn start_session(mut route_request: RouteRequest) -> HandlerResult {
+    let body = route_request.take_extension::<AdminLoginAttemptBody>()?;
+
+    let new_admin_session = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminStartSession {
+            answer_sender,
+            admin_login_attempt_body: body,
+        })
+        .await??;
+
+    Ok(Response::builder()
         .header(
             SET_COOKIE,
             format!(
@@ -95,61 +256,150 @@ pub async fn start_session(
             ),
         )
         .status(StatusCode::CREATED)
-        .body(Full::new(Bytes::new()))
+        .body(Full::new(Bytes::new()))?)
+}
+
+pub async fn create_election(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let body = route_request.take_extension::<AdminCreateElectionBody>()?;
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminCreateElection {
+            answer_sender,
+            requesting_credentials,
+            admin_create_election_body: body,
+        })
+        .await??;
+
+    ok_response()
 }
 
-pub async fn create_election(
-    request: Request<hyper::body::Incoming>,
-    to_central_state_authority_sender: mpsc::Sender<Message>,
-) -> ResponseResult {
-    let Some(requesting_admin_session) = extract_requesting_admin_session(&request) else {
-        return unauthorized_response();
+pub async fn close_election(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(election_id) = route_request.path_params.parse("election_id") else {
+        return Err(ElectionsManageError::MissingElection.into());
     };
 
-    let body_bytes = match request.into_body().collect().await {
-        Ok(collected) => collected.to_bytes(),
-        Err(e) => {
-            error!("{e:?}");
-            return internal_error_response();
-        }
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminCloseElection {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        })
+        .await??;
+
+    ok_response()
+}
+
+pub async fn open_election(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(election_id) = route_request.path_params.parse("election_id") else {
+        return Err(ElectionsManageError::MissingElection.into());
     };
 
-    let body: AdminCreateElectionBody = match serde_json::from_slice(&body_bytes) {
-        Ok(body) => body,
-        Err(e) => {
-            warn!("Bad request: {e:?}");
-            return bad_request_response();
-        }
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminOpenElection {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        })
+        .await??;
+
+    ok_response()
+}
+
+pub async fn publish_results(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(election_id) = route_request.path_params.parse("election_id") else {
+        return Err(ElectionsManageError::MissingElection.into());
     };
 
-    info!("{body:?}");
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminPublishResults {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        })
+        .await??;
+
+    ok_response()
+}
+
+pub async fn create_account(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_admin_session = route_request.take_extension::<AdminSession>()?;
+    let body = route_request.take_extension::<AdminCreateAccountBody>()?;
+
+    let admin_account = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminCreateAccount {
+            answer_sender,
+            requesting_admin_session,
+            admin_create_account_body: body,
+        })
+        .await??;
+
+    json_response(StatusCode::CREATED, &admin_account)
+}
 
-    let (answer_sender, answer_receiver) = oneshot::channel();
+pub async fn get_accounts(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_admin_session = route_request.take_extension::<AdminSession>()?;
 
-    if let Err(e) = to_central_state_authority_sender
-        .send(Message::AdminCreateElection {
+    let admin_accounts = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminGetAccounts {
             answer_sender,
             requesting_admin_session,
-            admin_create_election_body: body,
         })
-        .await
-    {
-        error!("{e:?}");
-        return internal_error_response();
-    }
+        .await??;
 
-    let answer = match answer_receiver.await {
-        Ok(answer) => answer,
-        Err(e) => {
-            error!("{e:?}");
-            return internal_error_response();
-        }
+    json_response(StatusCode::OK, &admin_accounts)
+}
+
+pub async fn get_own_account(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_admin_session = route_request.take_extension::<AdminSession>()?;
+
+    let admin_account = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminGetOwnAccount {
+            answer_sender,
+            requesting_admin_session,
+        })
+        .await??;
+
+    json_response(StatusCode::OK, &admin_account)
+}
+
+pub async fn delete_account(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_admin_session = route_request.take_extension::<AdminSession>()?;
+
+    let Some(admin_account_id) = route_request.path_params.parse("admin_account_id") else {
+        return Err(AdminAccountError::MissingAccount.into());
     };
 
-    match answer {
-        Ok(()) => ok_response(),
-        Err(err) => Response::builder()
-            .status(err.http_status_code())
-            .body(Full::new(Bytes::from_owner(err.to_string()))),
-    }
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminDeleteAccount {
+            answer_sender,
+            requesting_admin_session,
+            admin_account_id,
+        })
+        .await??;
+
+    ok_response()
 }
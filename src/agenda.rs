@@ -0,0 +1,140 @@
+use hyper::StatusCode;
+
+use crate::{
+    common::{HandlerResult, json_response},
+    election::Election,
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
+};
+
+pub type AgendaSessionId = usize;
+pub type AgendaItemId = usize;
+
+/// A block of the convention schedule, e.g. "Day 1, session 2".
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaSession {
+    pub id: AgendaSessionId,
+    pub title: String,
+    pub description: String,
+    /// Sessions are ordered by position, then by id.
+    pub position: u32,
+}
+
+/// A numbered point of a session, e.g. "4.3 Budget". Elections can belong to an agenda item.
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaItem {
+    pub id: AgendaItemId,
+    pub session_id: AgendaSessionId,
+    /// The number as printed in the agenda, e.g. "4.3".
+    pub number: String,
+    pub title: String,
+    pub description: String,
+    /// Agenda items are ordered by position within their session, then by id.
+    pub position: u32,
+    pub documents: Vec<AgendaDocument>,
+}
+
+/// A link to a document belonging to an agenda item, e.g. a motion text.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaDocument {
+    pub title: String,
+    /// An `http`, `https` or server relative URL.
+    pub url: String,
+}
+
+impl AgendaDocument {
+    pub fn has_allowed_url(&self) -> bool {
+        self.url.starts_with("https://")
+            || self.url.starts_with("http://")
+            || (self.url.starts_with('/') && !self.url.starts_with("//"))
+    }
+}
+
+/// All elections, grouped and ordered like the printed agenda.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectionsByAgenda<'a> {
+    pub sessions: Vec<AgendaSessionWithItems<'a>>,
+    /// Elections that do not belong to an agenda item, ordered by id.
+    pub unscheduled_elections: Vec<&'a Election>,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaSessionWithItems<'a> {
+    #[serde(flatten)]
+    pub session: &'a AgendaSession,
+    pub agenda_items: Vec<AgendaItemWithElections<'a>>,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaItemWithElections<'a> {
+    #[serde(flatten)]
+    pub agenda_item: &'a AgendaItem,
+    /// Ordered by id.
+    pub elections: Vec<&'a Election>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminCreateAgendaSessionBody {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// Defaults to after the last session.
+    #[serde(default)]
+    pub position: Option<u32>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminCreateAgendaItemBody {
+    pub session_id: AgendaSessionId,
+    pub number: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// Defaults to after the last agenda item of the session.
+    #[serde(default)]
+    pub position: Option<u32>,
+    #[serde(default)]
+    pub documents: Vec<AgendaDocument>,
+}
+
+pub async fn create_session(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+    let body = route_request.take_extension::<AdminCreateAgendaSessionBody>()?;
+
+    let agenda_session = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminCreateAgendaSession {
+            answer_sender,
+            requesting_credentials,
+            admin_create_agenda_session_body: body,
+        })
+        .await??;
+
+    json_response(StatusCode::CREATED, &agenda_session)
+}
+
+pub async fn create_item(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+    let body = route_request.take_extension::<AdminCreateAgendaItemBody>()?;
+
+    let agenda_item = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminCreateAgendaItem {
+            answer_sender,
+            requesting_credentials,
+            admin_create_agenda_item_body: body,
+        })
+        .await??;
+
+    json_response(StatusCode::CREATED, &agenda_item)
+}
@@ -0,0 +1,177 @@
+use std::{collections::BTreeSet, fmt};
+
+use hyper::StatusCode;
+
+use crate::{
+    admin::AdminSession,
+    common::{HandlerResult, json_response, ok_response},
+    error::ApiTokenError,
+    router::RouteRequest,
+    state::Message,
+};
+
+pub type ApiTokenId = usize;
+
+/// Shown in place of secrets when tokens are logged.
+const REDACTED: &str = "<redacted>";
+
+/// What a machine client is allowed to do with its token. Admin sessions have the scopes
+/// granted by the role of their account.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ApiTokenScope {
+    ReadElections,
+    ReadResults,
+    ManageElections,
+    ManageParticipants,
+}
+
+#[derive(Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiToken {
+    pub id: ApiTokenId,
+    pub name: String,
+    pub scopes: BTreeSet<ApiTokenScope>,
+    #[serde(skip)]
+    pub token: String,
+}
+
+impl fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiToken")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("scopes", &self.scopes)
+            .field("token", &REDACTED)
+            .finish()
+    }
+}
+
+/// Credentials sent as `Authorization: Bearer <id>.<token>`.
+#[derive(Clone)]
+pub struct ApiTokenCredentials {
+    pub id: ApiTokenId,
+    pub token: String,
+}
+
+impl fmt::Debug for ApiTokenCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiTokenCredentials")
+            .field("id", &self.id)
+            .field("token", &REDACTED)
+            .finish()
+    }
+}
+
+impl ApiTokenCredentials {
+    pub fn parse_bearer(bearer: &str) -> Option<Self> {
+        let (id_str, token) = bearer.split_once('.')?;
+
+        Some(Self {
+            id: id_str.parse().ok()?,
+            token: token.to_string(),
+        })
+    }
+}
+
+/// Answer to creating a token. This is the only time the secret is sent to the client.
+#[derive(serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedApiToken {
+    #[serde(flatten)]
+    pub api_token: ApiToken,
+    /// The value for the `Authorization: Bearer` header.
+    pub bearer_token: String,
+}
+
+impl fmt::Debug for CreatedApiToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreatedApiToken")
+            .field("api_token", &self.api_token)
+            .field("bearer_token", &REDACTED)
+            .finish()
+    }
+}
+
+impl From<ApiToken> for CreatedApiToken {
+    fn from(api_token: ApiToken) -> Self {
+        let bearer_token = format!("{}.{}", api_token.id, api_token.token);
+
+        Self {
+            api_token,
+            bearer_token,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminCreateApiTokenBody {
+    /// Free text to recognize the token later, e.g. "Projector in hall A".
+    pub name: String,
+    pub scopes: BTreeSet<ApiTokenScope>,
+}
+
+pub async fn create(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_admin_session = route_request.take_extension::<AdminSession>()?;
+    let body = route_request.take_extension::<AdminCreateApiTokenBody>()?;
+
+    let api_token = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminCreateApiToken {
+            answer_sender,
+            requesting_admin_session,
+            admin_create_api_token_body: body,
+        })
+        .await??;
+
+    json_response(StatusCode::CREATED, &CreatedApiToken::from(api_token))
+}
+
+pub async fn get_all(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_admin_session = route_request.take_extension::<AdminSession>()?;
+
+    let api_tokens = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminGetApiTokens {
+            answer_sender,
+            requesting_admin_session,
+        })
+        .await??;
+
+    json_response(StatusCode::OK, &api_tokens)
+}
+
+pub async fn revoke(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_admin_session = route_request.take_extension::<AdminSession>()?;
+
+    let Some(api_token_id) = route_request.path_params.parse("api_token_id") else {
+        return Err(ApiTokenError::MissingApiToken.into());
+    };
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminRevokeApiToken {
+            answer_sender,
+            requesting_admin_session,
+            api_token_id,
+        })
+        .await??;
+
+    ok_response()
+}
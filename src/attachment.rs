@@ -0,0 +1,151 @@
+use http_body_util::Full;
+use hyper::{
+    Response, StatusCode,
+    header::{CACHE_CONTROL, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS},
+};
+use tracing::warn;
+
+use crate::{
+    common::{HandlerResult, json_response},
+    error::AttachmentError,
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
+};
+
+pub type AttachmentId = usize;
+
+/// Upper bound for uploaded files. Attachments are kept in memory, like everything else.
+pub const MAX_ATTACHMENT_SIZE: usize = 5 * 1024 * 1024;
+
+/// The file types admins may upload, e.g. candidate photos or motion texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+pub enum AttachmentKind {
+    #[serde(rename = "image/png")]
+    Png,
+    #[serde(rename = "image/jpeg")]
+    Jpeg,
+    #[serde(rename = "image/gif")]
+    Gif,
+    #[serde(rename = "image/webp")]
+    Webp,
+    #[serde(rename = "application/pdf")]
+    Pdf,
+}
+
+impl AttachmentKind {
+    /// Parameters like `; charset=...` are ignored.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime_type = content_type.split(';').next().unwrap_or_default().trim();
+
+        match mime_type.to_ascii_lowercase().as_str() {
+            "image/png" => Some(AttachmentKind::Png),
+            "image/jpeg" => Some(AttachmentKind::Jpeg),
+            "image/gif" => Some(AttachmentKind::Gif),
+            "image/webp" => Some(AttachmentKind::Webp),
+            "application/pdf" => Some(AttachmentKind::Pdf),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            AttachmentKind::Png => "image/png",
+            AttachmentKind::Jpeg => "image/jpeg",
+            AttachmentKind::Gif => "image/gif",
+            AttachmentKind::Webp => "image/webp",
+            AttachmentKind::Pdf => "application/pdf",
+        }
+    }
+
+    /// Checks the magic bytes, so that the declared content type cannot be used to smuggle
+    /// e.g. HTML into the page.
+    pub fn matches(self, content: &[u8]) -> bool {
+        match self {
+            AttachmentKind::Png => content.starts_with(b"\x89PNG\r\n\x1a\n"),
+            AttachmentKind::Jpeg => content.starts_with(b"\xff\xd8\xff"),
+            AttachmentKind::Gif => content.starts_with(b"GIF87a") || content.starts_with(b"GIF89a"),
+            AttachmentKind::Webp => {
+                content.len() >= 12 && &content[0..4] == b"RIFF" && &content[8..12] == b"WEBP"
+            }
+            AttachmentKind::Pdf => content.starts_with(b"%PDF-"),
+        }
+    }
+}
+
+/// An uploaded file, without its content.
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub id: AttachmentId,
+    pub content_type: AttachmentKind,
+    pub size: usize,
+    /// Where participants can download the file.
+    pub url: String,
+}
+
+pub async fn upload(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(kind) = route_request
+        .request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(AttachmentKind::from_content_type)
+    else {
+        return Err(AttachmentError::UnsupportedContentType.into());
+    };
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminCheckAttachmentUpload {
+            answer_sender,
+            requesting_credentials: requesting_credentials.clone(),
+        })
+        .await??;
+
+    let content = route_request.body_bytes(MAX_ATTACHMENT_SIZE).await?;
+
+    if !kind.matches(&content) {
+        warn!("Uploaded attachment is not a valid {}", kind.content_type());
+        return Err(AttachmentError::ContentMismatch.into());
+    }
+
+    let attachment = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminUploadAttachment {
+            answer_sender,
+            requesting_credentials,
+            kind,
+            content,
+        })
+        .await??;
+
+    json_response(StatusCode::CREATED, &attachment)
+}
+
+pub async fn download(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(attachment_id) = route_request.path_params.parse("attachment_id") else {
+        return Err(AttachmentError::MissingAttachment.into());
+    };
+
+    let (kind, content) = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AttachmentsGet {
+            answer_sender,
+            requesting_credentials,
+            attachment_id,
+        })
+        .await??;
+
+    Ok(Response::builder()
+        .header(CONTENT_TYPE, kind.content_type())
+        .header(X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .header(CACHE_CONTROL, "private, max-age=3600")
+        .body(Full::new(content))?)
+}
@@ -0,0 +1,114 @@
+use hyper::StatusCode;
+use time::OffsetDateTime;
+
+use crate::{
+    common::{HandlerResult, json_response, ok_response},
+    error::AttendanceError,
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
+};
+
+#[derive(Debug, Clone, Copy, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AttendanceChangeKind {
+    CheckIn,
+    CheckOut,
+}
+
+/// A single check-in or check-out at the registration desk.
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceChange {
+    pub kind: AttendanceChangeKind,
+    #[serde(with = "time::serde::rfc3339")]
+    #[schemars(with = "String")]
+    pub at: OffsetDateTime,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceSettings {
+    /// Only participants that are checked in may vote.
+    pub require_check_in_to_vote: bool,
+    /// Number of present participants needed to open the voting in an election. Whether it is
+    /// still met when the election closes is reported in its results.
+    #[serde(default)]
+    pub quorum: Option<usize>,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceSummary {
+    pub num_registered: usize,
+    pub num_present: usize,
+    /// Participants that count for turnout. While check-in is required to vote, these are the
+    /// present participants, otherwise all registered ones.
+    pub num_eligible: usize,
+    pub settings: AttendanceSettings,
+}
+
+pub async fn get_summary(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let attendance_summary = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AttendanceGetSummary {
+            answer_sender,
+            requesting_credentials,
+        })
+        .await??;
+
+    json_response(StatusCode::OK, &attendance_summary)
+}
+
+pub async fn update_settings(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+    let attendance_settings = route_request.take_extension::<AttendanceSettings>()?;
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminUpdateAttendanceSettings {
+            answer_sender,
+            requesting_credentials,
+            attendance_settings,
+        })
+        .await??;
+
+    ok_response()
+}
+
+pub async fn check_in(route_request: RouteRequest) -> HandlerResult {
+    change_attendance(route_request, AttendanceChangeKind::CheckIn).await
+}
+
+pub async fn check_out(route_request: RouteRequest) -> HandlerResult {
+    change_attendance(route_request, AttendanceChangeKind::CheckOut).await
+}
+
+async fn change_attendance(
+    mut route_request: RouteRequest,
+    kind: AttendanceChangeKind,
+) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(participant_id) = route_request.path_params.parse("participant_id") else {
+        return Err(AttendanceError::MissingParticipant.into());
+    };
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminChangeAttendance {
+            answer_sender,
+            requesting_credentials,
+            participant_id,
+            kind,
+        })
+        .await??;
+
+    ok_response()
+}
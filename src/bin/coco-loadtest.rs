@@ -0,0 +1,514 @@
+//! Simulates the participants of a convention against a running server: every participant
+//! logs in, then keeps looking for open elections and votes in each of them once, pausing
+//! between requests like a person would. Reports throughput, latency percentiles and errors.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    env,
+    error::Error,
+    fmt,
+    process::ExitCode,
+    sync::Arc,
+    time::Duration,
+};
+
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    HeaderMap, Method, Request, StatusCode,
+    body::Bytes,
+    client::conn::http1::{self, SendRequest},
+    header::{CONTENT_TYPE, COOKIE, HOST, SET_COOKIE},
+};
+use hyper_util::rt::TokioIo;
+use rand::{Rng, seq::IndexedRandom};
+use serde_json::{Value, json};
+use tokio::{net::TcpStream, task::JoinSet, time::Instant};
+
+const USAGE: &str = "\
+Usage: coco-loadtest [OPTIONS]
+
+Options:
+  --url <URL>                 Server to test, only http:// [default: http://localhost:3030]
+  --participants <N>          Number of simulated participants [default: 100]
+  --password <PASSWORD>       Participant password of the event [default: $COCO_PARTICIPANT_PASSWORD]
+  --duration <SECONDS>        How long the participants keep voting [default: 60]
+  --think-time <MILLISECONDS> Average pause before every request [default: 1000]
+  --ramp-up <SECONDS>         Spreads the logins over this time [default: 0]
+  --help                      Print this help";
+
+struct Options {
+    /// Where to connect to, `host:port`.
+    authority: String,
+    participants: usize,
+    password: String,
+    duration: Duration,
+    think_time: Duration,
+    ramp_up: Duration,
+}
+
+impl Options {
+    /// Answers `None` if the help was requested.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut options = Options {
+            authority: String::from("localhost:3030"),
+            participants: 100,
+            password: env::var("COCO_PARTICIPANT_PASSWORD").unwrap_or_default(),
+            duration: Duration::from_secs(60),
+            think_time: Duration::from_millis(1000),
+            ramp_up: Duration::ZERO,
+        };
+
+        while let Some(arg) = args.next() {
+            if arg == "--help" {
+                return Ok(None);
+            }
+
+            let Some(value) = args.next() else {
+                return Err(format!("{arg} needs a value"));
+            };
+            let number = || {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| format!("{arg} needs a whole number, not {value}"))
+            };
+
+            match arg.as_str() {
+                "--url" => {
+                    let Some(authority) = value.strip_prefix("http://") else {
+                        return Err(format!("Only http:// URLs are supported, not {value}"));
+                    };
+                    options.authority = authority.trim_end_matches('/').to_string();
+                }
+                "--participants" => options.participants = number()? as usize,
+                "--password" => options.password = value,
+                "--duration" => options.duration = Duration::from_secs(number()?),
+                "--think-time" => options.think_time = Duration::from_millis(number()?),
+                "--ramp-up" => options.ramp_up = Duration::from_secs(number()?),
+                _ => return Err(format!("Unknown option {arg}")),
+            }
+        }
+
+        if options.password.is_empty() {
+            return Err(String::from(
+                "--password or COCO_PARTICIPANT_PASSWORD is required",
+            ));
+        }
+
+        Ok(Some(options))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Operation {
+    LogIn,
+    GetElections,
+    Vote,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Operation::LogIn => "log in",
+            Operation::GetElections => "get elections",
+            Operation::Vote => "vote",
+        })
+    }
+}
+
+/// What went wrong with a request: the status and error code of the response, or why there
+/// was none.
+type Failure = String;
+
+#[derive(Default)]
+struct Measurements {
+    latencies_by_operation: BTreeMap<Operation, Vec<Duration>>,
+    num_failures_by_operation_and_failure: BTreeMap<(Operation, Failure), usize>,
+    num_logged_in: usize,
+    num_votes_accepted: usize,
+}
+
+impl Measurements {
+    fn record(
+        &mut self,
+        operation: Operation,
+        latency: Duration,
+        outcome: &Result<Value, Failure>,
+    ) {
+        self.latencies_by_operation
+            .entry(operation)
+            .or_default()
+            .push(latency);
+
+        if let Err(failure) = outcome {
+            *self
+                .num_failures_by_operation_and_failure
+                .entry((operation, failure.clone()))
+                .or_default() += 1;
+        }
+    }
+
+    fn merge(&mut self, other: Measurements) {
+        for (operation, latencies) in other.latencies_by_operation {
+            self.latencies_by_operation
+                .entry(operation)
+                .or_default()
+                .extend(latencies);
+        }
+        for (key, num_failures) in other.num_failures_by_operation_and_failure {
+            *self
+                .num_failures_by_operation_and_failure
+                .entry(key)
+                .or_default() += num_failures;
+        }
+        self.num_logged_in += other.num_logged_in;
+        self.num_votes_accepted += other.num_votes_accepted;
+    }
+
+    fn print_report(mut self, options: &Options, elapsed: Duration) {
+        let elapsed_seconds = elapsed.as_secs_f64();
+        let num_requests: usize = self.latencies_by_operation.values().map(Vec::len).sum();
+        let num_failures: usize = self.num_failures_by_operation_and_failure.values().sum();
+
+        println!(
+            "{} of {} participants logged in, ran for {elapsed_seconds:.1} s",
+            self.num_logged_in, options.participants
+        );
+        println!(
+            "{num_requests} requests ({:.1}/s), {} votes accepted ({:.1}/s), {num_failures} failed",
+            num_requests as f64 / elapsed_seconds,
+            self.num_votes_accepted,
+            self.num_votes_accepted as f64 / elapsed_seconds,
+        );
+        println!();
+        println!(
+            "{:<14} {:>9} {:>9} {:>9} {:>9} {:>9} {:>9}",
+            "operation", "requests", "failed", "p50 ms", "p90 ms", "p99 ms", "max ms"
+        );
+
+        for (operation, latencies) in &mut self.latencies_by_operation {
+            latencies.sort_unstable();
+            let num_failed: usize = self
+                .num_failures_by_operation_and_failure
+                .iter()
+                .filter(|((failed_operation, _), _)| failed_operation == operation)
+                .map(|(_, num_failures)| num_failures)
+                .sum();
+            let milliseconds = |percent| percentile(latencies, percent).as_secs_f64() * 1000.0;
+
+            println!(
+                "{:<14} {:>9} {:>9} {:>9.1} {:>9.1} {:>9.1} {:>9.1}",
+                operation.to_string(),
+                latencies.len(),
+                num_failed,
+                milliseconds(50),
+                milliseconds(90),
+                milliseconds(99),
+                milliseconds(100),
+            );
+        }
+
+        if !self.num_failures_by_operation_and_failure.is_empty() {
+            println!();
+            println!("Failures:");
+            for ((operation, failure), num_failures) in &self.num_failures_by_operation_and_failure
+            {
+                println!("{num_failures:>9}  {operation}: {failure}");
+            }
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted `latencies`.
+fn percentile(latencies: &[Duration], percent: usize) -> Duration {
+    if latencies.is_empty() {
+        return Duration::ZERO;
+    }
+
+    let rank = (percent * latencies.len()).div_ceil(100).max(1);
+    latencies[rank - 1]
+}
+
+/// A keep-alive connection of one participant, reopened after errors.
+struct Connection {
+    authority: Arc<str>,
+    sender: Option<SendRequest<Full<Bytes>>>,
+    cookies: Vec<(String, String)>,
+}
+
+impl Connection {
+    async fn request(
+        &mut self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value, Failure> {
+        let result = self.try_request(method, path, body).await;
+        if result.is_err() {
+            self.sender = None;
+        }
+        result?
+    }
+
+    /// The outer error means that the connection is unusable.
+    async fn try_request(
+        &mut self,
+        method: Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Result<Value, Failure>, Failure> {
+        let sender = match &mut self.sender {
+            Some(sender) if !sender.is_closed() => sender,
+            _ => {
+                let stream = TcpStream::connect(&*self.authority)
+                    .await
+                    .map_err(|e| format!("connect: {e}"))?;
+                let (sender, connection) = http1::handshake(TokioIo::new(stream))
+                    .await
+                    .map_err(|e| format!("handshake: {e}"))?;
+                tokio::spawn(connection);
+                self.sender.insert(sender)
+            }
+        };
+        sender
+            .ready()
+            .await
+            .map_err(|e| format!("connection: {e}"))?;
+
+        let mut request_builder = Request::builder()
+            .method(method)
+            .uri(path)
+            .header(HOST, &*self.authority);
+        if !self.cookies.is_empty() {
+            let cookie = self
+                .cookies
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join("; ");
+            request_builder = request_builder.header(COOKIE, cookie);
+        }
+        let body = match body {
+            Some(body) => {
+                request_builder = request_builder.header(CONTENT_TYPE, "application/json");
+                Bytes::from(body.to_string())
+            }
+            None => Bytes::new(),
+        };
+        let request = request_builder
+            .body(Full::new(body))
+            .map_err(|e| format!("request: {e}"))?;
+
+        let response = sender
+            .send_request(request)
+            .await
+            .map_err(|e| format!("request: {e}"))?;
+        let status = response.status();
+        self.store_cookies(response.headers());
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| format!("response: {e}"))?
+            .to_bytes();
+
+        let json = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        if status.is_success() {
+            Ok(Ok(json))
+        } else {
+            Ok(Err(describe_failure(status, &json)))
+        }
+    }
+
+    fn store_cookies(&mut self, headers: &HeaderMap) {
+        for set_cookie in headers.get_all(SET_COOKIE) {
+            let Ok(set_cookie) = set_cookie.to_str() else {
+                continue;
+            };
+            let key_value = set_cookie.split(';').next().unwrap_or_default();
+            if let Some((key, value)) = key_value.split_once('=') {
+                self.cookies
+                    .retain(|(cookie_key, _)| cookie_key != key.trim());
+                self.cookies
+                    .push((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+    }
+}
+
+fn describe_failure(status: StatusCode, json: &Value) -> Failure {
+    match json["code"].as_str() {
+        Some(error_code) => format!("{status} {error_code}"),
+        None => status.to_string(),
+    }
+}
+
+/// Open elections with their ballot item ids, wherever they are on the agenda.
+fn open_elections(elections_by_agenda: &Value) -> Vec<(u64, Vec<u64>)> {
+    let scheduled = elections_by_agenda["sessions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|session| session["agendaItems"].as_array().into_iter().flatten())
+        .flat_map(|agenda_item| agenda_item["elections"].as_array().into_iter().flatten());
+    let unscheduled = elections_by_agenda["unscheduledElections"]
+        .as_array()
+        .into_iter()
+        .flatten();
+
+    scheduled
+        .chain(unscheduled)
+        .filter(|election| election["status"]["kind"] == "open")
+        .filter_map(|election| {
+            let election_id = election["id"].as_u64()?;
+            let ballot_item_ids = election["ballotItemsById"]
+                .as_object()?
+                .keys()
+                .filter_map(|ballot_item_id| ballot_item_id.parse().ok())
+                .collect();
+            Some((election_id, ballot_item_ids))
+        })
+        .collect()
+}
+
+/// Pauses for 50 % to 150 % of the think time, but not beyond the deadline.
+async fn think(think_time: Duration, deadline: Instant) {
+    let factor = rand::rng().random_range(0.5..1.5);
+    let until = (Instant::now() + think_time.mul_f64(factor)).min(deadline);
+    tokio::time::sleep_until(until).await;
+}
+
+async fn simulate_participant(
+    options: Arc<Options>,
+    authority: Arc<str>,
+    start_delay: Duration,
+    deadline: Instant,
+) -> Measurements {
+    let mut measurements = Measurements::default();
+    let mut connection = Connection {
+        authority,
+        sender: None,
+        cookies: Vec::new(),
+    };
+
+    tokio::time::sleep(start_delay).await;
+
+    let started_at = Instant::now();
+    let outcome = connection
+        .request(
+            Method::POST,
+            "/api/v1/participants",
+            Some(json!({ "password": options.password })),
+        )
+        .await;
+    measurements.record(Operation::LogIn, started_at.elapsed(), &outcome);
+    if outcome.is_err() {
+        return measurements;
+    }
+    measurements.num_logged_in += 1;
+
+    let mut voted_election_ids = HashSet::new();
+    while Instant::now() < deadline {
+        think(options.think_time, deadline).await;
+        if Instant::now() >= deadline {
+            break;
+        }
+
+        let started_at = Instant::now();
+        let outcome = connection
+            .request(Method::GET, "/api/v1/elections", None)
+            .await;
+        measurements.record(Operation::GetElections, started_at.elapsed(), &outcome);
+        let Ok(elections_by_agenda) = outcome else {
+            continue;
+        };
+
+        for (election_id, ballot_item_ids) in open_elections(&elections_by_agenda) {
+            if voted_election_ids.contains(&election_id) {
+                continue;
+            }
+
+            think(options.think_time, deadline).await;
+            if Instant::now() >= deadline {
+                break;
+            }
+            let Some(&ballot_item_id) = ballot_item_ids.choose(&mut rand::rng()) else {
+                continue;
+            };
+
+            let started_at = Instant::now();
+            let outcome = connection
+                .request(
+                    Method::POST,
+                    "/api/v1/votes",
+                    Some(json!({
+                        "electionId": election_id,
+                        "selectedBallotItemId": ballot_item_id,
+                    })),
+                )
+                .await;
+            measurements.record(Operation::Vote, started_at.elapsed(), &outcome);
+
+            // Elections that rejected the vote for good, e.g. after closing, are not retried.
+            match outcome {
+                Ok(_) => {
+                    measurements.num_votes_accepted += 1;
+                    voted_election_ids.insert(election_id);
+                }
+                Err(failure) if failure.starts_with('4') => {
+                    voted_election_ids.insert(election_id);
+                }
+                Err(_) => {}
+            }
+        }
+    }
+
+    measurements
+}
+
+#[tokio::main]
+async fn main() -> Result<ExitCode, Box<dyn Error + Send + Sync>> {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(Some(options)) => Arc::new(options),
+        Ok(None) => {
+            println!("{USAGE}");
+            return Ok(ExitCode::SUCCESS);
+        }
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    println!(
+        "Simulating {} participants against http://{} for {} s",
+        options.participants,
+        options.authority,
+        options.duration.as_secs()
+    );
+
+    let authority: Arc<str> = Arc::from(options.authority.as_str());
+    let started_at = Instant::now();
+    let deadline = started_at + options.ramp_up + options.duration;
+
+    let mut participants = JoinSet::new();
+    for i in 0..options.participants {
+        let start_delay = options
+            .ramp_up
+            .mul_f64(i as f64 / options.participants as f64);
+        participants.spawn(simulate_participant(
+            Arc::clone(&options),
+            Arc::clone(&authority),
+            start_delay,
+            deadline,
+        ));
+    }
+
+    let mut measurements = Measurements::default();
+    while let Some(participant_measurements) = participants.join_next().await {
+        measurements.merge(participant_measurements?);
+    }
+
+    measurements.print_report(&options, started_at.elapsed());
+
+    Ok(ExitCode::SUCCESS)
+}
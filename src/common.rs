@@ -1,36 +1,39 @@
 use http_body_util::Full;
-use hyper::{Request, Response, StatusCode, body::Bytes, header::COOKIE};
+use hyper::{
+    Request, Response, StatusCode,
+    body::Bytes,
+    header::{AUTHORIZATION, CONTENT_TYPE, COOKIE},
+};
+use tracing::error;
 
 use crate::{
     admin::{ADMIN_SESSION_ID_COOKIE_KEY, ADMIN_TOKEN_COOKIE_KEY, AdminSession},
+    api_token::ApiTokenCredentials,
+    error::ApiError,
     participant::{PARTICIPANT_ID_COOKIE_KEY, ParticipantCredentials, TOKEN_COOKIE_KEY},
     state::RequestingCredentials,
 };
 
 pub type ResponseResult = Result<Response<Full<Bytes>>, hyper::http::Error>;
 
-pub fn ok_response() -> ResponseResult {
-    Response::builder()
-        .status(StatusCode::OK)
-        .body(Full::new(Bytes::new()))
-}
+pub type HandlerResult = Result<Response<Full<Bytes>>, ApiError>;
 
-pub fn bad_request_response() -> ResponseResult {
-    Response::builder()
-        .status(StatusCode::BAD_REQUEST)
-        .body(Full::new(Bytes::new()))
+pub fn ok_response() -> HandlerResult {
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .body(Full::new(Bytes::new()))?)
 }
 
-pub fn unauthorized_response() -> ResponseResult {
-    Response::builder()
-        .status(StatusCode::UNAUTHORIZED)
-        .body(Full::new(Bytes::new()))
-}
+pub fn json_response<T: serde::Serialize>(status: StatusCode, value: &T) -> HandlerResult {
+    let body = serde_json::to_vec(value).map_err(|e| {
+        error!("{e:?}");
+        ApiError::unexpected()
+    })?;
 
-pub fn internal_error_response() -> ResponseResult {
-    Response::builder()
-        .status(StatusCode::INTERNAL_SERVER_ERROR)
-        .body(Full::new(Bytes::new()))
+    Ok(Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json; charset=utf-8")
+        .body(Full::new(Bytes::from_owner(body)))?)
 }
 
 pub fn get_cookie_value<'a>(cookie: &'a [u8], key: &[u8]) -> Option<&'a str> {
@@ -41,9 +44,7 @@ pub fn get_cookie_value<'a>(cookie: &'a [u8], key: &[u8]) -> Option<&'a str> {
         .and_then(|value| str::from_utf8(value).ok())
 }
 
-pub fn extract_requesting_participant(
-    request: &Request<hyper::body::Incoming>,
-) -> Option<ParticipantCredentials> {
+pub fn extract_requesting_participant<B>(request: &Request<B>) -> Option<ParticipantCredentials> {
     let cookie = &request.headers().get(COOKIE)?;
     let id_str = get_cookie_value(cookie.as_bytes(), PARTICIPANT_ID_COOKIE_KEY.as_bytes())?;
     let id = id_str.parse().ok()?;
@@ -55,9 +56,7 @@ pub fn extract_requesting_participant(
     })
 }
 
-pub fn extract_requesting_admin_session(
-    request: &Request<hyper::body::Incoming>,
-) -> Option<AdminSession> {
+pub fn extract_requesting_admin_session<B>(request: &Request<B>) -> Option<AdminSession> {
     let cookie = &request.headers().get(COOKIE)?;
     let id_str = get_cookie_value(cookie.as_bytes(), ADMIN_SESSION_ID_COOKIE_KEY.as_bytes())?;
     let id = id_str.parse().ok()?;
@@ -69,10 +68,23 @@ pub fn extract_requesting_admin_session(
     })
 }
 
-pub fn extract_requesting_credentials(
-    request: &Request<hyper::body::Incoming>,
+pub fn extract_requesting_api_token<B>(request: &Request<B>) -> Option<ApiTokenCredentials> {
+    let authorization = request.headers().get(AUTHORIZATION)?.to_str().ok()?;
+    let bearer = authorization.strip_prefix("Bearer ")?;
+
+    ApiTokenCredentials::parse_bearer(bearer.trim())
+}
+
+/// Credentials that are allowed to use admin endpoints, i.e. an admin session or an API token.
+pub fn extract_requesting_admin_credentials<B>(
+    request: &Request<B>,
 ) -> Option<RequestingCredentials> {
-    extract_requesting_admin_session(request)
-        .map(RequestingCredentials::Admin)
+    extract_requesting_api_token(request)
+        .map(RequestingCredentials::ApiToken)
+        .or_else(|| extract_requesting_admin_session(request).map(RequestingCredentials::Admin))
+}
+
+pub fn extract_requesting_credentials<B>(request: &Request<B>) -> Option<RequestingCredentials> {
+    extract_requesting_admin_credentials(request)
         .or_else(|| extract_requesting_participant(request).map(RequestingCredentials::Normal))
 }
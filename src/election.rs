@@ -1,132 +1,381 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
-use http_body_util::{BodyExt, Full};
-use hyper::{Request, Response};
-use tokio::sync::{mpsc, oneshot};
-use tracing::{error, info, warn};
+use http_body_util::Full;
+use hyper::Response;
+use time::OffsetDateTime;
 
 use crate::{
-    common::{
-        ResponseResult, bad_request_response, extract_requesting_credentials,
-        extract_requesting_participant, internal_error_response, ok_response,
-        unauthorized_response,
-    },
-    participant::ParticipantId,
-    state::Message,
+    agenda::AgendaItemId,
+    attachment::Attachment,
+    common::{HandlerResult, ok_response},
+    error::ElectionsGetError,
+    i18n::{BallotItemTranslation, ElectionTranslation, Translations},
+    metrics::METRICS,
+    motion::Motion,
+    participant::{ParticipantCredentials, ParticipantGroup, ParticipantId},
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
 };
 
 pub type BallotItemId = usize;
 pub type ElectionId = usize;
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Election {
     pub id: ElectionId,
     pub name: String,
+    pub translations: Translations<ElectionTranslation>,
+    pub method: VotingMethod,
+    pub eligibility: Eligibility,
+    pub status: ElectionStatus,
+    pub agenda_item_id: Option<AgendaItemId>,
+    /// Set if the election decides on a motion or an amendment to one.
+    pub motion: Option<Motion>,
+    #[serde(with = "time::serde::rfc3339")]
+    #[schemars(with = "String")]
+    pub created_at: OffsetDateTime,
+    /// The election closes automatically at this point in time.
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[schemars(with = "Option<String>")]
+    pub voting_deadline: Option<OffsetDateTime>,
+    /// Whether the admin released the results to be shown in the presentation mode.
+    pub results_published: bool,
     pub ballot_items_by_id: HashMap<BallotItemId, BallotItem>,
     #[serde(skip)]
     pub participant_ids_who_voted: HashSet<ParticipantId>,
 }
 
-#[derive(Debug, serde::Serialize)]
+impl Election {
+    pub fn is_closed(&self) -> bool {
+        matches!(self.status, ElectionStatus::Closed { .. })
+    }
+
+    /// The quorum as checked when the election was closed.
+    pub fn quorum_check(&self) -> Option<QuorumCheck> {
+        match self.status {
+            ElectionStatus::Closed { quorum_check, .. } => quorum_check,
+            _ => None,
+        }
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        let num_votes_cast = self.participant_ids_who_voted.len();
+
+        if num_votes_cast == 0 {
+            return Outcome::NoVotes;
+        }
+
+        match self.method {
+            VotingMethod::Plurality => {
+                let max_num_votes = self
+                    .ballot_items_by_id
+                    .values()
+                    .map(|ballot_item| ballot_item.num_votes)
+                    .max()
+                    .unwrap_or(0);
+
+                let mut leading_ballot_item_ids: Vec<_> = self
+                    .ballot_items_by_id
+                    .values()
+                    .filter(|ballot_item| ballot_item.num_votes == max_num_votes)
+                    .map(|ballot_item| ballot_item.id)
+                    .collect();
+                leading_ballot_item_ids.sort();
+
+                match leading_ballot_item_ids.as_slice() {
+                    [ballot_item_id] => Outcome::Decided {
+                        ballot_item_id: *ballot_item_id,
+                    },
+                    _ => Outcome::Tie {
+                        ballot_item_ids: leading_ballot_item_ids,
+                    },
+                }
+            }
+            VotingMethod::Majority { threshold } => self
+                .ballot_items_by_id
+                .values()
+                .find(|ballot_item| threshold.is_reached(ballot_item.num_votes, num_votes_cast))
+                .map_or(Outcome::NoMajority, |ballot_item| Outcome::Decided {
+                    ballot_item_id: ballot_item.id,
+                }),
+        }
+    }
+}
+
+/// How the outcome of an election is determined from the votes.
+#[derive(
+    Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum VotingMethod {
+    /// The ballot item with the most votes wins.
+    #[default]
+    Plurality,
+    /// A ballot item wins if its share of the votes cast reaches the threshold.
+    Majority { threshold: Threshold },
+}
+
+impl VotingMethod {
+    pub fn threshold(&self) -> Option<Threshold> {
+        match self {
+            VotingMethod::Plurality => None,
+            VotingMethod::Majority { threshold } => Some(*threshold),
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            VotingMethod::Plurality => "plurality",
+            VotingMethod::Majority { .. } => "majority",
+        }
+    }
+}
+
+/// Who may vote in an election.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Eligibility {
+    /// Every participant.
+    #[default]
+    Everyone,
+    /// Participants that belong to at least one of the groups.
+    Groups { groups: BTreeSet<ParticipantGroup> },
+}
+
+impl Eligibility {
+    pub fn admits(&self, participant_groups: &BTreeSet<ParticipantGroup>) -> bool {
+        match self {
+            Eligibility::Everyone => true,
+            Eligibility::Groups { groups } => !groups.is_disjoint(participant_groups),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Eligibility::Everyone => String::from("all participants"),
+            Eligibility::Groups { groups } => {
+                let groups: Vec<_> = groups.iter().map(String::as_str).collect();
+                format!("members of {}", groups.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Threshold {
+    MoreThanHalf,
+    AtLeastTwoThirds,
+}
+
+impl Threshold {
+    /// Exact integer arithmetic, so that e.g. 2 of 3 votes reach two thirds.
+    pub fn is_reached(&self, num_votes: usize, num_votes_cast: usize) -> bool {
+        match self {
+            Threshold::MoreThanHalf => 2 * num_votes > num_votes_cast,
+            Threshold::AtLeastTwoThirds => 3 * num_votes >= 2 * num_votes_cast,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Threshold::MoreThanHalf => "more than half of the votes cast",
+            Threshold::AtLeastTwoThirds => "at least two thirds of the votes cast",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum ElectionStatus {
+    /// Participants nominate candidates, voting has not started yet.
+    Nominating {
+        required_seconders: usize,
+        /// Voting closes automatically this many seconds after it was opened.
+        voting_duration_seconds: Option<u64>,
+    },
+    /// A main motion whose amendments are voted on first.
+    AwaitingAmendments {
+        /// Voting closes automatically this many seconds after it was opened.
+        voting_duration_seconds: Option<u64>,
+    },
+    Open,
+    Closed {
+        #[serde(with = "time::serde::rfc3339")]
+        #[schemars(with = "String")]
+        closed_at: OffsetDateTime,
+        /// Number of participants that could have voted when the election was closed, including
+        /// those who voted and were checked out since.
+        num_eligible_participants: usize,
+        /// `None` if no quorum was set when the election was closed.
+        quorum_check: Option<QuorumCheck>,
+    },
+}
+
+/// The quorum compared with the number of present participants.
+#[derive(Debug, Clone, Copy, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuorumCheck {
+    pub quorum: usize,
+    pub num_present: usize,
+    pub is_met: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum Outcome {
+    Decided { ballot_item_id: BallotItemId },
+    Tie { ballot_item_ids: Vec<BallotItemId> },
+    NoMajority,
+    NoVotes,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct BallotItem {
     pub id: ElectionId,
     pub name: String,
+    /// Markdown, e.g. a candidate statement or the full text of a motion.
+    pub description: String,
+    /// E.g. the party or region a candidate belongs to, may be empty.
+    pub affiliation: String,
+    /// Photos and documents, in the order the admin listed them.
+    pub attachments: Vec<Attachment>,
+    pub translations: Translations<BallotItemTranslation>,
     #[serde(skip)]
     pub num_votes: usize,
 }
 
-#[derive(Debug, serde::Deserialize)]
+impl BallotItem {
+    /// A ballot item with nothing but a name.
+    pub fn named(id: BallotItemId, name: String) -> Self {
+        Self {
+            id,
+            name,
+            description: String::new(),
+            affiliation: String::new(),
+            attachments: Vec::new(),
+            translations: Translations::new(),
+            num_votes: 0,
+        }
+    }
+}
+
+/// Vote counts of an election. Only visible to admins and API tokens with the `readResults` scope.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectionResults {
+    pub election_id: ElectionId,
+    pub num_votes_cast: usize,
+    pub num_votes_by_ballot_item_id: HashMap<BallotItemId, usize>,
+    /// `null` while the election is open or if no quorum was set when it was closed.
+    pub quorum_check: Option<QuorumCheck>,
+}
+
+impl From<&Election> for ElectionResults {
+    fn from(election: &Election) -> Self {
+        Self {
+            election_id: election.id,
+            quorum_check: election.quorum_check(),
+            num_votes_cast: election.participant_ids_who_voted.len(),
+            num_votes_by_ballot_item_id: election
+                .ballot_items_by_id
+                .values()
+                .map(|ballot_item| (ballot_item.id, ballot_item.num_votes))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ElectionsVoteBody {
     pub election_id: ElectionId,
     pub selected_ballot_item_id: BallotItemId,
 }
 
-pub async fn get_all(
-    request: Request<hyper::body::Incoming>,
-    to_central_state_authority_sender: mpsc::Sender<Message>,
-) -> ResponseResult {
-    let Some(requesting_credentials) = extract_requesting_credentials(&request) else {
-        return unauthorized_response();
-    };
+pub async fn get_all(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
 
-    let (answer_sender, answer_receiver) = oneshot::channel();
-    if let Err(e) = to_central_state_authority_sender
-        .send(Message::ElectionsGet {
+    let body = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::ElectionsGet {
             answer_sender,
             requesting_credentials,
         })
-        .await
-    {
-        error!("{e:?}");
-        return internal_error_response();
-    }
+        .await??;
 
-    let answer = match answer_receiver.await {
-        Ok(body) => body,
-        Err(e) => {
-            error!("{e:?}");
-            return internal_error_response();
-        }
+    Ok(Response::builder().body(Full::new(body))?)
+}
+
+pub async fn get_one(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(election_id) = route_request.path_params.parse("election_id") else {
+        return Err(ElectionsGetError::MissingElection.into());
     };
 
-    match answer {
-        Ok(body) => Response::builder().body(Full::new(body)),
-        Err(err) => err.to_response(),
-    }
+    let body = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::ElectionsGetById {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        })
+        .await??;
+
+    Ok(Response::builder().body(Full::new(body))?)
 }
 
-pub async fn vote(
-    request: Request<hyper::body::Incoming>,
-    to_central_state_authority_sender: mpsc::Sender<Message>,
-) -> ResponseResult {
-    let Some(requesting_participant_credentials) = extract_requesting_participant(&request) else {
-        return unauthorized_response();
-    };
+pub async fn get_results(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
 
-    let body_bytes = match request.into_body().collect().await {
-        Ok(collected) => collected.to_bytes(),
-        Err(e) => {
-            error!("{e:?}");
-            return internal_error_response();
-        }
+    let Some(election_id) = route_request.path_params.parse("election_id") else {
+        return Err(ElectionsGetError::MissingElection.into());
     };
 
-    let body: ElectionsVoteBody = match serde_json::from_slice(&body_bytes) {
-        Ok(body) => body,
-        Err(e) => {
-            warn!("Bad request: {e:?}");
-            return bad_request_response();
-        }
-    };
+    let body = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::ElectionsGetResults {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        })
+        .await??;
 
-    info!("{body:?}");
+    Ok(Response::builder().body(Full::new(body))?)
+}
 
-    let (answer_sender, answer_receiver) = oneshot::channel();
+pub async fn vote(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_participant_credentials =
+        route_request.take_extension::<ParticipantCredentials>()?;
 
-    if let Err(e) = to_central_state_authority_sender
-        .send(Message::ElectionsVote {
+    let body = route_request.take_extension::<ElectionsVoteBody>()?;
+
+    let vote_result = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::ElectionsVote {
             answer_sender,
             requesting_participant_credentials,
             elections_vote_body: body,
         })
-        .await
-    {
-        error!("{e:?}");
-        return internal_error_response();
-    }
+        .await?;
 
-    let answer = match answer_receiver.await {
-        Ok(body) => body,
-        Err(e) => {
-            error!("{e:?}");
-            return internal_error_response();
-        }
-    };
+    METRICS.record_vote(&vote_result);
+    vote_result?;
 
-    match answer {
-        Ok(()) => ok_response(),
-        Err(err) => err.to_response(),
-    }
+    ok_response()
 }
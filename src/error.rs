@@ -1,7 +1,183 @@
 use http_body_util::Full;
-use hyper::{Response, StatusCode, body::Bytes};
+use hyper::{Response, StatusCode, body::Bytes, header::CONTENT_TYPE};
+use tracing::error;
 
-use crate::common::ResponseResult;
+use crate::{
+    common::ResponseResult,
+    election::QuorumCheck,
+    i18n::{self, Locale},
+};
+
+/// Stable, machine-readable identifiers for every error the API can return.
+/// Clients should react to these instead of the human readable message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    NotLoggedIn,
+    WrongToken,
+    WrongPassword,
+    InternalError,
+    NotFound,
+    MethodNotAllowed,
+    BodyTooLarge,
+    BodyUnreadable,
+    BodyMalformed,
+    ElectionNotFound,
+    BallotItemNotFound,
+    AlreadyVoted,
+    InsufficientScope,
+    ApiTokenNotFound,
+    ElectionClosed,
+    ElectionAlreadyClosed,
+    ElectionNotClosed,
+    AgendaSessionNotFound,
+    AgendaItemNotFound,
+    InvalidDocumentUrl,
+    EventNotFound,
+    EventSlugTaken,
+    InvalidEventSlug,
+    NotDefaultEvent,
+    InsufficientRole,
+    AdminAccountNotFound,
+    AdminUsernameTaken,
+    LastSuperAdmin,
+    NotCheckedIn,
+    ParticipantNotFound,
+    AlreadyCheckedIn,
+    AlreadyCheckedOut,
+    NotEligible,
+    InvalidGroupName,
+    NoEligibleGroups,
+    ElectionNotYetOpen,
+    ElectionNotNominating,
+    NoCandidates,
+    NominationNotFound,
+    EmptyCandidateName,
+    CandidateAlreadyNominated,
+    AlreadySeconded,
+    CannotSecondOwnNomination,
+    NotCandidate,
+    AttachmentNotFound,
+    UnsupportedAttachmentType,
+    AttachmentContentMismatch,
+    AmendmentsNotClosed,
+    ParentMotionNotFound,
+    ParentMotionNotAwaitingAmendments,
+    AmendedTextNotFound,
+    MotionWithNominations,
+    SpeakerRequestNotFound,
+    AlreadyRequestedToSpeak,
+    SpeakerRequestNotWaiting,
+    NotSpeaking,
+    NotOwnSpeakerRequest,
+    InvalidSpeakerOrder,
+    QuorumNotMet,
+}
+
+/// The error type every handler returns. It is sent to the client as
+/// `{"code": ..., "message": ..., "details": ...}`.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+pub struct ApiError {
+    #[serde(skip)]
+    status: StatusCode,
+    code: ErrorCode,
+    /// Human readable description in the language of the request, not meant to be parsed.
+    message: String,
+    /// Additional structured information, depending on the error code.
+    details: Option<serde_json::Value>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Replaces the message with the catalog's one for `locale`, if there is one.
+    pub fn localized(mut self, locale: Locale) -> Self {
+        if let Some(message) = i18n::error_message(self.code, locale) {
+            self.message = String::from(message);
+        }
+        self
+    }
+
+    pub fn unexpected() -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::InternalError,
+            "Unexpected internal error.",
+        )
+    }
+
+    pub fn not_found() -> Self {
+        Self::new(
+            StatusCode::NOT_FOUND,
+            ErrorCode::NotFound,
+            "CoCo does not know this page.",
+        )
+    }
+
+    pub fn wrong_password() -> Self {
+        Self::new(
+            StatusCode::UNAUTHORIZED,
+            ErrorCode::WrongPassword,
+            "Wrong password.",
+        )
+    }
+
+    pub fn to_response(&self) -> ResponseResult {
+        let body = match serde_json::to_vec(self) {
+            Ok(serialized) => serialized,
+            Err(e) => {
+                error!("{e:?}");
+                Vec::new()
+            }
+        };
+
+        Response::builder()
+            .status(self.status)
+            .header(CONTENT_TYPE, "application/json; charset=utf-8")
+            .body(Full::new(Bytes::from_owner(body)))
+    }
+}
+
+impl From<hyper::http::Error> for ApiError {
+    fn from(err: hyper::http::Error) -> Self {
+        error!("{err:?}");
+        Self::unexpected()
+    }
+}
+
+/// Implemented by the domain specific error enums so that they convert into [`ApiError`].
+pub trait ApiErrorSource: std::error::Error {
+    fn http_status_code(&self) -> StatusCode;
+
+    fn error_code(&self) -> ErrorCode;
+
+    fn details(&self) -> Option<serde_json::Value> {
+        None
+    }
+}
+
+impl<E: ApiErrorSource> From<E> for ApiError {
+    fn from(err: E) -> Self {
+        let api_error = ApiError::new(err.http_status_code(), err.error_code(), err.to_string());
+
+        match err.details() {
+            Some(details) => api_error.with_details(details),
+            None => api_error,
+        }
+    }
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum InvalidCredentialsError {
@@ -9,23 +185,37 @@ pub enum InvalidCredentialsError {
     Missing,
     #[error("The supplied login token for this participant is wrong.")]
     WrongToken,
+    #[error("Wrong username or password.")]
+    WrongPassword,
+    #[error("The API token does not have the scope required for this action.")]
+    InsufficientScope,
+    #[error("Your admin role does not allow this action.")]
+    InsufficientRole,
     #[error("Unexpected internal error.")]
     Unexpected,
 }
 
-impl InvalidCredentialsError {
-    pub fn http_status_code(&self) -> StatusCode {
+impl ApiErrorSource for InvalidCredentialsError {
+    fn http_status_code(&self) -> StatusCode {
         match self {
             InvalidCredentialsError::Missing => StatusCode::UNAUTHORIZED,
             InvalidCredentialsError::WrongToken => StatusCode::UNAUTHORIZED,
+            InvalidCredentialsError::WrongPassword => StatusCode::UNAUTHORIZED,
+            InvalidCredentialsError::InsufficientScope => StatusCode::FORBIDDEN,
+            InvalidCredentialsError::InsufficientRole => StatusCode::FORBIDDEN,
             InvalidCredentialsError::Unexpected => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
-    pub fn to_response(&self) -> ResponseResult {
-        Response::builder()
-            .status(self.http_status_code())
-            .body(Full::new(Bytes::from_owner(self.to_string())))
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            InvalidCredentialsError::Missing => ErrorCode::NotLoggedIn,
+            InvalidCredentialsError::WrongToken => ErrorCode::WrongToken,
+            InvalidCredentialsError::WrongPassword => ErrorCode::WrongPassword,
+            InvalidCredentialsError::InsufficientScope => ErrorCode::InsufficientScope,
+            InvalidCredentialsError::InsufficientRole => ErrorCode::InsufficientRole,
+            InvalidCredentialsError::Unexpected => ErrorCode::InternalError,
+        }
     }
 }
 
@@ -39,10 +229,34 @@ pub enum ElectionsVoteError {
     AlreadyVoted,
     #[error("The election was modified by the administrator.")]
     MissingBallotItem,
+    #[error("The election is already closed.")]
+    ElectionClosed,
+    #[error("Only participants who are checked in at the registration desk may vote.")]
+    NotCheckedIn,
+    #[error("You are not eligible to vote in this election.")]
+    NotEligible,
+    #[error("Voting in this election has not started yet.")]
+    NotYetOpen,
 }
 
 impl ElectionsVoteError {
-    pub fn http_status_code(&self) -> StatusCode {
+    /// Label of rejected votes in the metrics.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ElectionsVoteError::InvalidParticipant(_) => "InvalidParticipant",
+            ElectionsVoteError::MissingElection => "MissingElection",
+            ElectionsVoteError::AlreadyVoted => "AlreadyVoted",
+            ElectionsVoteError::MissingBallotItem => "MissingBallotItem",
+            ElectionsVoteError::ElectionClosed => "ElectionClosed",
+            ElectionsVoteError::NotCheckedIn => "NotCheckedIn",
+            ElectionsVoteError::NotEligible => "NotEligible",
+            ElectionsVoteError::NotYetOpen => "NotYetOpen",
+        }
+    }
+}
+
+impl ApiErrorSource for ElectionsVoteError {
+    fn http_status_code(&self) -> StatusCode {
         match self {
             ElectionsVoteError::InvalidParticipant(invalid_participant_error) => {
                 invalid_participant_error.http_status_code()
@@ -50,12 +264,623 @@ impl ElectionsVoteError {
             ElectionsVoteError::MissingElection => StatusCode::NOT_FOUND,
             ElectionsVoteError::AlreadyVoted => StatusCode::FORBIDDEN,
             ElectionsVoteError::MissingBallotItem => StatusCode::NOT_FOUND,
+            ElectionsVoteError::ElectionClosed => StatusCode::FORBIDDEN,
+            ElectionsVoteError::NotCheckedIn => StatusCode::FORBIDDEN,
+            ElectionsVoteError::NotEligible => StatusCode::FORBIDDEN,
+            ElectionsVoteError::NotYetOpen => StatusCode::FORBIDDEN,
         }
     }
 
-    pub fn to_response(&self) -> ResponseResult {
-        Response::builder()
-            .status(self.http_status_code())
-            .body(Full::new(Bytes::from_owner(self.to_string())))
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ElectionsVoteError::InvalidParticipant(invalid_participant_error) => {
+                invalid_participant_error.error_code()
+            }
+            ElectionsVoteError::MissingElection => ErrorCode::ElectionNotFound,
+            ElectionsVoteError::AlreadyVoted => ErrorCode::AlreadyVoted,
+            ElectionsVoteError::MissingBallotItem => ErrorCode::BallotItemNotFound,
+            ElectionsVoteError::ElectionClosed => ErrorCode::ElectionClosed,
+            ElectionsVoteError::NotCheckedIn => ErrorCode::NotCheckedIn,
+            ElectionsVoteError::NotEligible => ErrorCode::NotEligible,
+            ElectionsVoteError::NotYetOpen => ErrorCode::ElectionNotYetOpen,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ElectionsGetError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This election does not exist.")]
+    MissingElection,
+    #[error("You are not eligible to vote in this election.")]
+    NotEligible,
+}
+
+impl ApiErrorSource for ElectionsGetError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            ElectionsGetError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            ElectionsGetError::MissingElection => StatusCode::NOT_FOUND,
+            ElectionsGetError::NotEligible => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ElectionsGetError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            ElectionsGetError::MissingElection => ErrorCode::ElectionNotFound,
+            ElectionsGetError::NotEligible => ErrorCode::NotEligible,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ElectionsManageError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This election does not exist.")]
+    MissingElection,
+    #[error("This election is already closed.")]
+    AlreadyClosed,
+    #[error("This election needs to be closed first.")]
+    NotClosed,
+    #[error("Voting in this election has not started yet.")]
+    NotYetOpen,
+    #[error("This election is not in its nomination phase.")]
+    NotNominating,
+    #[error("No nomination is accepted, approved and seconded, so the ballot would be empty.")]
+    NoCandidates,
+    #[error("All amendments to this motion need to be closed first.")]
+    AmendmentsNotClosed,
+    #[error(
+        "Only {} of the {} participants needed for the quorum are present.",
+        .0.num_present,
+        .0.quorum
+    )]
+    QuorumNotMet(QuorumCheck),
+}
+
+impl ApiErrorSource for ElectionsManageError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            ElectionsManageError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            ElectionsManageError::MissingElection => StatusCode::NOT_FOUND,
+            ElectionsManageError::AlreadyClosed => StatusCode::CONFLICT,
+            ElectionsManageError::NotClosed => StatusCode::CONFLICT,
+            ElectionsManageError::NotYetOpen => StatusCode::CONFLICT,
+            ElectionsManageError::NotNominating => StatusCode::CONFLICT,
+            ElectionsManageError::NoCandidates => StatusCode::CONFLICT,
+            ElectionsManageError::AmendmentsNotClosed => StatusCode::CONFLICT,
+            ElectionsManageError::QuorumNotMet(_) => StatusCode::CONFLICT,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ElectionsManageError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            ElectionsManageError::MissingElection => ErrorCode::ElectionNotFound,
+            ElectionsManageError::AlreadyClosed => ErrorCode::ElectionAlreadyClosed,
+            ElectionsManageError::NotClosed => ErrorCode::ElectionNotClosed,
+            ElectionsManageError::NotYetOpen => ErrorCode::ElectionNotYetOpen,
+            ElectionsManageError::NotNominating => ErrorCode::ElectionNotNominating,
+            ElectionsManageError::NoCandidates => ErrorCode::NoCandidates,
+            ElectionsManageError::AmendmentsNotClosed => ErrorCode::AmendmentsNotClosed,
+            ElectionsManageError::QuorumNotMet(_) => ErrorCode::QuorumNotMet,
+        }
+    }
+
+    fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            ElectionsManageError::QuorumNotMet(quorum_check) => {
+                Some(quorum_check_details(quorum_check))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ElectionsCreateError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This agenda item does not exist.")]
+    MissingAgendaItem,
+    #[error("Group names must have 1 to 64 characters without surrounding whitespace.")]
+    InvalidGroupName,
+    #[error("Restricted elections need at least one eligible group.")]
+    NoEligibleGroups,
+    #[error("An attachment of a ballot item does not exist.")]
+    MissingAttachment,
+    #[error("The motion to amend does not exist.")]
+    MissingParentMotion,
+    #[error("The motion to amend is already being voted on or closed.")]
+    ParentMotionNotAwaitingAmendments,
+    #[error("The text to replace has to be a non-empty part of the motion.")]
+    AmendedTextNotFound,
+    #[error("Motions and amendments cannot have a nomination phase.")]
+    MotionWithNominations,
+    #[error(
+        "Only {} of the {} participants needed for the quorum are present.",
+        .0.num_present,
+        .0.quorum
+    )]
+    QuorumNotMet(QuorumCheck),
+}
+
+impl ApiErrorSource for ElectionsCreateError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            ElectionsCreateError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            ElectionsCreateError::MissingAgendaItem => StatusCode::NOT_FOUND,
+            ElectionsCreateError::InvalidGroupName => StatusCode::BAD_REQUEST,
+            ElectionsCreateError::NoEligibleGroups => StatusCode::BAD_REQUEST,
+            ElectionsCreateError::MissingAttachment => StatusCode::NOT_FOUND,
+            ElectionsCreateError::MissingParentMotion => StatusCode::NOT_FOUND,
+            ElectionsCreateError::ParentMotionNotAwaitingAmendments => StatusCode::CONFLICT,
+            ElectionsCreateError::AmendedTextNotFound => StatusCode::BAD_REQUEST,
+            ElectionsCreateError::MotionWithNominations => StatusCode::BAD_REQUEST,
+            ElectionsCreateError::QuorumNotMet(_) => StatusCode::CONFLICT,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ElectionsCreateError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            ElectionsCreateError::MissingAgendaItem => ErrorCode::AgendaItemNotFound,
+            ElectionsCreateError::InvalidGroupName => ErrorCode::InvalidGroupName,
+            ElectionsCreateError::NoEligibleGroups => ErrorCode::NoEligibleGroups,
+            ElectionsCreateError::MissingAttachment => ErrorCode::AttachmentNotFound,
+            ElectionsCreateError::MissingParentMotion => ErrorCode::ParentMotionNotFound,
+            ElectionsCreateError::ParentMotionNotAwaitingAmendments => {
+                ErrorCode::ParentMotionNotAwaitingAmendments
+            }
+            ElectionsCreateError::AmendedTextNotFound => ErrorCode::AmendedTextNotFound,
+            ElectionsCreateError::MotionWithNominations => ErrorCode::MotionWithNominations,
+            ElectionsCreateError::QuorumNotMet(_) => ErrorCode::QuorumNotMet,
+        }
+    }
+
+    fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            ElectionsCreateError::QuorumNotMet(quorum_check) => {
+                Some(quorum_check_details(quorum_check))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn quorum_check_details(quorum_check: &QuorumCheck) -> serde_json::Value {
+    serde_json::json!({
+        "quorum": quorum_check.quorum,
+        "numPresent": quorum_check.num_present,
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AgendaError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This agenda session does not exist.")]
+    MissingSession,
+    #[error("Document URLs must start with https://, http:// or /.")]
+    InvalidDocumentUrl,
+}
+
+impl ApiErrorSource for AgendaError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            AgendaError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            AgendaError::MissingSession => StatusCode::NOT_FOUND,
+            AgendaError::InvalidDocumentUrl => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            AgendaError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            AgendaError::MissingSession => ErrorCode::AgendaSessionNotFound,
+            AgendaError::InvalidDocumentUrl => ErrorCode::InvalidDocumentUrl,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventError {
+    #[error("There is no event at this address.")]
+    MissingEvent,
+    #[error("An event with this slug already exists.")]
+    SlugTaken,
+    #[error("Event slugs consist of up to 63 lowercase letters, digits and inner dashes.")]
+    InvalidSlug,
+    #[error("Events can only be managed by admins of the default event.")]
+    NotDefaultEvent,
+}
+
+impl ApiErrorSource for EventError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            EventError::MissingEvent => StatusCode::NOT_FOUND,
+            EventError::SlugTaken => StatusCode::CONFLICT,
+            EventError::InvalidSlug => StatusCode::BAD_REQUEST,
+            EventError::NotDefaultEvent => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            EventError::MissingEvent => ErrorCode::EventNotFound,
+            EventError::SlugTaken => ErrorCode::EventSlugTaken,
+            EventError::InvalidSlug => ErrorCode::InvalidEventSlug,
+            EventError::NotDefaultEvent => ErrorCode::NotDefaultEvent,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AdminAccountError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This admin account does not exist.")]
+    MissingAccount,
+    #[error("An admin account with this username already exists.")]
+    UsernameTaken,
+    #[error("The last super admin account cannot be deleted.")]
+    LastSuperAdmin,
+}
+
+impl ApiErrorSource for AdminAccountError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            AdminAccountError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            AdminAccountError::MissingAccount => StatusCode::NOT_FOUND,
+            AdminAccountError::UsernameTaken => StatusCode::CONFLICT,
+            AdminAccountError::LastSuperAdmin => StatusCode::CONFLICT,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            AdminAccountError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            AdminAccountError::MissingAccount => ErrorCode::AdminAccountNotFound,
+            AdminAccountError::UsernameTaken => ErrorCode::AdminUsernameTaken,
+            AdminAccountError::LastSuperAdmin => ErrorCode::LastSuperAdmin,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParticipantsManageError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This participant does not exist.")]
+    MissingParticipant,
+    #[error("Group names must have 1 to 64 characters without surrounding whitespace.")]
+    InvalidGroupName,
+}
+
+impl ApiErrorSource for ParticipantsManageError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            ParticipantsManageError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            ParticipantsManageError::MissingParticipant => StatusCode::NOT_FOUND,
+            ParticipantsManageError::InvalidGroupName => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ParticipantsManageError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            ParticipantsManageError::MissingParticipant => ErrorCode::ParticipantNotFound,
+            ParticipantsManageError::InvalidGroupName => ErrorCode::InvalidGroupName,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum NominationError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This election does not exist.")]
+    MissingElection,
+    #[error("This nomination does not exist.")]
+    MissingNomination,
+    #[error("The nominated participant does not exist.")]
+    MissingCandidate,
+    #[error("Nominations for this election are closed.")]
+    NotNominating,
+    #[error("Only participants eligible to vote in this election can nominate and second.")]
+    NotEligible,
+    #[error("The candidate name cannot be empty.")]
+    EmptyCandidateName,
+    #[error("This participant is already nominated in this election.")]
+    AlreadyNominated,
+    #[error("You already seconded this nomination.")]
+    AlreadySeconded,
+    #[error("You cannot second your own nomination.")]
+    OwnNomination,
+    #[error("Only the nominated candidate can accept or decline a nomination.")]
+    NotCandidate,
+}
+
+impl ApiErrorSource for NominationError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            NominationError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            NominationError::MissingElection => StatusCode::NOT_FOUND,
+            NominationError::MissingNomination => StatusCode::NOT_FOUND,
+            NominationError::MissingCandidate => StatusCode::NOT_FOUND,
+            NominationError::NotNominating => StatusCode::CONFLICT,
+            NominationError::NotEligible => StatusCode::FORBIDDEN,
+            NominationError::EmptyCandidateName => StatusCode::BAD_REQUEST,
+            NominationError::AlreadyNominated => StatusCode::CONFLICT,
+            NominationError::AlreadySeconded => StatusCode::CONFLICT,
+            NominationError::OwnNomination => StatusCode::FORBIDDEN,
+            NominationError::NotCandidate => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            NominationError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            NominationError::MissingElection => ErrorCode::ElectionNotFound,
+            NominationError::MissingNomination => ErrorCode::NominationNotFound,
+            NominationError::MissingCandidate => ErrorCode::ParticipantNotFound,
+            NominationError::NotNominating => ErrorCode::ElectionNotNominating,
+            NominationError::NotEligible => ErrorCode::NotEligible,
+            NominationError::EmptyCandidateName => ErrorCode::EmptyCandidateName,
+            NominationError::AlreadyNominated => ErrorCode::CandidateAlreadyNominated,
+            NominationError::AlreadySeconded => ErrorCode::AlreadySeconded,
+            NominationError::OwnNomination => ErrorCode::CannotSecondOwnNomination,
+            NominationError::NotCandidate => ErrorCode::NotCandidate,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AttachmentError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This attachment does not exist.")]
+    MissingAttachment,
+    #[error("Attachments must be PNG, JPEG, GIF or WebP images or PDF documents.")]
+    UnsupportedContentType,
+    #[error("The file content does not match its content type.")]
+    ContentMismatch,
+}
+
+impl ApiErrorSource for AttachmentError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            AttachmentError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            AttachmentError::MissingAttachment => StatusCode::NOT_FOUND,
+            AttachmentError::UnsupportedContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AttachmentError::ContentMismatch => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            AttachmentError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            AttachmentError::MissingAttachment => ErrorCode::AttachmentNotFound,
+            AttachmentError::UnsupportedContentType => ErrorCode::UnsupportedAttachmentType,
+            AttachmentError::ContentMismatch => ErrorCode::AttachmentContentMismatch,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AttendanceError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This participant does not exist.")]
+    MissingParticipant,
+    #[error("This participant is already checked in.")]
+    AlreadyCheckedIn,
+    #[error("This participant is not checked in.")]
+    AlreadyCheckedOut,
+}
+
+impl ApiErrorSource for AttendanceError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            AttendanceError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            AttendanceError::MissingParticipant => StatusCode::NOT_FOUND,
+            AttendanceError::AlreadyCheckedIn => StatusCode::CONFLICT,
+            AttendanceError::AlreadyCheckedOut => StatusCode::CONFLICT,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            AttendanceError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            AttendanceError::MissingParticipant => ErrorCode::ParticipantNotFound,
+            AttendanceError::AlreadyCheckedIn => ErrorCode::AlreadyCheckedIn,
+            AttendanceError::AlreadyCheckedOut => ErrorCode::AlreadyCheckedOut,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiTokenError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This API token does not exist or was already revoked.")]
+    MissingApiToken,
+}
+
+impl ApiErrorSource for ApiTokenError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            ApiTokenError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            ApiTokenError::MissingApiToken => StatusCode::NOT_FOUND,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            ApiTokenError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            ApiTokenError::MissingApiToken => ErrorCode::ApiTokenNotFound,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SpeakersError {
+    #[error("{0}")]
+    InvalidCredentials(#[from] InvalidCredentialsError),
+    #[error("This agenda item does not exist.")]
+    MissingAgendaItem,
+    #[error("This request to speak does not exist.")]
+    MissingSpeakerRequest,
+    #[error("You are already on the speakers list of this agenda item.")]
+    AlreadyRequested,
+    #[error("This speaker is not waiting anymore.")]
+    NotWaiting,
+    #[error("This speaker does not have the floor.")]
+    NotSpeaking,
+    #[error("You can only withdraw your own request to speak.")]
+    NotOwnRequest,
+    #[error("The new order has to contain every waiting speaker exactly once.")]
+    InvalidOrder,
+}
+
+impl ApiErrorSource for SpeakersError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            SpeakersError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.http_status_code()
+            }
+            SpeakersError::MissingAgendaItem => StatusCode::NOT_FOUND,
+            SpeakersError::MissingSpeakerRequest => StatusCode::NOT_FOUND,
+            SpeakersError::AlreadyRequested => StatusCode::CONFLICT,
+            SpeakersError::NotWaiting => StatusCode::CONFLICT,
+            SpeakersError::NotSpeaking => StatusCode::CONFLICT,
+            SpeakersError::NotOwnRequest => StatusCode::FORBIDDEN,
+            SpeakersError::InvalidOrder => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            SpeakersError::InvalidCredentials(invalid_credentials_error) => {
+                invalid_credentials_error.error_code()
+            }
+            SpeakersError::MissingAgendaItem => ErrorCode::AgendaItemNotFound,
+            SpeakersError::MissingSpeakerRequest => ErrorCode::SpeakerRequestNotFound,
+            SpeakersError::AlreadyRequested => ErrorCode::AlreadyRequestedToSpeak,
+            SpeakersError::NotWaiting => ErrorCode::SpeakerRequestNotWaiting,
+            SpeakersError::NotSpeaking => ErrorCode::NotSpeaking,
+            SpeakersError::NotOwnRequest => ErrorCode::NotOwnSpeakerRequest,
+            SpeakersError::InvalidOrder => ErrorCode::InvalidSpeakerOrder,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StateAuthorityError {
+    #[error("Unexpected internal error.")]
+    Unavailable,
+}
+
+impl ApiErrorSource for StateAuthorityError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            StateAuthorityError::Unavailable => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            StateAuthorityError::Unavailable => ErrorCode::InternalError,
+        }
+    }
+}
+
+/// Stops the server, since every request to the event would fail from then on.
+#[derive(Debug, thiserror::Error)]
+#[error("The state authority of event {event_slug} panicked: {panic_message}")]
+pub struct StateAuthorityPanic {
+    pub event_slug: String,
+    pub panic_message: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JsonBodyError {
+    #[error("The request body is larger than {0} bytes.")]
+    TooLarge(usize),
+    #[error("The request body could not be read.")]
+    Unreadable,
+    #[error("The request body is malformed: {0}")]
+    Malformed(#[from] serde_json::Error),
+}
+
+impl ApiErrorSource for JsonBodyError {
+    fn http_status_code(&self) -> StatusCode {
+        match self {
+            JsonBodyError::TooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            JsonBodyError::Unreadable => StatusCode::BAD_REQUEST,
+            JsonBodyError::Malformed(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            JsonBodyError::TooLarge(_) => ErrorCode::BodyTooLarge,
+            JsonBodyError::Unreadable => ErrorCode::BodyUnreadable,
+            JsonBodyError::Malformed(_) => ErrorCode::BodyMalformed,
+        }
+    }
+
+    fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            JsonBodyError::TooLarge(limit) => Some(serde_json::json!({ "limitBytes": limit })),
+            JsonBodyError::Unreadable => None,
+            JsonBodyError::Malformed(err) => Some(serde_json::json!({
+                "line": err.line(),
+                "column": err.column(),
+            })),
+        }
     }
 }
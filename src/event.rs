@@ -0,0 +1,353 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use hyper::StatusCode;
+use tokio::{sync::mpsc, task::JoinSet};
+use tracing::{error, info};
+
+use crate::{
+    admin::{AdminCreateAccountBody, AdminRole, AdminSession},
+    common::{HandlerResult, json_response},
+    error::{ApiError, EventError, StateAuthorityPanic},
+    metrics::QueueDepth,
+    router::RouteRequest,
+    state::{self, Message, StateAuthorityHandle},
+};
+
+/// The event that is served on the base domain itself. Its admins manage all other events.
+pub const DEFAULT_EVENT_SLUG: &str = "main";
+
+/// Username of the super admin account every event starts with.
+pub const INITIAL_ADMIN_USERNAME: &str = "admin";
+
+const STATE_AUTHORITY_CHANNEL_CAPACITY: usize = 512;
+
+/// How long the readiness check waits for the state authorities to answer a ping.
+const READINESS_PING_DEADLINE: Duration = Duration::from_secs(1);
+
+/// A convention or sub-convention. Every event has its own state authority, so participants,
+/// elections, admin sessions and API tokens of different events never mix.
+pub struct Event {
+    pub slug: String,
+    pub name: String,
+    participant_password: String,
+    pub state_authority: StateAuthorityHandle,
+}
+
+impl Event {
+    /// Spawns the state authority of the new event. If it panics, the panic is sent to
+    /// `panic_sender`.
+    fn spawn(
+        admin_create_event_body: AdminCreateEventBody,
+        with_demo_data: bool,
+        panic_sender: mpsc::UnboundedSender<StateAuthorityPanic>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(STATE_AUTHORITY_CHANNEL_CAPACITY);
+        let initial_admin_account = AdminCreateAccountBody {
+            username: String::from(INITIAL_ADMIN_USERNAME),
+            password: admin_create_event_body.admin_password,
+            role: AdminRole::SuperAdmin,
+        };
+        let state_authority_task = tokio::spawn(state::central_state_authority(
+            receiver,
+            initial_admin_account,
+            with_demo_data,
+        ));
+
+        let event_slug = admin_create_event_body.slug.clone();
+        tokio::spawn(async move {
+            let Err(join_error) = state_authority_task.await else {
+                return;
+            };
+            let Ok(panic) = join_error.try_into_panic() else {
+                return;
+            };
+
+            let panic_message = panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("unknown panic payload"));
+            error!("The state authority of event {event_slug} panicked: {panic_message}");
+
+            let _ = panic_sender.send(StateAuthorityPanic {
+                event_slug,
+                panic_message,
+            });
+        });
+
+        Self {
+            slug: admin_create_event_body.slug,
+            name: admin_create_event_body.name,
+            participant_password: admin_create_event_body.participant_password,
+            state_authority: StateAuthorityHandle::new(sender),
+        }
+    }
+
+    pub fn is_participant_password(&self, password: &str) -> bool {
+        self.participant_password == password
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.slug == DEFAULT_EVENT_SLUG
+    }
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EventSummary {
+    pub slug: String,
+    pub name: String,
+    /// Whether this is the organization's default event, whose admins manage all events.
+    pub is_default: bool,
+}
+
+impl From<&Event> for EventSummary {
+    fn from(event: &Event) -> Self {
+        Self {
+            slug: event.slug.clone(),
+            name: event.name.clone(),
+            is_default: event.is_default(),
+        }
+    }
+}
+
+#[derive(Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminCreateEventBody {
+    /// Lowercase letters, digits and dashes. The event is served at `<slug>.<base domain>`.
+    pub slug: String,
+    pub name: String,
+    pub participant_password: String,
+    /// Password of the event's initial super admin account `admin`.
+    pub admin_password: String,
+}
+
+/// Request bodies are logged, passwords must not be.
+impl fmt::Debug for AdminCreateEventBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdminCreateEventBody")
+            .field("slug", &self.slug)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// All events of the server, looked up by the subdomain of each request.
+#[derive(Clone)]
+pub struct Events {
+    events_by_slug: Arc<RwLock<HashMap<String, Arc<Event>>>>,
+    base_domain: Arc<str>,
+    state_authority_panic_sender: mpsc::UnboundedSender<StateAuthorityPanic>,
+}
+
+impl Events {
+    /// Spawns the default event. The returned receiver gets the panic of any event's state
+    /// authority, after which that event can no longer answer requests.
+    pub fn new(
+        base_domain: &str,
+        default_event_body: AdminCreateEventBody,
+        with_demo_data: bool,
+    ) -> (Self, mpsc::UnboundedReceiver<StateAuthorityPanic>) {
+        let (state_authority_panic_sender, state_authority_panic_receiver) =
+            mpsc::unbounded_channel();
+
+        let default_event = Event::spawn(
+            default_event_body,
+            with_demo_data,
+            state_authority_panic_sender.clone(),
+        );
+        let events_by_slug = HashMap::from([(default_event.slug.clone(), Arc::new(default_event))]);
+
+        let events = Self {
+            events_by_slug: Arc::new(RwLock::new(events_by_slug)),
+            base_domain: Arc::from(base_domain.to_ascii_lowercase()),
+            state_authority_panic_sender,
+        };
+
+        (events, state_authority_panic_receiver)
+    }
+
+    /// `<slug>.<base domain>` selects the event `slug`. The base domain itself and every
+    /// other host, e.g. a plain IP address, select the default event.
+    pub fn resolve(&self, host: Option<&str>) -> Result<Arc<Event>, EventError> {
+        let host_name = host
+            .and_then(|host| host.split(':').next())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        let slug = match host_name
+            .strip_suffix(&*self.base_domain)
+            .and_then(|subdomain| subdomain.strip_suffix('.'))
+        {
+            Some(subdomain) => subdomain,
+            None => DEFAULT_EVENT_SLUG,
+        };
+
+        self.get(slug).ok_or(EventError::MissingEvent)
+    }
+
+    /// The event of [server-wide](crate::router::Route::server_wide) routes, whatever the host.
+    pub fn default_event(&self) -> Result<Arc<Event>, EventError> {
+        self.get(DEFAULT_EVENT_SLUG).ok_or(EventError::MissingEvent)
+    }
+
+    fn get(&self, slug: &str) -> Option<Arc<Event>> {
+        let events_by_slug = self
+            .events_by_slug
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+        events_by_slug.get(slug).cloned()
+    }
+
+    /// Spawns the state authority of a new event without demo data.
+    fn spawn(
+        &self,
+        admin_create_event_body: AdminCreateEventBody,
+    ) -> Result<EventSummary, EventError> {
+        let mut events_by_slug = self
+            .events_by_slug
+            .write()
+            .unwrap_or_else(|e| e.into_inner());
+
+        if events_by_slug.contains_key(&admin_create_event_body.slug) {
+            return Err(EventError::SlugTaken);
+        }
+
+        let event = Event::spawn(
+            admin_create_event_body,
+            false,
+            self.state_authority_panic_sender.clone(),
+        );
+        let event_summary = EventSummary::from(&event);
+        events_by_slug.insert(event.slug.clone(), Arc::new(event));
+
+        Ok(event_summary)
+    }
+
+    /// Slugs of the events whose state authority does not answer a ping in time.
+    pub async fn unresponsive_slugs(&self) -> Vec<String> {
+        let events: Vec<_> = self
+            .events_by_slug
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+
+        let mut pings = JoinSet::new();
+        for event in events {
+            pings.spawn(async move {
+                let is_responsive = event.state_authority.ping(READINESS_PING_DEADLINE).await;
+                (event, is_responsive)
+            });
+        }
+
+        let mut unresponsive_slugs = Vec::new();
+        while let Some(ping) = pings.join_next().await {
+            if let Ok((event, false)) = ping {
+                unresponsive_slugs.push(event.slug.clone());
+            }
+        }
+        unresponsive_slugs.sort();
+
+        unresponsive_slugs
+    }
+
+    pub fn queue_depths(&self) -> Vec<QueueDepth> {
+        let events_by_slug = self
+            .events_by_slug
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let mut queue_depths: Vec<_> = events_by_slug
+            .values()
+            .map(|event| QueueDepth {
+                event_slug: event.slug.clone(),
+                num_messages: event.state_authority.queue_depth(),
+                capacity: event.state_authority.queue_capacity(),
+            })
+            .collect();
+        queue_depths.sort_by(|a, b| a.event_slug.cmp(&b.event_slug));
+
+        queue_depths
+    }
+
+    fn summaries(&self) -> Vec<EventSummary> {
+        let events_by_slug = self
+            .events_by_slug
+            .read()
+            .unwrap_or_else(|e| e.into_inner());
+
+        let mut summaries: Vec<_> = events_by_slug
+            .values()
+            .map(|event| EventSummary::from(event.as_ref()))
+            .collect();
+        summaries.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+        summaries
+    }
+}
+
+fn is_valid_slug(slug: &str) -> bool {
+    (1..=63).contains(&slug.len())
+        && slug
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-')
+        && !slug.starts_with('-')
+        && !slug.ends_with('-')
+}
+
+/// Only super admins of the default event may manage events. Answers with their username.
+async fn check_default_event_super_admin(
+    route_request: &mut RouteRequest,
+) -> Result<String, ApiError> {
+    let requesting_admin_session = route_request.take_extension::<AdminSession>()?;
+
+    if !route_request.event.is_default() {
+        return Err(EventError::NotDefaultEvent.into());
+    }
+
+    let actor = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminCheckSuperAdmin {
+            answer_sender,
+            requesting_admin_session,
+        })
+        .await??;
+
+    Ok(actor)
+}
+
+pub async fn get_current(route_request: RouteRequest) -> HandlerResult {
+    json_response(
+        StatusCode::OK,
+        &EventSummary::from(route_request.event.as_ref()),
+    )
+}
+
+pub async fn get_all(mut route_request: RouteRequest) -> HandlerResult {
+    check_default_event_super_admin(&mut route_request).await?;
+
+    json_response(StatusCode::OK, &route_request.events.summaries())
+}
+
+pub async fn create(mut route_request: RouteRequest) -> HandlerResult {
+    let body = route_request.take_extension::<AdminCreateEventBody>()?;
+
+    let actor = check_default_event_super_admin(&mut route_request).await?;
+
+    if !is_valid_slug(&body.slug) {
+        return Err(EventError::InvalidSlug.into());
+    }
+
+    let event_summary = route_request.events.spawn(body)?;
+    info!("{actor} created event {}", event_summary.slug);
+
+    json_response(StatusCode::CREATED, &event_summary)
+}
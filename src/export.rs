@@ -0,0 +1,414 @@
+use std::fmt::Write;
+
+use http_body_util::Full;
+use hyper::{
+    Response, StatusCode,
+    body::Bytes,
+    header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339, macros::format_description};
+
+use crate::{
+    common::{HandlerResult, json_response},
+    election::{
+        BallotItemId, Election, ElectionId, ElectionStatus, Eligibility, Outcome, QuorumCheck,
+        VotingMethod,
+    },
+    error::ApiError,
+    motion::Motion,
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
+};
+
+/// Everything the minutes need to know about a closed election.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectionReport {
+    pub election_id: ElectionId,
+    pub name: String,
+    pub method: VotingMethod,
+    pub eligibility: Eligibility,
+    pub motion: Option<Motion>,
+    #[serde(with = "time::serde::rfc3339")]
+    #[schemars(with = "String")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    #[schemars(with = "String")]
+    pub closed_at: OffsetDateTime,
+    pub num_eligible_participants: usize,
+    pub num_votes_cast: usize,
+    /// Share of eligible participants who voted, `null` if nobody was eligible.
+    pub turnout_percent: Option<f64>,
+    /// `null` if no quorum was set when the election was closed.
+    pub quorum_check: Option<QuorumCheck>,
+    pub ballot_items: Vec<BallotItemReport>,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BallotItemReport {
+    pub id: BallotItemId,
+    pub name: String,
+    pub num_votes: usize,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultsExport {
+    #[serde(with = "time::serde::rfc3339")]
+    #[schemars(with = "String")]
+    pub generated_at: OffsetDateTime,
+    pub elections: Vec<ElectionReport>,
+}
+
+impl ElectionReport {
+    /// Returns `None` for elections that are still open.
+    pub fn from_closed_election(election: &Election) -> Option<Self> {
+        let ElectionStatus::Closed {
+            closed_at,
+            num_eligible_participants,
+            quorum_check,
+        } = election.status
+        else {
+            return None;
+        };
+
+        let num_votes_cast = election.participant_ids_who_voted.len();
+        let turnout_percent = (num_eligible_participants > 0)
+            .then(|| 100.0 * num_votes_cast as f64 / num_eligible_participants as f64);
+
+        let mut ballot_items: Vec<_> = election
+            .ballot_items_by_id
+            .values()
+            .map(|ballot_item| BallotItemReport {
+                id: ballot_item.id,
+                name: ballot_item.name.clone(),
+                num_votes: ballot_item.num_votes,
+            })
+            .collect();
+        ballot_items.sort_by_key(|ballot_item| ballot_item.id);
+
+        Some(Self {
+            election_id: election.id,
+            name: election.name.clone(),
+            method: election.method,
+            eligibility: election.eligibility.clone(),
+            motion: election.motion.clone(),
+            created_at: election.created_at,
+            closed_at,
+            num_eligible_participants,
+            num_votes_cast,
+            turnout_percent,
+            quorum_check,
+            ballot_items,
+            outcome: election.outcome(),
+        })
+    }
+
+    fn ballot_item_name(&self, ballot_item_id: BallotItemId) -> &str {
+        self.ballot_items
+            .iter()
+            .find(|ballot_item| ballot_item.id == ballot_item_id)
+            .map_or("?", |ballot_item| ballot_item.name.as_str())
+    }
+
+    fn threshold_description(&self) -> &'static str {
+        self.method
+            .threshold()
+            .map_or("", |threshold| threshold.description())
+    }
+
+    /// The text that was voted on, empty for elections that are not about a motion.
+    fn motion_description(&self) -> String {
+        match &self.motion {
+            None => String::new(),
+            Some(Motion::Main {
+                text,
+                consolidated_text,
+                ..
+            }) => consolidated_text.as_ref().unwrap_or(text).clone(),
+            Some(Motion::Amendment {
+                parent_election_id,
+                original_text,
+                replacement_text,
+            }) => format!(
+                "In motion {parent_election_id}, replace \"{original_text}\" by \"{replacement_text}\""
+            ),
+        }
+    }
+
+    fn outcome_description(&self) -> String {
+        match &self.outcome {
+            Outcome::Decided { ballot_item_id } => {
+                format!("Decided: {}", self.ballot_item_name(*ballot_item_id))
+            }
+            Outcome::Tie { ballot_item_ids } => {
+                let names: Vec<_> = ballot_item_ids
+                    .iter()
+                    .map(|ballot_item_id| self.ballot_item_name(*ballot_item_id))
+                    .collect();
+                format!("Tie between {}", names.join(", "))
+            }
+            Outcome::NoMajority => String::from("No ballot item reached the required majority"),
+            Outcome::NoVotes => String::from("No votes were cast"),
+        }
+    }
+}
+
+pub async fn results_json(route_request: RouteRequest) -> HandlerResult {
+    let results_export = fetch_results_export(route_request).await?;
+    json_response(StatusCode::OK, &results_export)
+}
+
+pub async fn results_csv(route_request: RouteRequest) -> HandlerResult {
+    let results_export = fetch_results_export(route_request).await?;
+
+    Ok(Response::builder()
+        .header(CONTENT_TYPE, "text/csv; charset=utf-8")
+        .header(
+            CONTENT_DISPOSITION,
+            "attachment; filename=\"coco-results.csv\"",
+        )
+        .body(Full::new(Bytes::from_owner(render_csv(&results_export))))?)
+}
+
+pub async fn minutes_html(route_request: RouteRequest) -> HandlerResult {
+    let results_export = fetch_results_export(route_request).await?;
+
+    Ok(Response::builder()
+        .header(CONTENT_TYPE, "text/html; charset=utf-8")
+        .body(Full::new(Bytes::from_owner(render_minutes_html(
+            &results_export,
+        ))))?)
+}
+
+async fn fetch_results_export(mut route_request: RouteRequest) -> Result<ResultsExport, ApiError> {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let elections = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminGetElectionReports {
+            answer_sender,
+            requesting_credentials,
+        })
+        .await??;
+
+    Ok(ResultsExport {
+        generated_at: OffsetDateTime::now_utc(),
+        elections,
+    })
+}
+
+fn render_csv(results_export: &ResultsExport) -> String {
+    let mut csv = String::from(
+        "election_id,election_name,method,threshold,created_at,closed_at,\
+         num_eligible_participants,num_votes_cast,turnout_percent,\
+         quorum,num_present,quorum_met,ballot_item_id,ballot_item_name,num_votes,outcome\r\n",
+    );
+
+    for election in &results_export.elections {
+        // An election without ballot items still gets a row, with empty ballot item columns.
+        let ballot_item_fields: Vec<_> = if election.ballot_items.is_empty() {
+            vec![[String::new(), String::new(), String::new()]]
+        } else {
+            election
+                .ballot_items
+                .iter()
+                .map(|ballot_item| {
+                    [
+                        ballot_item.id.to_string(),
+                        ballot_item.name.clone(),
+                        ballot_item.num_votes.to_string(),
+                    ]
+                })
+                .collect()
+        };
+
+        for [ballot_item_id, ballot_item_name, num_votes] in ballot_item_fields {
+            let fields = [
+                election.election_id.to_string(),
+                election.name.clone(),
+                election.method.description().to_string(),
+                election.threshold_description().to_string(),
+                format_rfc3339(election.created_at),
+                format_rfc3339(election.closed_at),
+                election.num_eligible_participants.to_string(),
+                election.num_votes_cast.to_string(),
+                election
+                    .turnout_percent
+                    .map_or(String::new(), |turnout| format!("{turnout:.1}")),
+                election.quorum_check.map_or(String::new(), |quorum_check| {
+                    quorum_check.quorum.to_string()
+                }),
+                election.quorum_check.map_or(String::new(), |quorum_check| {
+                    quorum_check.num_present.to_string()
+                }),
+                election.quorum_check.map_or(String::new(), |quorum_check| {
+                    quorum_check.is_met.to_string()
+                }),
+                ballot_item_id,
+                ballot_item_name,
+                num_votes,
+                election.outcome_description(),
+            ];
+
+            let escaped: Vec<_> = fields.iter().map(|field| escape_csv(field)).collect();
+            csv.push_str(&escaped.join(","));
+            csv.push_str("\r\n");
+        }
+    }
+
+    csv
+}
+
+fn render_minutes_html(results_export: &ResultsExport) -> String {
+    let mut html = String::from(MINUTES_HTML_HEAD);
+
+    let _ = write!(
+        html,
+        "<h1>Voting results</h1>\n<p class=\"meta\">Generated on {}</p>\n",
+        format_human(results_export.generated_at)
+    );
+
+    if results_export.elections.is_empty() {
+        html.push_str("<p>No elections have been closed yet.</p>\n");
+    }
+
+    for election in &results_export.elections {
+        let _ = write!(
+            html,
+            "<section>\n<h2>{id}. {name}</h2>\n<dl>\n\
+             <dt>Method</dt><dd>{method}</dd>\n\
+             <dt>Threshold</dt><dd>{threshold}</dd>\n\
+             <dt>Eligible</dt><dd>{eligibility}</dd>\n\
+             {motion}\
+             <dt>Opened</dt><dd>{created_at}</dd>\n\
+             <dt>Closed</dt><dd>{closed_at}</dd>\n\
+             <dt>Votes cast</dt><dd>{num_votes_cast} of {num_eligible} eligible ({turnout})</dd>\n\
+             {quorum}\
+             </dl>\n<table>\n<thead><tr><th>Ballot item</th><th>Votes</th></tr></thead>\n<tbody>\n",
+            id = election.election_id,
+            name = escape_html(&election.name),
+            method = election.method.description(),
+            threshold = match election.threshold_description() {
+                "" => "none",
+                threshold => threshold,
+            },
+            eligibility = escape_html(&election.eligibility.description()),
+            motion = match election.motion_description().as_str() {
+                "" => String::new(),
+                motion => format!(
+                    "<dt>Motion</dt><dd class=\"motion\">{}</dd>\n",
+                    escape_html(motion)
+                ),
+            },
+            created_at = format_human(election.created_at),
+            closed_at = format_human(election.closed_at),
+            num_votes_cast = election.num_votes_cast,
+            num_eligible = election.num_eligible_participants,
+            turnout = election
+                .turnout_percent
+                .map_or(String::from("turnout n/a"), |turnout| format!(
+                    "{turnout:.1} % turnout"
+                )),
+            quorum = election
+                .quorum_check
+                .map_or(String::new(), |quorum_check| format!(
+                    "<dt>Quorum</dt><dd>{} of {} present, {}</dd>\n",
+                    quorum_check.num_present,
+                    quorum_check.quorum,
+                    if quorum_check.is_met {
+                        "met"
+                    } else {
+                        "not met"
+                    }
+                )),
+        );
+
+        for ballot_item in &election.ballot_items {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                escape_html(&ballot_item.name),
+                ballot_item.num_votes
+            );
+        }
+
+        let _ = write!(
+            html,
+            "</tbody>\n</table>\n<p class=\"outcome\">{}</p>\n</section>\n",
+            escape_html(&election.outcome_description())
+        );
+    }
+
+    html.push_str(MINUTES_HTML_SIGNATURES);
+    html
+}
+
+fn format_rfc3339(timestamp: OffsetDateTime) -> String {
+    timestamp.format(&Rfc3339).unwrap_or_default()
+}
+
+fn format_human(timestamp: OffsetDateTime) -> String {
+    timestamp
+        .format(format_description!(
+            "[year]-[month]-[day] [hour]:[minute]:[second] UTC"
+        ))
+        .unwrap_or_default()
+}
+
+/// Also neutralises formulas: ballot item names may come from participants through
+/// nominations, and spreadsheets evaluate fields starting with one of these characters.
+fn escape_csv(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{field}")
+    } else {
+        field.to_string()
+    };
+
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// The styles are served from `frontend/minutes.css`, the Content-Security-Policy forbids inline
+/// ones.
+const MINUTES_HTML_HEAD: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8" />
+<title>coco - Minutes</title>
+<link rel="stylesheet" href="/minutes.css" />
+</head>
+<body>
+"#;
+
+const MINUTES_HTML_SIGNATURES: &str = r#"<div class="signatures">
+<div class="signature">Place, date</div>
+<div class="signature">Chair</div>
+<div class="signature">Secretary</div>
+</div>
+</body>
+</html>
+"#;
@@ -1,154 +1,234 @@
-use std::{collections::HashMap, sync::LazyLock};
+mod asset;
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use http_body_util::Full;
+use hyper::{
+    HeaderMap, Response, StatusCode,
+    body::Bytes,
+    header::{
+        ACCEPT_ENCODING, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY,
+    },
+};
+use tracing::{debug, error, info};
+
+use self::asset::FileKind;
+use crate::{common::HandlerResult, error::ApiError, router::RouteRequest};
 
 const INCLUDE_SOURCEMAPS_AND_TS: bool = true;
 
-pub static FRONTEND_FILES: LazyLock<HashMap<&'static str, &'static FrontEndFile>> =
-    LazyLock::new(|| {
-        FILE_DATA
+/// Set to the frontend directory, e.g. `frontend`, to serve the files from disk instead of the
+/// ones embedded at build time, so that changes show up without rebuilding the server.
+const FRONTEND_DIR_ENV_VAR: &str = "COCO_FRONTEND_DIR";
+
+/// Served from disk in development, read on every request.
+static FRONTEND_DIR: LazyLock<Option<PathBuf>> =
+    LazyLock::new(|| env::var_os(FRONTEND_DIR_ENV_VAR).map(PathBuf::from));
+
+/// Whether the server runs for frontend development.
+pub fn is_served_from_disk() -> bool {
+    FRONTEND_DIR.is_some()
+}
+
+/// The paths the frontend is served at. Files added to the frontend directory in development
+/// are only served after restarting the server.
+pub fn served_paths() -> Vec<&'static str> {
+    match &*FRONTEND_DIR {
+        Some(frontend_dir) => match asset::assets(frontend_dir) {
+            Ok(assets) => {
+                info!("Serving the frontend from {frontend_dir:?}");
+                assets
+                    .into_iter()
+                    .filter(|asset| is_included(asset.kind))
+                    .map(|asset| &*asset.path.leak())
+                    .collect()
+            }
+            Err(e) => {
+                error!("Unable to read the frontend directory {frontend_dir:?}: {e}");
+                Vec::new()
+            }
+        },
+        None => FILE_DATA
             .iter()
-            .filter_map(|file_data| {
-                if !INCLUDE_SOURCEMAPS_AND_TS
-                    && (file_data.kind == FileKind::JsMap || file_data.kind == FileKind::Ts)
-                {
-                    None
-                } else {
-                    Some((file_data.path, file_data))
-                }
-            })
-            .collect()
-    });
+            .filter(|file_data| is_included(file_data.kind))
+            .map(|file_data| file_data.path)
+            .collect(),
+    }
+}
+
+fn is_included(kind: FileKind) -> bool {
+    INCLUDE_SOURCEMAPS_AND_TS || !matches!(kind, FileKind::JsMap | FileKind::Ts)
+}
+
+pub async fn serve_file(route_request: RouteRequest) -> HandlerResult {
+    let path = route_request.request.uri().path().to_string();
+    let request_headers = route_request.request.headers();
+
+    let Some(frontend_dir) = &*FRONTEND_DIR else {
+        let Some(file_data) = FILE_DATA.iter().find(|file_data| file_data.path == path) else {
+            return Err(ApiError::not_found());
+        };
+
+        debug!("Serving frontend file {}", file_data.name);
+
+        return file_data.respond(request_headers);
+    };
 
+    let frontend_dir = frontend_dir.clone();
+    let read_file = tokio::task::spawn_blocking(move || read_from_disk(&frontend_dir, &path));
+    let Ok(Some((kind, name, content))) = read_file.await else {
+        return Err(ApiError::not_found());
+    };
+
+    debug!("Serving frontend file {name} from disk");
+
+    let representation = Representation {
+        content_hash: &asset::content_hash(&content),
+        content: Bytes::from(content),
+        encoding: None,
+    };
+    respond(request_headers, kind, representation, "no-cache", false)
+}
+
+fn read_from_disk(frontend_dir: &Path, path: &str) -> Option<(FileKind, String, Vec<u8>)> {
+    let assets = asset::assets(frontend_dir).ok()?;
+    let asset = assets.into_iter().find(|asset| asset.path == path)?;
+    let content = std::fs::read(&asset.file_path).ok()?;
+
+    Some((asset.kind, asset.name, content))
+}
+
+/// A file embedded at build time.
 pub struct FrontEndFile {
     pub kind: FileKind,
+    /// Relative to the frontend directory.
     pub name: &'static str,
     pub path: &'static str,
     pub content: &'static [u8],
+    /// Only set if compressing makes the file smaller.
+    pub gzip: Option<&'static [u8]>,
+    pub brotli: Option<&'static [u8]>,
+    pub content_hash: &'static str,
 }
 
-const FILE_DATA: &[FrontEndFile] = &[
-    FrontEndFile {
-        kind: FileKind::Html,
-        name: "index.html",
-        path: "/",
-        content: include_bytes!("../frontend/index.html"),
-    },
-    FrontEndFile {
-        kind: FileKind::Js,
-        name: "index.js",
-        path: "/index.js",
-        content: include_bytes!("../frontend/out/index.js"),
-    },
-    FrontEndFile {
-        kind: FileKind::Ts,
-        name: "index.ts",
-        path: "/index.ts",
-        content: include_bytes!("../frontend/index.ts"),
-    },
-    FrontEndFile {
-        kind: FileKind::JsMap,
-        name: "index.js.map",
-        path: "/index.js.map",
-        content: include_bytes!("../frontend/out/index.js.map"),
-    },
-    FrontEndFile {
-        kind: FileKind::Html,
-        name: "login.html",
-        path: "/login",
-        content: include_bytes!("../frontend/login.html"),
-    },
-    FrontEndFile {
-        kind: FileKind::Js,
-        name: "login.js",
-        path: "/login.js",
-        content: include_bytes!("../frontend/out/login.js"),
-    },
-    FrontEndFile {
-        kind: FileKind::Ts,
-        name: "login.ts",
-        path: "/login.ts",
-        content: include_bytes!("../frontend/login.ts"),
-    },
-    FrontEndFile {
-        kind: FileKind::JsMap,
-        name: "login.js.map",
-        path: "/login.js.map",
-        content: include_bytes!("../frontend/out/login.js.map"),
-    },
-    FrontEndFile {
-        kind: FileKind::Html,
-        name: "admin/index.html",
-        path: "/admin",
-        content: include_bytes!("../frontend/admin/index.html"),
-    },
-    FrontEndFile {
-        kind: FileKind::Js,
-        name: "admin/index.js",
-        path: "/admin/index.js",
-        content: include_bytes!("../frontend/out/admin/index.js"),
-    },
-    FrontEndFile {
-        kind: FileKind::Ts,
-        name: "admin/index.ts",
-        path: "/admin/index.ts",
-        content: include_bytes!("../frontend/admin/index.ts"),
-    },
-    FrontEndFile {
-        kind: FileKind::JsMap,
-        name: "admin/index.js.map",
-        path: "/admin/index.js.map",
-        content: include_bytes!("../frontend/out/admin/index.js.map"),
-    },
-    FrontEndFile {
-        kind: FileKind::Html,
-        name: "admin/login.html",
-        path: "/admin/login",
-        content: include_bytes!("../frontend/admin/login.html"),
-    },
-    FrontEndFile {
-        kind: FileKind::Js,
-        name: "admin/login.js",
-        path: "/admin/login.js",
-        content: include_bytes!("../frontend/out/admin/login.js"),
-    },
-    FrontEndFile {
-        kind: FileKind::Ts,
-        name: "admin/login.ts",
-        path: "/admin/login.ts",
-        content: include_bytes!("../frontend/admin/login.ts"),
-    },
-    FrontEndFile {
-        kind: FileKind::JsMap,
-        name: "admin/login.js.map",
-        path: "/admin/login.js.map",
-        content: include_bytes!("../frontend/out/admin/login.js.map"),
-    },
-    FrontEndFile {
-        kind: FileKind::Ico,
-        name: "favicon.ico",
-        path: "/favicon.ico",
-        content: include_bytes!("../frontend/assets/favicon.ico"),
-    },
-];
-
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub enum FileKind {
-    Html,
-    Css,
-    Js,
-    Ts,
-    JsMap,
-    Ico,
+impl FrontEndFile {
+    fn respond(&self, request_headers: &HeaderMap) -> HandlerResult {
+        let accept_encoding = request_headers
+            .get(ACCEPT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        let representation = [(self.brotli, "br"), (self.gzip, "gzip")]
+            .into_iter()
+            .find_map(|(content, encoding)| {
+                content
+                    .filter(|_| accepts_encoding(accept_encoding, encoding))
+                    .map(|content| Representation {
+                        content: Bytes::from_static(content),
+                        content_hash: self.content_hash,
+                        encoding: Some(encoding),
+                    })
+            })
+            .unwrap_or(Representation {
+                content: Bytes::from_static(self.content),
+                content_hash: self.content_hash,
+                encoding: None,
+            });
+
+        // Paths do not change with the content, so browsers have to revalidate everything but
+        // the favicon.
+        let cache_control = match self.kind {
+            FileKind::Ico => "public, max-age=86400",
+            _ => "no-cache",
+        };
+
+        respond(
+            request_headers,
+            self.kind,
+            representation,
+            cache_control,
+            self.gzip.is_some() || self.brotli.is_some(),
+        )
+    }
 }
 
-impl FileKind {
-    pub fn content_type(&self) -> &'static str {
-        match self {
-            FileKind::Html => "text/html; charset=utf-8",
-            FileKind::Css => "text/css; charset=utf-8",
-            FileKind::Js => "text/javascript; charset=utf-8",
-            FileKind::Ts => "text/typescript; charset=utf-8",
-            FileKind::JsMap => "application/json; charset=utf-8",
-            FileKind::Ico => "image/x-icon",
+include!(concat!(env!("OUT_DIR"), "/frontend_files.rs"));
+
+struct Representation<'a> {
+    content: Bytes,
+    content_hash: &'a str,
+    /// The `Content-Encoding`, `None` if the content is not compressed.
+    encoding: Option<&'static str>,
+}
+
+impl Representation<'_> {
+    /// Differs between the encodings, as their bytes differ.
+    fn etag(&self) -> String {
+        match self.encoding {
+            Some(encoding) => format!("\"{}-{encoding}\"", self.content_hash),
+            None => format!("\"{}\"", self.content_hash),
         }
     }
 }
+
+/// Answers `304 Not Modified` if the browser already has this representation.
+fn respond(
+    request_headers: &HeaderMap,
+    kind: FileKind,
+    representation: Representation,
+    cache_control: &'static str,
+    varies_by_encoding: bool,
+) -> HandlerResult {
+    let etag = representation.etag();
+    let not_modified = request_headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|if_none_match| matches_etag(if_none_match, &etag));
+
+    let mut response_builder = Response::builder()
+        .header(ETAG, &etag)
+        .header(CACHE_CONTROL, cache_control);
+    if varies_by_encoding {
+        response_builder = response_builder.header(VARY, "Accept-Encoding");
+    }
+
+    if not_modified {
+        return Ok(response_builder
+            .status(StatusCode::NOT_MODIFIED)
+            .body(Full::new(Bytes::new()))?);
+    }
+
+    response_builder = response_builder.header(CONTENT_TYPE, kind.content_type());
+    if let Some(encoding) = representation.encoding {
+        response_builder = response_builder.header(CONTENT_ENCODING, encoding);
+    }
+
+    Ok(response_builder.body(Full::new(representation.content))?)
+}
+
+/// Whether an `Accept-Encoding` header allows `encoding`, i.e. lists it or `*` without `q=0`.
+fn accepts_encoding(accept_encoding: &str, encoding: &str) -> bool {
+    accept_encoding.split(',').any(|coding| {
+        let mut parameters = coding.split(';').map(str::trim);
+        let name = parameters.next().unwrap_or_default();
+        let is_refused = parameters.any(|parameter| {
+            parameter
+                .strip_prefix("q=")
+                .and_then(|quality| quality.parse::<f32>().ok())
+                .is_some_and(|quality| quality == 0.0)
+        });
+
+        (name.eq_ignore_ascii_case(encoding) || name == "*") && !is_refused
+    })
+}
+
+/// Whether an `If-None-Match` header lists `etag`. Weak tags match, as only `GET` is served.
+fn matches_etag(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
@@ -0,0 +1,144 @@
+//! Which file of the frontend directory is served at which path. Also compiled into `build.rs`,
+//! which embeds the files, so only `std` may be used here.
+
+use std::{
+    fs,
+    hash::{DefaultHasher, Hasher},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Where the TypeScript compiler writes the JavaScript files and source maps.
+const COMPILED_DIR: &str = "out";
+/// Files served at the root, e.g. `assets/favicon.ico` at `/favicon.ico`.
+const ASSETS_DIR: &str = "assets";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Html,
+    Css,
+    Js,
+    Ts,
+    JsMap,
+    Ico,
+}
+
+impl FileKind {
+    fn of(relative_path: &str) -> Option<Self> {
+        let kind = if relative_path.ends_with(".js.map") {
+            FileKind::JsMap
+        } else if relative_path.ends_with(".d.ts") {
+            return None;
+        } else {
+            match relative_path.rsplit_once('.')?.1 {
+                "html" => FileKind::Html,
+                "css" => FileKind::Css,
+                "js" => FileKind::Js,
+                "ts" => FileKind::Ts,
+                "ico" => FileKind::Ico,
+                _ => return None,
+            }
+        };
+
+        Some(kind)
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            FileKind::Html => "text/html; charset=utf-8",
+            FileKind::Css => "text/css; charset=utf-8",
+            FileKind::Js => "text/javascript; charset=utf-8",
+            FileKind::Ts => "text/typescript; charset=utf-8",
+            FileKind::JsMap => "application/json; charset=utf-8",
+            FileKind::Ico => "image/x-icon",
+        }
+    }
+}
+
+/// A file of the frontend directory that is served.
+#[derive(Debug)]
+pub struct Asset {
+    pub kind: FileKind,
+    /// Relative to the frontend directory, with `/` as separator.
+    pub name: String,
+    pub path: String,
+    pub file_path: PathBuf,
+}
+
+/// The path at which the file at `name`, relative to the frontend directory, is served:
+///
+/// - compiled `out/<name>.js` and `out/<name>.js.map` at `/<name>.js` and `/<name>.js.map`,
+/// - `assets/<name>` at `/<name>`,
+/// - `<dir>/index.html` at `/<dir>` and other pages `<name>.html` at `/<name>`,
+/// - stylesheets and TypeScript sources at `/<name>`.
+fn served_path(name: &str) -> Option<(String, FileKind)> {
+    let kind = FileKind::of(name)?;
+
+    let path = if let Some(compiled_name) = name.strip_prefix(&format!("{COMPILED_DIR}/")) {
+        if !matches!(kind, FileKind::Js | FileKind::JsMap) {
+            return None;
+        }
+        format!("/{compiled_name}")
+    } else if matches!(kind, FileKind::Js | FileKind::JsMap) {
+        return None;
+    } else if let Some(asset_name) = name.strip_prefix(&format!("{ASSETS_DIR}/")) {
+        format!("/{asset_name}")
+    } else if kind == FileKind::Html {
+        let page = name.strip_suffix(".html").unwrap_or(name);
+        let page = if page == "index" {
+            ""
+        } else {
+            page.strip_suffix("/index").unwrap_or(page)
+        };
+        format!("/{page}")
+    } else {
+        format!("/{name}")
+    };
+
+    Some((path, kind))
+}
+
+/// All served files of `frontend_dir`, ordered by path.
+pub fn assets(frontend_dir: &Path) -> io::Result<Vec<Asset>> {
+    let mut assets = Vec::new();
+    let mut dirs = vec![frontend_dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let file_path = entry?.path();
+            if file_path.is_dir() {
+                dirs.push(file_path);
+                continue;
+            }
+
+            let Ok(relative_path) = file_path.strip_prefix(frontend_dir) else {
+                continue;
+            };
+            let name = relative_path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            if let Some((path, kind)) = served_path(&name) {
+                assets.push(Asset {
+                    kind,
+                    name,
+                    path,
+                    file_path,
+                });
+            }
+        }
+    }
+
+    assets.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(assets)
+}
+
+/// Identifies `content` in `ETag`s, without the quotes.
+pub fn content_hash(content: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(content);
+    format!("{:016x}", hasher.finish())
+}
@@ -0,0 +1,36 @@
+use hyper::StatusCode;
+
+use crate::{
+    common::{HandlerResult, json_response, ok_response},
+    router::RouteRequest,
+};
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Readiness {
+    /// Slugs of the events whose state authority did not answer in time.
+    pub unresponsive_events: Vec<String>,
+}
+
+/// Answers as long as the process accepts connections.
+pub async fn get_liveness(_route_request: RouteRequest) -> HandlerResult {
+    ok_response()
+}
+
+/// Ready when the state authority of every event answers a ping in time.
+pub async fn get_readiness(route_request: RouteRequest) -> HandlerResult {
+    let unresponsive_events = route_request.events.unresponsive_slugs().await;
+
+    let status = if unresponsive_events.is_empty() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    json_response(
+        status,
+        &Readiness {
+            unresponsive_events,
+        },
+    )
+}
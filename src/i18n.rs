@@ -0,0 +1,115 @@
+//! Languages of the user interface and of the API's error messages. Conventions are held in
+//! German and English, so names of elections and ballot items may be translated, too.
+
+mod catalog;
+
+use std::collections::BTreeMap;
+
+use hyper::{
+    HeaderMap,
+    header::{ACCEPT_LANGUAGE, COOKIE},
+};
+
+use crate::common::get_cookie_value;
+
+pub use self::catalog::error_message;
+
+/// Set by the frontend's language switcher, takes precedence over `Accept-Language`.
+pub const LOCALE_COOKIE_KEY: &str = "coco_locale";
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// Texts entered by admins, e.g. [`Election::name`](crate::election::Election::name), are
+    /// expected in this language.
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    /// The language tag without region, e.g. `de` for `de-AT`.
+    fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+
+        if primary.eq_ignore_ascii_case("en") {
+            Some(Locale::En)
+        } else if primary.eq_ignore_ascii_case("de") {
+            Some(Locale::De)
+        } else {
+            None
+        }
+    }
+
+    /// The locale of a request: the `coco_locale` cookie if it names a supported locale,
+    /// otherwise the preferred supported language of `Accept-Language`.
+    pub fn negotiate(headers: &HeaderMap) -> Self {
+        let preference = headers
+            .get(COOKIE)
+            .and_then(|cookie| get_cookie_value(cookie.as_bytes(), LOCALE_COOKIE_KEY.as_bytes()))
+            .and_then(Locale::from_tag);
+
+        preference
+            .or_else(|| {
+                headers
+                    .get(ACCEPT_LANGUAGE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(preferred_locale)
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The supported locale with the highest quality in an `Accept-Language` header. Of equally
+/// preferred languages, the first listed wins.
+fn preferred_locale(accept_language: &str) -> Option<Locale> {
+    let mut preferred: Option<(Locale, f32)> = None;
+
+    for language_range in accept_language.split(',') {
+        let mut parameters = language_range.split(';').map(str::trim);
+        let Some(locale) = parameters.next().and_then(Locale::from_tag) else {
+            continue;
+        };
+        let quality = parameters
+            .find_map(|parameter| parameter.strip_prefix("q="))
+            .map_or(Some(1.0), |quality| quality.parse::<f32>().ok())
+            .unwrap_or(0.0);
+
+        if quality > 0.0 && preferred.is_none_or(|(_, best_quality)| quality > best_quality) {
+            preferred = Some((locale, quality));
+        }
+    }
+
+    preferred.map(|(locale, _)| locale)
+}
+
+/// Translations of the texts an admin entered, by locale. Missing translations fall back to
+/// the untranslated text.
+pub type Translations<T> = BTreeMap<Locale, T>;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectionTranslation {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BallotItemTranslation {
+    pub name: String,
+    /// Markdown. If empty, the untranslated description is shown.
+    #[serde(default)]
+    pub description: String,
+}
@@ -0,0 +1,121 @@
+//! Error messages by locale. The English ones are the `Display` texts of the error types, which
+//! may be more specific than the catalog, e.g. name the limit of a request body.
+
+use super::Locale;
+use crate::error::ErrorCode;
+
+/// The message for `code` in `locale`, `None` for English.
+pub fn error_message(code: ErrorCode, locale: Locale) -> Option<&'static str> {
+    match locale {
+        Locale::En => None,
+        Locale::De => Some(german(code)),
+    }
+}
+
+fn german(code: ErrorCode) -> &'static str {
+    match code {
+        ErrorCode::NotLoggedIn => "Bitte melde dich zuerst an.",
+        ErrorCode::WrongToken => "Das Anmeldetoken für diese teilnehmende Person ist falsch.",
+        ErrorCode::WrongPassword => "Falscher Benutzername oder falsches Passwort.",
+        ErrorCode::InternalError => "Unerwarteter interner Fehler.",
+        ErrorCode::NotFound => "CoCo kennt diese Seite nicht.",
+        ErrorCode::MethodNotAllowed => "Diese Seite unterstützt die Anfragemethode nicht.",
+        ErrorCode::BodyTooLarge => "Der Inhalt der Anfrage ist zu groß.",
+        ErrorCode::BodyUnreadable => "Der Inhalt der Anfrage konnte nicht gelesen werden.",
+        ErrorCode::BodyMalformed => "Der Inhalt der Anfrage ist fehlerhaft.",
+        ErrorCode::ElectionNotFound => "Diese Wahl existiert nicht.",
+        ErrorCode::BallotItemNotFound => "Die Wahl wurde von der Versammlungsleitung geändert.",
+        ErrorCode::AlreadyVoted => "Du hast bereits abgestimmt.",
+        ErrorCode::InsufficientScope => {
+            "Das API-Token hat nicht die für diese Aktion nötige Berechtigung."
+        }
+        ErrorCode::ApiTokenNotFound => {
+            "Dieses API-Token existiert nicht oder wurde bereits widerrufen."
+        }
+        ErrorCode::ElectionClosed => "Die Wahl ist bereits beendet.",
+        ErrorCode::ElectionAlreadyClosed => "Diese Wahl ist bereits beendet.",
+        ErrorCode::ElectionNotClosed => "Diese Wahl muss zuerst beendet werden.",
+        ErrorCode::AgendaSessionNotFound => "Diese Sitzung existiert nicht.",
+        ErrorCode::AgendaItemNotFound => "Dieser Tagesordnungspunkt existiert nicht.",
+        ErrorCode::InvalidDocumentUrl => {
+            "Dokument-URLs müssen mit https://, http:// oder / beginnen."
+        }
+        ErrorCode::EventNotFound => "Unter dieser Adresse gibt es keine Veranstaltung.",
+        ErrorCode::EventSlugTaken => "Eine Veranstaltung mit diesem Kürzel existiert bereits.",
+        ErrorCode::InvalidEventSlug => {
+            "Kürzel von Veranstaltungen bestehen aus bis zu 63 Kleinbuchstaben, Ziffern und \
+             inneren Bindestrichen."
+        }
+        ErrorCode::NotDefaultEvent => {
+            "Veranstaltungen können nur von Admins der Standardveranstaltung verwaltet werden."
+        }
+        ErrorCode::InsufficientRole => "Deine Admin-Rolle erlaubt diese Aktion nicht.",
+        ErrorCode::AdminAccountNotFound => "Dieses Admin-Konto existiert nicht.",
+        ErrorCode::AdminUsernameTaken => {
+            "Ein Admin-Konto mit diesem Benutzernamen existiert bereits."
+        }
+        ErrorCode::LastSuperAdmin => "Das letzte Super-Admin-Konto kann nicht gelöscht werden.",
+        ErrorCode::NotCheckedIn => "Nur am Empfang eingecheckte Teilnehmende dürfen abstimmen.",
+        ErrorCode::ParticipantNotFound => "Diese teilnehmende Person existiert nicht.",
+        ErrorCode::AlreadyCheckedIn => "Diese Person ist bereits eingecheckt.",
+        ErrorCode::AlreadyCheckedOut => "Diese Person ist nicht eingecheckt.",
+        ErrorCode::NotEligible => "Du bist bei dieser Wahl nicht stimmberechtigt.",
+        ErrorCode::InvalidGroupName => {
+            "Gruppennamen müssen 1 bis 64 Zeichen ohne umgebende Leerzeichen haben."
+        }
+        ErrorCode::NoEligibleGroups => {
+            "Eingeschränkte Wahlen brauchen mindestens eine stimmberechtigte Gruppe."
+        }
+        ErrorCode::ElectionNotYetOpen => "Die Abstimmung in dieser Wahl hat noch nicht begonnen.",
+        ErrorCode::ElectionNotNominating => "Die Nominierungen für diese Wahl sind geschlossen.",
+        ErrorCode::NoCandidates => {
+            "Keine Nominierung ist angenommen, bestätigt und unterstützt, der Stimmzettel wäre \
+             leer."
+        }
+        ErrorCode::NominationNotFound => "Diese Nominierung existiert nicht.",
+        ErrorCode::EmptyCandidateName => "Der Name der Kandidatur darf nicht leer sein.",
+        ErrorCode::CandidateAlreadyNominated => {
+            "Diese Person ist in dieser Wahl bereits nominiert."
+        }
+        ErrorCode::AlreadySeconded => "Du hast diese Nominierung bereits unterstützt.",
+        ErrorCode::CannotSecondOwnNomination => {
+            "Du kannst deine eigene Nominierung nicht unterstützen."
+        }
+        ErrorCode::NotCandidate => {
+            "Nur die nominierte Person kann eine Nominierung annehmen oder ablehnen."
+        }
+        ErrorCode::AttachmentNotFound => "Dieser Anhang existiert nicht.",
+        ErrorCode::UnsupportedAttachmentType => {
+            "Anhänge müssen PNG-, JPEG-, GIF- oder WebP-Bilder oder PDF-Dokumente sein."
+        }
+        ErrorCode::AttachmentContentMismatch => {
+            "Der Inhalt der Datei passt nicht zu ihrem Inhaltstyp."
+        }
+        ErrorCode::AmendmentsNotClosed => {
+            "Alle Änderungsanträge zu diesem Antrag müssen zuerst beendet werden."
+        }
+        ErrorCode::ParentMotionNotFound => "Der zu ändernde Antrag existiert nicht.",
+        ErrorCode::ParentMotionNotAwaitingAmendments => {
+            "Über den zu ändernden Antrag wird bereits abgestimmt oder er ist beendet."
+        }
+        ErrorCode::AmendedTextNotFound => {
+            "Der zu ersetzende Text muss ein nicht leerer Teil des Antrags sein."
+        }
+        ErrorCode::MotionWithNominations => {
+            "Anträge und Änderungsanträge können keine Nominierungsphase haben."
+        }
+        ErrorCode::SpeakerRequestNotFound => "Diese Wortmeldung existiert nicht.",
+        ErrorCode::AlreadyRequestedToSpeak => {
+            "Du stehst bereits auf der Redeliste dieses Tagesordnungspunkts."
+        }
+        ErrorCode::SpeakerRequestNotWaiting => "Diese Wortmeldung wartet nicht mehr.",
+        ErrorCode::NotSpeaking => "Diese Person hat gerade nicht das Wort.",
+        ErrorCode::NotOwnSpeakerRequest => "Du kannst nur deine eigene Wortmeldung zurückziehen.",
+        ErrorCode::InvalidSpeakerOrder => {
+            "Die neue Reihenfolge muss jede wartende Wortmeldung genau einmal enthalten."
+        }
+        ErrorCode::QuorumNotMet => {
+            "Es sind nicht genug Teilnehmende anwesend, um das Quorum zu erreichen."
+        }
+    }
+}
@@ -0,0 +1,776 @@
+mod admin;
+mod agenda;
+mod api_token;
+mod attachment;
+mod attendance;
+mod common;
+mod election;
+mod error;
+pub mod event;
+mod export;
+mod frontend;
+mod health;
+mod i18n;
+pub mod logging;
+mod metrics;
+mod motion;
+mod nomination;
+mod openapi;
+mod participant;
+mod presentation;
+mod router;
+mod security_headers;
+mod speaker;
+mod state;
+
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, StatusCode, header::HOST};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::task;
+use tracing::{Instrument, error, info, info_span};
+
+use std::{collections::HashMap, error::Error, sync::Arc, time::Instant};
+
+use crate::admin::{
+    AdminAccount, AdminCreateAccountBody, AdminCreateElectionBody, AdminLoginAttemptBody,
+};
+use crate::agenda::{
+    AdminCreateAgendaItemBody, AdminCreateAgendaSessionBody, AgendaItem, AgendaSession,
+    ElectionsByAgenda,
+};
+use crate::api_token::{AdminCreateApiTokenBody, ApiToken, ApiTokenScope, CreatedApiToken};
+use crate::attachment::Attachment;
+use crate::attendance::{AttendanceSettings, AttendanceSummary};
+use crate::common::ResponseResult;
+use crate::election::{BallotItemId, Election, ElectionId, ElectionResults, ElectionsVoteBody};
+use crate::error::{ApiError, StateAuthorityPanic};
+use crate::event::{AdminCreateEventBody, EventSummary, Events};
+use crate::export::ResultsExport;
+use crate::health::Readiness;
+use crate::i18n::Locale;
+use crate::logging::{REQUEST_ID_HEADER, RequestId};
+use crate::metrics::METRICS;
+use crate::nomination::{NominateBody, Nomination};
+use crate::openapi::{ApiDoc, Auth};
+use crate::participant::{AddParticipantBody, AdminSetParticipantGroupsBody, ParticipantSummary};
+use crate::presentation::Presentation;
+use crate::router::middleware::{
+    json_body, json_body_limited, require_admin_credentials, require_admin_session,
+    require_credentials, require_participant,
+};
+use crate::router::{Route, Router};
+use crate::speaker::{
+    AdminCallSpeakerBody, AdminOrderSpeakersBody, RequestToSpeakBody, SpeakerRequest, SpeakersList,
+};
+
+/// Serves the requests accepted by `listener` until accepting fails or the state authority of
+/// an event panics.
+pub async fn serve(
+    listener: TcpListener,
+    events: Events,
+    mut state_authority_panic_receiver: mpsc::UnboundedReceiver<StateAuthorityPanic>,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let router = Arc::new(build_router());
+
+    loop {
+        let (tcp_stream, address) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            Some(state_authority_panic) = state_authority_panic_receiver.recv() => {
+                error!("Stopping the server, since requests to the event would keep failing");
+                return Err(state_authority_panic.into());
+            }
+        };
+        info!("Accepted connection from: {}", address);
+
+        let connection_io_stream = TokioIo::new(tcp_stream);
+        let events = events.clone();
+        let router = Arc::clone(&router);
+
+        task::spawn(async move {
+            let _connection_guard = METRICS.connection_opened();
+            let service = service_fn(|request| handle_request(request, &router, events.clone()));
+
+            if let Err(err) = http1::Builder::new()
+                .serve_connection(connection_io_stream, service)
+                .await
+            {
+                error!("Error serving connection: {:?}", err);
+            }
+        });
+    }
+}
+
+fn build_router() -> Router {
+    const LOGIN_BODY_LIMIT: usize = 1024;
+
+    let router = frontend::served_paths()
+        .into_iter()
+        .fold(Router::new(), |router, path| {
+            router.route(Route::get(path, frontend::serve_file))
+        });
+
+    router
+        .route(
+            Route::post("/api/v1/participants", participant::add)
+                .with(json_body_limited::<AddParticipantBody, LOGIN_BODY_LIMIT>)
+                .doc(
+                    ApiDoc::new(
+                        "Log in as a new participant. Sets the participant cookies.",
+                        Auth::None,
+                    )
+                    .request::<AddParticipantBody>()
+                    .status(StatusCode::CREATED),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/participants/me/votes", participant::get_votes)
+                .with(require_participant)
+                .doc(
+                    ApiDoc::new(
+                        "Selected ballot item id of the requesting participant by election id.",
+                        Auth::Participant,
+                    )
+                    .response::<HashMap<ElectionId, BallotItemId>>(),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/elections", election::get_all)
+                .with(require_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "All elections, grouped by agenda session and agenda item.",
+                        Auth::Any(ApiTokenScope::ReadElections),
+                    )
+                    .response::<ElectionsByAgenda>(),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/elections/{election_id}", election::get_one)
+                .with(require_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "A single election.",
+                        Auth::Any(ApiTokenScope::ReadElections),
+                    )
+                    .response::<Election>(),
+                ),
+        )
+        .route(
+            Route::get(
+                "/api/v1/elections/{election_id}/results",
+                election::get_results,
+            )
+            .with(require_admin_credentials)
+            .doc(
+                ApiDoc::new(
+                    "Vote counts of an election.",
+                    Auth::Admin(ApiTokenScope::ReadResults),
+                )
+                .response::<ElectionResults>(),
+            ),
+        )
+        .route(
+            Route::post("/api/v1/votes", election::vote)
+                .with(require_participant)
+                .with(json_body::<ElectionsVoteBody>)
+                .doc(
+                    ApiDoc::new(
+                        "Cast the vote of the requesting participant.",
+                        Auth::Participant,
+                    )
+                    .request::<ElectionsVoteBody>(),
+                ),
+        )
+        .route(
+            Route::post("/api/v1/admin/sessions", admin::start_session)
+                .with(json_body_limited::<AdminLoginAttemptBody, LOGIN_BODY_LIMIT>)
+                .doc(
+                    ApiDoc::new(
+                        "Log in to an admin account. Sets the admin cookies.",
+                        Auth::None,
+                    )
+                    .request::<AdminLoginAttemptBody>()
+                    .status(StatusCode::CREATED),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/admin/accounts/me", admin::get_own_account)
+                .with(require_admin_session)
+                .doc(
+                    ApiDoc::new(
+                        "The admin account of the requesting session.",
+                        Auth::AdminSession,
+                    )
+                    .response::<AdminAccount>(),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/admin/accounts", admin::get_accounts)
+                .with(require_admin_session)
+                .doc(
+                    ApiDoc::new("All admin accounts, without their passwords.", Auth::SuperAdmin)
+                        .response::<Vec<AdminAccount>>(),
+                ),
+        )
+        .route(
+            Route::post("/api/v1/admin/accounts", admin::create_account)
+                .with(require_admin_session)
+                .with(json_body_limited::<AdminCreateAccountBody, LOGIN_BODY_LIMIT>)
+                .doc(
+                    ApiDoc::new("Create an admin account.", Auth::SuperAdmin)
+                        .request::<AdminCreateAccountBody>()
+                        .response::<AdminAccount>()
+                        .status(StatusCode::CREATED),
+                ),
+        )
+        .route(
+            Route::delete(
+                "/api/v1/admin/accounts/{admin_account_id}",
+                admin::delete_account,
+            )
+            .with(require_admin_session)
+            .doc(ApiDoc::new(
+                "Delete an admin account. Its sessions end immediately.",
+                Auth::SuperAdmin,
+            )),
+        )
+        .route(
+            Route::post("/api/v1/admin/elections", admin::create_election)
+                .with(require_admin_credentials)
+                .with(json_body::<AdminCreateElectionBody>)
+                .doc(
+                    ApiDoc::new(
+                        "Create a new election.",
+                        Auth::Admin(ApiTokenScope::ManageElections),
+                    )
+                    .request::<AdminCreateElectionBody>(),
+                ),
+        )
+        .route(
+            Route::post("/api/v1/admin/attachments", attachment::upload)
+                .with(require_admin_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "Upload a PNG, JPEG, GIF or WebP image or a PDF document of up to 5 MiB as the raw request body, with a matching Content-Type header. Its id can be used for ballot items.",
+                        Auth::Admin(ApiTokenScope::ManageElections),
+                    )
+                    .response::<Attachment>()
+                    .status(StatusCode::CREATED),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/attachments/{attachment_id}", attachment::download)
+                .with(require_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "Download an attachment of a ballot item.",
+                        Auth::Any(ApiTokenScope::ReadElections),
+                    )
+                    .response_text("application/octet-stream"),
+                ),
+        )
+        .route(
+            Route::post(
+                "/api/v1/admin/elections/{election_id}/close",
+                admin::close_election,
+            )
+            .with(require_admin_credentials)
+            .doc(ApiDoc::new(
+                "Close an election. No more votes are accepted afterwards.",
+                Auth::Admin(ApiTokenScope::ManageElections),
+            )),
+        )
+        .route(
+            Route::post(
+                "/api/v1/admin/elections/{election_id}/open",
+                admin::open_election,
+            )
+            .with(require_admin_credentials)
+            .doc(ApiDoc::new(
+                "End the nomination phase and start voting with the accepted, approved and seconded nominations.",
+                Auth::Admin(ApiTokenScope::ManageElections),
+            )),
+        )
+        .route(
+            Route::get(
+                "/api/v1/elections/{election_id}/nominations",
+                nomination::get_all,
+            )
+            .with(require_credentials)
+            .doc(
+                ApiDoc::new(
+                    "All nominations of an election.",
+                    Auth::Any(ApiTokenScope::ReadElections),
+                )
+                .response::<Vec<Nomination>>(),
+            ),
+        )
+        .route(
+            Route::post(
+                "/api/v1/elections/{election_id}/nominations",
+                nomination::nominate,
+            )
+            .with(require_participant)
+            .with(json_body::<NominateBody>)
+            .doc(
+                ApiDoc::new(
+                    "Nominate a candidate while the election is nominating.",
+                    Auth::Participant,
+                )
+                .request::<NominateBody>()
+                .response::<Nomination>()
+                .status(StatusCode::CREATED),
+            ),
+        )
+        .route(
+            Route::post("/api/v1/nominations/{nomination_id}/second", nomination::second)
+                .with(require_participant)
+                .doc(ApiDoc::new(
+                    "Second somebody else's nomination.",
+                    Auth::Participant,
+                )),
+        )
+        .route(
+            Route::post("/api/v1/nominations/{nomination_id}/accept", nomination::accept)
+                .with(require_participant)
+                .doc(ApiDoc::new(
+                    "Accept a nomination as its candidate.",
+                    Auth::Participant,
+                )),
+        )
+        .route(
+            Route::post("/api/v1/nominations/{nomination_id}/decline", nomination::decline)
+                .with(require_participant)
+                .doc(ApiDoc::new(
+                    "Decline a nomination as its candidate.",
+                    Auth::Participant,
+                )),
+        )
+        .route(
+            Route::post(
+                "/api/v1/admin/nominations/{nomination_id}/approve",
+                nomination::approve,
+            )
+            .with(require_admin_credentials)
+            .doc(ApiDoc::new(
+                "Approve a nomination after reviewing it.",
+                Auth::Admin(ApiTokenScope::ManageElections),
+            )),
+        )
+        .route(
+            Route::post(
+                "/api/v1/admin/nominations/{nomination_id}/reject",
+                nomination::reject,
+            )
+            .with(require_admin_credentials)
+            .doc(ApiDoc::new(
+                "Reject a nomination, it will not be on the ballot.",
+                Auth::Admin(ApiTokenScope::ManageElections),
+            )),
+        )
+        .route(
+            Route::post(
+                "/api/v1/admin/elections/{election_id}/publish",
+                admin::publish_results,
+            )
+            .with(require_admin_credentials)
+            .doc(ApiDoc::new(
+                "Show the results of a closed election in the presentation mode.",
+                Auth::Admin(ApiTokenScope::ManageElections),
+            )),
+        )
+        .route(
+            Route::get("/api/v1/presentation", presentation::get)
+                .with(require_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "What the projector shows: the current election with its turnout and, once published, its results.",
+                        Auth::Any(ApiTokenScope::ReadElections),
+                    )
+                    .response::<Presentation>(),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/event", event::get_current).doc(
+                ApiDoc::new("The event served at the requested host.", Auth::None)
+                    .response::<EventSummary>(),
+            ),
+        )
+        .route(
+            Route::get("/api/v1/admin/events", event::get_all)
+                .with(require_admin_session)
+                .doc(
+                    ApiDoc::new(
+                        "All events of the server. Only for super admins of the default event.",
+                        Auth::SuperAdmin,
+                    )
+                    .response::<Vec<EventSummary>>(),
+                ),
+        )
+        .route(
+            Route::post("/api/v1/admin/events", event::create)
+                .with(require_admin_session)
+                .with(json_body_limited::<AdminCreateEventBody, LOGIN_BODY_LIMIT>)
+                .doc(
+                    ApiDoc::new(
+                        "Create an event, served at its own subdomain. Only for super admins of the default event.",
+                        Auth::SuperAdmin,
+                    )
+                    .request::<AdminCreateEventBody>()
+                    .response::<EventSummary>()
+                    .status(StatusCode::CREATED),
+                ),
+        )
+        .route(
+            Route::post("/api/v1/admin/agenda/sessions", agenda::create_session)
+                .with(require_admin_credentials)
+                .with(json_body::<AdminCreateAgendaSessionBody>)
+                .doc(
+                    ApiDoc::new(
+                        "Add a session to the convention agenda.",
+                        Auth::Admin(ApiTokenScope::ManageElections),
+                    )
+                    .request::<AdminCreateAgendaSessionBody>()
+                    .response::<AgendaSession>()
+                    .status(StatusCode::CREATED),
+                ),
+        )
+        .route(
+            Route::post("/api/v1/admin/agenda/items", agenda::create_item)
+                .with(require_admin_credentials)
+                .with(json_body::<AdminCreateAgendaItemBody>)
+                .doc(
+                    ApiDoc::new(
+                        "Add an agenda item to a session.",
+                        Auth::Admin(ApiTokenScope::ManageElections),
+                    )
+                    .request::<AdminCreateAgendaItemBody>()
+                    .response::<AgendaItem>()
+                    .status(StatusCode::CREATED),
+                ),
+        )
+        .route(
+            Route::get(
+                "/api/v1/agenda/items/{agenda_item_id}/speakers",
+                speaker::get_list,
+            )
+            .with(require_credentials)
+            .doc(
+                ApiDoc::new(
+                    "The speakers list of an agenda item: who has the floor, who waits and who already spoke.",
+                    Auth::Any(ApiTokenScope::ReadElections),
+                )
+                .response::<SpeakersList>(),
+            ),
+        )
+        .route(
+            Route::post(
+                "/api/v1/agenda/items/{agenda_item_id}/speakers",
+                speaker::request_to_speak,
+            )
+            .with(require_participant)
+            .with(json_body::<RequestToSpeakBody>)
+            .doc(
+                ApiDoc::new(
+                    "Request to speak on an agenda item.",
+                    Auth::Participant,
+                )
+                .request::<RequestToSpeakBody>()
+                .response::<SpeakerRequest>()
+                .status(StatusCode::CREATED),
+            ),
+        )
+        .route(
+            Route::delete(
+                "/api/v1/speaker-requests/{speaker_request_id}",
+                speaker::withdraw,
+            )
+            .with(require_participant)
+            .doc(ApiDoc::new(
+                "Withdraw one's own request to speak before being called.",
+                Auth::Participant,
+            )),
+        )
+        .route(
+            Route::put(
+                "/api/v1/admin/agenda/items/{agenda_item_id}/speakers/order",
+                speaker::order,
+            )
+            .with(require_admin_credentials)
+            .with(json_body::<AdminOrderSpeakersBody>)
+            .doc(
+                ApiDoc::new(
+                    "Reorder the waiting speakers of an agenda item.",
+                    Auth::Admin(ApiTokenScope::ManageElections),
+                )
+                .request::<AdminOrderSpeakersBody>(),
+            ),
+        )
+        .route(
+            Route::post(
+                "/api/v1/admin/speaker-requests/{speaker_request_id}/call",
+                speaker::call,
+            )
+            .with(require_admin_credentials)
+            .with(json_body::<AdminCallSpeakerBody>)
+            .doc(
+                ApiDoc::new(
+                    "Give the floor to a waiting speaker. The current speaker of the agenda item is finished.",
+                    Auth::Admin(ApiTokenScope::ManageElections),
+                )
+                .request::<AdminCallSpeakerBody>(),
+            ),
+        )
+        .route(
+            Route::post(
+                "/api/v1/admin/speaker-requests/{speaker_request_id}/finish",
+                speaker::finish,
+            )
+            .with(require_admin_credentials)
+            .doc(ApiDoc::new(
+                "End the speech of the speaker who has the floor.",
+                Auth::Admin(ApiTokenScope::ManageElections),
+            )),
+        )
+        .route(
+            Route::delete(
+                "/api/v1/admin/speaker-requests/{speaker_request_id}",
+                speaker::remove,
+            )
+            .with(require_admin_credentials)
+            .doc(ApiDoc::new(
+                "Remove a waiting speaker from the speakers list.",
+                Auth::Admin(ApiTokenScope::ManageElections),
+            )),
+        )
+        .route(
+            Route::get("/api/v1/admin/exports/results.json", export::results_json)
+                .with(require_admin_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "Results of all closed elections as JSON.",
+                        Auth::Admin(ApiTokenScope::ReadResults),
+                    )
+                    .response::<ResultsExport>(),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/admin/exports/results.csv", export::results_csv)
+                .with(require_admin_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "Results of all closed elections as CSV, one row per ballot item.",
+                        Auth::Admin(ApiTokenScope::ReadResults),
+                    )
+                    .response_text("text/csv"),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/admin/exports/minutes.html", export::minutes_html)
+                .with(require_admin_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "Printable minutes of all closed elections.",
+                        Auth::Admin(ApiTokenScope::ReadResults),
+                    )
+                    .response_text("text/html"),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/admin/participants", participant::get_all)
+                .with(require_admin_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "All participants, without their selected ballot items.",
+                        Auth::Admin(ApiTokenScope::ManageParticipants),
+                    )
+                    .response::<Vec<ParticipantSummary>>(),
+                ),
+        )
+        .route(
+            Route::put(
+                "/api/v1/admin/participants/{participant_id}/groups",
+                participant::set_groups,
+            )
+            .with(require_admin_credentials)
+            .with(json_body::<AdminSetParticipantGroupsBody>)
+            .doc(
+                ApiDoc::new(
+                    "Replace the groups of a participant, which decide the elections they may vote in.",
+                    Auth::Admin(ApiTokenScope::ManageParticipants),
+                )
+                .request::<AdminSetParticipantGroupsBody>(),
+            ),
+        )
+        .route(
+            Route::post(
+                "/api/v1/admin/participants/{participant_id}/check-in",
+                attendance::check_in,
+            )
+            .with(require_admin_credentials)
+            .doc(ApiDoc::new(
+                "Check a participant in at the registration desk.",
+                Auth::Admin(ApiTokenScope::ManageParticipants),
+            )),
+        )
+        .route(
+            Route::post(
+                "/api/v1/admin/participants/{participant_id}/check-out",
+                attendance::check_out,
+            )
+            .with(require_admin_credentials)
+            .doc(ApiDoc::new(
+                "Check a participant out when they leave the venue.",
+                Auth::Admin(ApiTokenScope::ManageParticipants),
+            )),
+        )
+        .route(
+            Route::get("/api/v1/attendance", attendance::get_summary)
+                .with(require_credentials)
+                .doc(
+                    ApiDoc::new(
+                        "How many participants are registered and how many are present.",
+                        Auth::Any(ApiTokenScope::ReadElections),
+                    )
+                    .response::<AttendanceSummary>(),
+                ),
+        )
+        .route(
+            Route::put("/api/v1/admin/attendance/settings", attendance::update_settings)
+                .with(require_admin_credentials)
+                .with(json_body::<AttendanceSettings>)
+                .doc(
+                    ApiDoc::new(
+                        "Change whether participants must be checked in to vote.",
+                        Auth::Admin(ApiTokenScope::ManageElections),
+                    )
+                    .request::<AttendanceSettings>(),
+                ),
+        )
+        .route(
+            Route::post("/api/v1/admin/api-tokens", api_token::create)
+                .with(require_admin_session)
+                .with(json_body::<AdminCreateApiTokenBody>)
+                .doc(
+                    ApiDoc::new(
+                        "Issue an API token for a machine client.",
+                        Auth::SuperAdmin,
+                    )
+                    .request::<AdminCreateApiTokenBody>()
+                    .response::<CreatedApiToken>()
+                    .status(StatusCode::CREATED),
+                ),
+        )
+        .route(
+            Route::get("/api/v1/admin/api-tokens", api_token::get_all)
+                .with(require_admin_session)
+                .doc(
+                    ApiDoc::new("All API tokens, without their secrets.", Auth::SuperAdmin)
+                        .response::<Vec<ApiToken>>(),
+                ),
+        )
+        .route(
+            Route::delete("/api/v1/admin/api-tokens/{api_token_id}", api_token::revoke)
+                .with(require_admin_session)
+                .doc(ApiDoc::new("Revoke an API token.", Auth::SuperAdmin)),
+        )
+        .route(
+            Route::get("/healthz", health::get_liveness)
+                .server_wide()
+                .doc(ApiDoc::new(
+                    "Answers as long as the server process is alive.",
+                    Auth::None,
+                )),
+        )
+        .route(
+            Route::get("/readyz", health::get_readiness).server_wide().doc(
+                ApiDoc::new(
+                    "Whether the state authority of every event answers within a second. Answers with 503 otherwise.",
+                    Auth::None,
+                )
+                .response::<Readiness>(),
+            ),
+        )
+        .route(
+            Route::get("/metrics", metrics::get)
+                .server_wide()
+                .doc(ApiDoc::new(
+                    "Request, vote, connection and queue statistics of the whole server in the Prometheus text format.",
+                    Auth::None,
+                )),
+        )
+        .openapi_route("/api/v1/openapi.json")
+}
+
+async fn handle_request(
+    request: Request<hyper::body::Incoming>,
+    router: &Router,
+    events: Events,
+) -> ResponseResult {
+    let request_id = RequestId::of(&request);
+    let span = info_span!("request", request_id = request_id.as_str());
+
+    let mut result = request_id
+        .clone()
+        .scope(route_request(request, router, events))
+        .instrument(span)
+        .await;
+
+    if let Ok(response) = result.as_mut() {
+        let headers = response.headers_mut();
+        headers.insert(REQUEST_ID_HEADER, request_id.to_header_value());
+        security_headers::add_security_headers(headers);
+    }
+
+    result
+}
+
+async fn route_request(
+    request: Request<hyper::body::Incoming>,
+    router: &Router,
+    events: Events,
+) -> ResponseResult {
+    info!(
+        "Incoming request - {} {}",
+        request.method(),
+        request.uri().path()
+    );
+
+    let received_at = Instant::now();
+    let method = request.method().clone();
+    let route = router.matched_pattern(&method, request.uri().path());
+
+    let event = if router.is_server_wide(&method, request.uri().path()) {
+        events.default_event()
+    } else {
+        let host = request
+            .headers()
+            .get(HOST)
+            .and_then(|host| host.to_str().ok());
+        events.resolve(host)
+    };
+
+    let result = match event {
+        Ok(event) => router.handle(request, event, events).await,
+        Err(event_error) => ApiError::from(event_error)
+            .localized(Locale::negotiate(request.headers()))
+            .to_response(),
+    };
+
+    let status = result
+        .as_ref()
+        .map_or(StatusCode::INTERNAL_SERVER_ERROR, |response| {
+            response.status()
+        });
+    let duration = received_at.elapsed();
+    info!(
+        "Sending response: {status} after {:.1} ms",
+        duration.as_secs_f64() * 1000.0
+    );
+    METRICS.record_request(&method, route, status, duration);
+
+    result
+}
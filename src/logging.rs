@@ -0,0 +1,135 @@
+use std::{env, future::Future};
+
+use hyper::{Request, header::HeaderValue};
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{
+    EnvFilter, fmt::time::OffsetTime, layer::SubscriberExt, util::SubscriberInitExt,
+};
+
+/// Header that carries the request ID. A valid ID sent by a client or proxy is kept, so that
+/// its logs can be matched with ours.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+const MAX_REQUEST_ID_LENGTH: usize = 64;
+
+/// Rotated log files older than this many days are deleted.
+const MAX_LOG_FILES: usize = 14;
+
+tokio::task_local! {
+    static REQUEST_ID: RequestId;
+}
+
+/// Identifies a request in the logs of the request handling and of the state authority.
+#[derive(Debug, Clone)]
+pub struct RequestId(String);
+
+impl RequestId {
+    pub fn of<B>(request: &Request<B>) -> Self {
+        request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|id| is_valid_request_id(id))
+            .map(|id| Self(id.to_string()))
+            .unwrap_or_else(|| Self(format!("{:032x}", rand::random::<u128>())))
+    }
+
+    /// The ID of the request whose handling is running on this task.
+    pub fn current() -> Option<Self> {
+        REQUEST_ID.try_with(Clone::clone).ok()
+    }
+
+    /// Makes this the current ID while `future` runs.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        REQUEST_ID.scope(self, future).await
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn to_header_value(&self) -> HeaderValue {
+        // Only visible ASCII ends up in a request ID, see `is_valid_request_id`.
+        HeaderValue::from_str(&self.0).unwrap_or_else(|_| HeaderValue::from_static("invalid"))
+    }
+}
+
+fn is_valid_request_id(id: &str) -> bool {
+    (1..=MAX_REQUEST_ID_LENGTH).contains(&id.len())
+        && id
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'-' | b'_' | b'.'))
+}
+
+/// Sets up logging from the environment:
+/// - `RUST_LOG` filters the logs, e.g. `info` or `coco=debug,hyper=warn`. Defaults to `info`.
+/// - `COCO_LOG_FORMAT=json` writes one JSON object per line to stdout instead of plain text.
+/// - `COCO_LOG_DIR` additionally writes JSON logs to daily rotated files in that directory.
+///
+/// The returned guard flushes the log files when dropped, so it has to be kept until the end.
+pub fn init() -> Option<WorkerGuard> {
+    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|e| {
+        if env::var_os(EnvFilter::DEFAULT_ENV).is_some() {
+            eprintln!("WARNING: Invalid RUST_LOG, logging at the info level. Error message: {e}");
+        }
+        EnvFilter::new("info")
+    });
+
+    let timer = OffsetTime::local_rfc_3339().unwrap_or_else(|e| {
+        eprintln!(
+            "WARNING: Unable to get local time zone information for logs. Error message: {e}"
+        );
+        OffsetTime::new(
+            time::UtcOffset::UTC,
+            time::format_description::well_known::Rfc3339,
+        )
+    });
+
+    let is_json = env::var("COCO_LOG_FORMAT").is_ok_and(|format| format == "json");
+    let stdout_json_layer = is_json.then(|| {
+        tracing_subscriber::fmt::layer()
+            .json()
+            .with_timer(timer.clone())
+    });
+    let stdout_text_layer =
+        (!is_json).then(|| tracing_subscriber::fmt::layer().with_timer(timer.clone()));
+
+    let (file_layer, guard) = match env::var("COCO_LOG_DIR") {
+        Ok(log_dir) => match RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("coco")
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(&log_dir)
+        {
+            Ok(file_appender) => {
+                let (writer, guard) = tracing_appender::non_blocking(file_appender);
+                let file_layer = tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_timer(timer)
+                    .with_writer(writer);
+                (Some(file_layer), Some(guard))
+            }
+            Err(e) => {
+                eprintln!("WARNING: Unable to write logs to {log_dir}. Error message: {e}");
+                (None, None)
+            }
+        },
+        Err(_) => (None, None),
+    };
+
+    if let Err(e) = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(stdout_json_layer)
+        .with(stdout_text_layer)
+        .with(file_layer)
+        .try_init()
+    {
+        eprintln!("Unable to set global default subscriber: {e}");
+    }
+
+    guard
+}
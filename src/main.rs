@@ -1,137 +1,67 @@
-mod admin;
-mod common;
-mod election;
-mod error;
-mod frontend;
-mod participant;
-mod state;
+use std::{env, error::Error, net::SocketAddr};
 
-use http_body_util::Full;
-use hyper::body::Bytes;
-use hyper::server::conn::http1;
-use hyper::service::service_fn;
-use hyper::{Method, Request, Response, StatusCode};
-use hyper_util::rt::TokioIo;
+use coco::event::{AdminCreateEventBody, DEFAULT_EVENT_SLUG, Events};
+use coco::logging;
+use rand::{Rng, distr::Alphanumeric};
 use tokio::net::TcpListener;
-use tokio::sync::mpsc;
-use tokio::task;
-use tracing::{error, info, warn};
-use tracing_subscriber::fmt::time;
+use tracing::{info, warn};
 
-use std::{error::Error, net::SocketAddr};
+/// Password participants of the default event log in with.
+const PARTICIPANT_PASSWORD_ENV_VAR: &str = "COCO_PARTICIPANT_PASSWORD";
+/// Password of the default event's initial super admin account `admin`.
+const ADMIN_PASSWORD_ENV_VAR: &str = "COCO_ADMIN_PASSWORD";
+const GENERATED_PASSWORD_LENGTH: usize = 16;
 
-use state::Message;
-
-use crate::common::ResponseResult;
-use crate::frontend::FRONTEND_FILES;
-
-fn main() -> Result<(), Box<dyn Error>> {
-    let subscriber_builder = tracing_subscriber::fmt();
-
-    let time_offset_initialization_result = match time::OffsetTime::local_rfc_3339() {
-        Ok(timer) => subscriber_builder.with_timer(timer).try_init(),
-        Err(e) => {
-            eprintln!(
-                "WARNING: Unable to get local time zone information for logs. Error message: {e}"
-            );
-            subscriber_builder.try_init()
-        }
-    };
-
-    if let Err(e) = time_offset_initialization_result {
-        eprintln!("Unable to set global default subscriber: {e}");
-    }
+fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let _log_file_guard = logging::init();
 
     run_server()
 }
 
 #[tokio::main]
-async fn run_server() -> Result<(), Box<dyn Error>> {
+async fn run_server() -> Result<(), Box<dyn Error + Send + Sync>> {
     tracing::info!("Server runtime started");
 
-    let (to_central_state_authority_sender, central_state_authority_receiver) = mpsc::channel(512);
-
-    tokio::spawn(state::central_state_authority(
-        central_state_authority_receiver,
-    ));
+    let base_domain = env::var("COCO_BASE_DOMAIN").unwrap_or_else(|_| String::from("localhost"));
+
+    let (events, state_authority_panic_receiver) = Events::new(
+        &base_domain,
+        AdminCreateEventBody {
+            slug: String::from(DEFAULT_EVENT_SLUG),
+            name: String::from("coco"),
+            participant_password: password_from_env(
+                PARTICIPANT_PASSWORD_ENV_VAR,
+                "participant password",
+            ),
+            admin_password: password_from_env(
+                ADMIN_PASSWORD_ENV_VAR,
+                "password of the super admin account `admin`",
+            ),
+        },
+        true,
+    );
 
     let socket_address: SocketAddr = ([127, 0, 0, 1], 3030).into();
     let listener = TcpListener::bind(socket_address).await?;
 
     info!("Listening on http://{}", socket_address);
 
-    loop {
-        let (tcp_stream, address) = listener.accept().await?;
-        info!("Accepted connection from: {}", address);
-
-        let connection_io_stream = TokioIo::new(tcp_stream);
-        let to_central_state_authority_sender = to_central_state_authority_sender.clone();
-
-        task::spawn(async move {
-            let service = service_fn(|request| {
-                handle_request(request, to_central_state_authority_sender.clone())
-            });
-
-            if let Err(err) = http1::Builder::new()
-                .serve_connection(connection_io_stream, service)
-                .await
-            {
-                error!("Error serving connection: {:?}", err);
-            }
-        });
-    }
+    coco::serve(listener, events, state_authority_panic_receiver).await
 }
 
-async fn handle_request(
-    request: Request<hyper::body::Incoming>,
-    to_central_state_authority_sender: mpsc::Sender<Message>,
-) -> ResponseResult {
-    info!(
-        "Incoming request - {} {}",
-        request.method(),
-        request.uri().path()
-    );
-
-    let result = match (request.method(), request.uri().path()) {
-        (&Method::GET, path) if FRONTEND_FILES.contains_key(path) => {
-            let file_data = FRONTEND_FILES[path];
-            Response::builder()
-                .header("Content-Type", file_data.kind.content_type())
-                .body(Full::new(Bytes::from(file_data.content)))
-        }
-        (&Method::POST, "/participants/add") => {
-            participant::add(request, to_central_state_authority_sender).await
-        }
-        (&Method::GET, "/participants/votes") => {
-            participant::get_votes(request, to_central_state_authority_sender).await
-        }
-        (&Method::POST, "/elections/vote") => {
-            election::vote(request, to_central_state_authority_sender).await
-        }
-        (&Method::GET, "/elections") => {
-            election::get_all(request, to_central_state_authority_sender).await
-        }
-        (&Method::POST, "/admin/start-session") => {
-            admin::start_session(request, to_central_state_authority_sender).await
-        }
-        (&Method::POST, "/admin/create-election") => {
-            admin::create_election(request, to_central_state_authority_sender).await
-        }
+/// Reads a bootstrap password of the default event. Without one, a random password is
+/// generated and logged once, since all state, and with it the password, is lost on restart.
+fn password_from_env(env_var: &str, description: &str) -> String {
+    match env::var(env_var) {
+        Ok(password) if !password.is_empty() => password,
         _ => {
-            warn!("Unable to handle request");
-            Response::builder()
-                .status(StatusCode::NOT_FOUND)
-                .body(Full::new(Bytes::from("CoCo does not know this page.")))
+            let password: String = rand::rng()
+                .sample_iter(Alphanumeric)
+                .take(GENERATED_PASSWORD_LENGTH)
+                .map(char::from)
+                .collect();
+            warn!("{env_var} is not set, the generated {description} is {password}");
+            password
         }
-    };
-
-    if let Ok(response) = result.as_ref() {
-        info!(
-            "Sending response: {:?} {:?}",
-            response.status(),
-            response.headers()
-        );
     }
-
-    result
 }
@@ -0,0 +1,269 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use http_body_util::Full;
+use hyper::{Method, Response, StatusCode, body::Bytes, header::CONTENT_TYPE};
+
+use crate::{common::HandlerResult, error::ElectionsVoteError, router::RouteRequest};
+
+/// Counters of the whole server, shared by all events.
+pub static METRICS: Metrics = Metrics::new();
+
+/// Upper bounds of the request duration histogram buckets.
+const DURATION_BUCKETS_SECONDS: [f64; 11] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+/// Requests that match no route are counted under this route label.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+pub struct Metrics {
+    requests_by_route: Mutex<BTreeMap<(&'static str, &'static str), RouteStats>>,
+    votes_accepted: AtomicU64,
+    votes_rejected_by_reason: Mutex<BTreeMap<&'static str, u64>>,
+    connections_opened: AtomicU64,
+    connections_closed: AtomicU64,
+}
+
+#[derive(Default)]
+struct RouteStats {
+    responses_by_status: BTreeMap<u16, u64>,
+    /// Not cumulative, each request is only counted in the first bucket it fits in.
+    duration_bucket_counts: [u64; DURATION_BUCKETS_SECONDS.len()],
+    duration_sum_seconds: f64,
+    num_requests: u64,
+}
+
+/// Counts the connection as closed when dropped.
+pub struct ConnectionGuard(());
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        METRICS.connections_closed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// The depth of the message queue of one event's state authority.
+pub struct QueueDepth {
+    pub event_slug: String,
+    pub num_messages: usize,
+    pub capacity: usize,
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            requests_by_route: Mutex::new(BTreeMap::new()),
+            votes_accepted: AtomicU64::new(0),
+            votes_rejected_by_reason: Mutex::new(BTreeMap::new()),
+            connections_opened: AtomicU64::new(0),
+            connections_closed: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record_request(
+        &self,
+        method: &Method,
+        route: Option<&'static str>,
+        status: StatusCode,
+        duration: Duration,
+    ) {
+        let key = (method_label(method), route.unwrap_or(UNMATCHED_ROUTE));
+        let duration_seconds = duration.as_secs_f64();
+
+        let mut requests_by_route = self
+            .requests_by_route
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let route_stats = requests_by_route.entry(key).or_default();
+
+        *route_stats
+            .responses_by_status
+            .entry(status.as_u16())
+            .or_default() += 1;
+        if let Some(bucket_index) = DURATION_BUCKETS_SECONDS
+            .iter()
+            .position(|upper_bound| duration_seconds <= *upper_bound)
+        {
+            route_stats.duration_bucket_counts[bucket_index] += 1;
+        }
+        route_stats.duration_sum_seconds += duration_seconds;
+        route_stats.num_requests += 1;
+    }
+
+    pub fn record_vote(&self, result: &Result<(), ElectionsVoteError>) {
+        match result {
+            Ok(()) => {
+                self.votes_accepted.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(elections_vote_error) => {
+                let mut votes_rejected_by_reason = self
+                    .votes_rejected_by_reason
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner());
+                *votes_rejected_by_reason
+                    .entry(elections_vote_error.variant_name())
+                    .or_default() += 1;
+            }
+        }
+    }
+
+    pub fn connection_opened(&self) -> ConnectionGuard {
+        self.connections_opened.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard(())
+    }
+
+    /// Renders all metrics in the Prometheus text exposition format.
+    fn render(&self, queue_depths: &[QueueDepth]) -> String {
+        let mut text = String::new();
+
+        text.push_str(
+            "# HELP coco_http_requests_total HTTP responses by method, route and status.\n\
+             # TYPE coco_http_requests_total counter\n",
+        );
+        let requests_by_route = self
+            .requests_by_route
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        for ((method, route), route_stats) in requests_by_route.iter() {
+            for (status, num_responses) in &route_stats.responses_by_status {
+                let _ = writeln!(
+                    text,
+                    "coco_http_requests_total{{method=\"{method}\",route=\"{}\",status=\"{status}\"}} {num_responses}",
+                    escape_label_value(route)
+                );
+            }
+        }
+
+        text.push_str(
+            "# HELP coco_http_request_duration_seconds Time from receiving a request to having its response ready.\n\
+             # TYPE coco_http_request_duration_seconds histogram\n",
+        );
+        for ((method, route), route_stats) in requests_by_route.iter() {
+            let labels = format!(
+                "method=\"{method}\",route=\"{}\"",
+                escape_label_value(route)
+            );
+
+            let mut cumulative_count = 0;
+            for (upper_bound, bucket_count) in DURATION_BUCKETS_SECONDS
+                .iter()
+                .zip(route_stats.duration_bucket_counts)
+            {
+                cumulative_count += bucket_count;
+                let _ = writeln!(
+                    text,
+                    "coco_http_request_duration_seconds_bucket{{{labels},le=\"{upper_bound}\"}} {cumulative_count}"
+                );
+            }
+            let _ = write!(
+                text,
+                "coco_http_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {num_requests}\n\
+                 coco_http_request_duration_seconds_sum{{{labels}}} {sum}\n\
+                 coco_http_request_duration_seconds_count{{{labels}}} {num_requests}\n",
+                num_requests = route_stats.num_requests,
+                sum = route_stats.duration_sum_seconds,
+            );
+        }
+        drop(requests_by_route);
+
+        let _ = write!(
+            text,
+            "# HELP coco_votes_accepted_total Votes that were counted.\n\
+             # TYPE coco_votes_accepted_total counter\n\
+             coco_votes_accepted_total {}\n\
+             # HELP coco_votes_rejected_total Votes that were refused, by reason.\n\
+             # TYPE coco_votes_rejected_total counter\n",
+            self.votes_accepted.load(Ordering::Relaxed)
+        );
+        let votes_rejected_by_reason = self
+            .votes_rejected_by_reason
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        for (reason, num_votes) in votes_rejected_by_reason.iter() {
+            let _ = writeln!(
+                text,
+                "coco_votes_rejected_total{{reason=\"{reason}\"}} {num_votes}"
+            );
+        }
+        drop(votes_rejected_by_reason);
+
+        let connections_opened = self.connections_opened.load(Ordering::Relaxed);
+        let connections_closed = self.connections_closed.load(Ordering::Relaxed);
+        let _ = write!(
+            text,
+            "# HELP coco_connections_total Accepted TCP connections.\n\
+             # TYPE coco_connections_total counter\n\
+             coco_connections_total {connections_opened}\n\
+             # HELP coco_active_connections Currently open TCP connections.\n\
+             # TYPE coco_active_connections gauge\n\
+             coco_active_connections {}\n",
+            connections_opened.saturating_sub(connections_closed)
+        );
+
+        text.push_str(
+            "# HELP coco_state_authority_queue_depth Messages waiting for the state authority of an event.\n\
+             # TYPE coco_state_authority_queue_depth gauge\n",
+        );
+        for queue_depth in queue_depths {
+            let _ = writeln!(
+                text,
+                "coco_state_authority_queue_depth{{event=\"{}\"}} {}",
+                escape_label_value(&queue_depth.event_slug),
+                queue_depth.num_messages
+            );
+        }
+
+        text.push_str(
+            "# HELP coco_state_authority_queue_capacity Maximum number of waiting messages before senders have to wait.\n\
+             # TYPE coco_state_authority_queue_capacity gauge\n",
+        );
+        for queue_depth in queue_depths {
+            let _ = writeln!(
+                text,
+                "coco_state_authority_queue_capacity{{event=\"{}\"}} {}",
+                escape_label_value(&queue_depth.event_slug),
+                queue_depth.capacity
+            );
+        }
+
+        text
+    }
+}
+
+pub async fn get(route_request: RouteRequest) -> HandlerResult {
+    let text = METRICS.render(&route_request.events.queue_depths());
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")
+        .body(Full::new(Bytes::from(text)))?)
+}
+
+/// Arbitrary methods sent by clients must not create new series.
+fn method_label(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::DELETE => "DELETE",
+        Method::HEAD => "HEAD",
+        Method::OPTIONS => "OPTIONS",
+        Method::PATCH => "PATCH",
+        _ => "OTHER",
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,84 @@
+use crate::election::{Election, ElectionId, Outcome};
+
+/// The ballot item that approves a motion or an amendment. Admins list it first, e.g. "Yes".
+pub const APPROVING_BALLOT_ITEM_ID: usize = 0;
+
+/// The text an election decides on, if it is a motion or an amendment to one.
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum Motion {
+    /// Voted on after all of its amendments have been closed.
+    Main {
+        text: String,
+        /// `text` with all adopted amendments applied, set once the voting is opened.
+        consolidated_text: Option<String>,
+        /// Adopted amendments whose original text was no longer part of the motion, e.g.
+        /// because an earlier amendment already replaced it.
+        unapplied_amendment_ids: Vec<ElectionId>,
+    },
+    /// If adopted, replaces `original_text` in the parent motion by `replacement_text`.
+    Amendment {
+        parent_election_id: ElectionId,
+        original_text: String,
+        replacement_text: String,
+    },
+}
+
+impl Motion {
+    pub fn parent_election_id(&self) -> Option<ElectionId> {
+        match self {
+            Motion::Main { .. } => None,
+            Motion::Amendment {
+                parent_election_id, ..
+            } => Some(*parent_election_id),
+        }
+    }
+}
+
+/// Whether a closed election approved its motion or amendment.
+pub fn is_adopted(election: &Election) -> bool {
+    election.is_closed()
+        && matches!(
+            election.outcome(),
+            Outcome::Decided { ballot_item_id } if ballot_item_id == APPROVING_BALLOT_ITEM_ID
+        )
+}
+
+/// Applies the adopted amendments in the order they were created. Returns the consolidated
+/// text and the ids of the adopted amendments that could not be applied.
+pub fn consolidate<'a>(
+    text: &str,
+    amendments: impl IntoIterator<Item = &'a Election>,
+) -> (String, Vec<ElectionId>) {
+    let mut amendments: Vec<_> = amendments
+        .into_iter()
+        .filter(|election| is_adopted(election))
+        .collect();
+    amendments.sort_by_key(|election| election.id);
+
+    let mut consolidated_text = String::from(text);
+    let mut unapplied_amendment_ids = Vec::new();
+
+    for amendment in amendments {
+        let Some(Motion::Amendment {
+            original_text,
+            replacement_text,
+            ..
+        }) = &amendment.motion
+        else {
+            continue;
+        };
+
+        match consolidated_text.find(original_text.as_str()) {
+            Some(start) => consolidated_text
+                .replace_range(start..start + original_text.len(), replacement_text),
+            None => unapplied_amendment_ids.push(amendment.id),
+        }
+    }
+
+    (consolidated_text, unapplied_amendment_ids)
+}
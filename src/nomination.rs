@@ -0,0 +1,192 @@
+use std::collections::BTreeSet;
+
+use hyper::StatusCode;
+use time::OffsetDateTime;
+
+use crate::{
+    common::{HandlerResult, json_response, ok_response},
+    election::ElectionId,
+    error::NominationError,
+    participant::{ParticipantCredentials, ParticipantId},
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
+};
+
+pub type NominationId = usize;
+
+/// Configures the nomination phase of an election. Voting only starts once an admin opens it.
+#[derive(Debug, Clone, Copy, Default, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NominationSettings {
+    /// How many other participants have to second a nomination before it can be on the ballot.
+    #[serde(default)]
+    pub required_seconders: usize,
+}
+
+/// A candidate proposed by a participant while the election is nominating.
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Nomination {
+    pub id: NominationId,
+    pub election_id: ElectionId,
+    /// The name shown on the ballot.
+    pub candidate_name: String,
+    pub candidate_participant_id: ParticipantId,
+    pub nominated_by: ParticipantId,
+    pub seconded_by: BTreeSet<ParticipantId>,
+    pub candidate_response: CandidateResponse,
+    pub review: NominationReview,
+    #[serde(with = "time::serde::rfc3339")]
+    #[schemars(with = "String")]
+    pub created_at: OffsetDateTime,
+}
+
+impl Nomination {
+    /// Whether the nomination becomes a ballot item when voting opens.
+    pub fn is_on_ballot(&self, required_seconders: usize) -> bool {
+        matches!(self.candidate_response, CandidateResponse::Accepted)
+            && matches!(self.review, NominationReview::Approved)
+            && self.seconded_by.len() >= required_seconders
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum CandidateResponse {
+    Pending,
+    Accepted,
+    Declined,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum NominationReview {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NominateBody {
+    pub candidate_name: String,
+    /// Nominating oneself counts as accepting the nomination.
+    pub candidate_participant_id: ParticipantId,
+}
+
+pub async fn get_all(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(election_id) = route_request.path_params.parse("election_id") else {
+        return Err(NominationError::MissingElection.into());
+    };
+
+    let nominations = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::NominationsGet {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        })
+        .await??;
+
+    json_response(StatusCode::OK, &nominations)
+}
+
+pub async fn nominate(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_participant_credentials =
+        route_request.take_extension::<ParticipantCredentials>()?;
+    let body = route_request.take_extension::<NominateBody>()?;
+
+    let Some(election_id) = route_request.path_params.parse("election_id") else {
+        return Err(NominationError::MissingElection.into());
+    };
+
+    let nomination = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::NominationsAdd {
+            answer_sender,
+            requesting_participant_credentials,
+            election_id,
+            nominate_body: body,
+        })
+        .await??;
+
+    json_response(StatusCode::CREATED, &nomination)
+}
+
+pub async fn second(route_request: RouteRequest) -> HandlerResult {
+    act_on_nomination(route_request, NominationAction::Second).await
+}
+
+pub async fn accept(route_request: RouteRequest) -> HandlerResult {
+    act_on_nomination(route_request, NominationAction::Accept).await
+}
+
+pub async fn decline(route_request: RouteRequest) -> HandlerResult {
+    act_on_nomination(route_request, NominationAction::Decline).await
+}
+
+pub async fn approve(route_request: RouteRequest) -> HandlerResult {
+    review(route_request, NominationReview::Approved).await
+}
+
+pub async fn reject(route_request: RouteRequest) -> HandlerResult {
+    review(route_request, NominationReview::Rejected).await
+}
+
+/// What a participant can do with someone's nomination.
+#[derive(Debug, Clone, Copy)]
+pub enum NominationAction {
+    Second,
+    Accept,
+    Decline,
+}
+
+async fn act_on_nomination(
+    mut route_request: RouteRequest,
+    action: NominationAction,
+) -> HandlerResult {
+    let requesting_participant_credentials =
+        route_request.take_extension::<ParticipantCredentials>()?;
+
+    let Some(nomination_id) = route_request.path_params.parse("nomination_id") else {
+        return Err(NominationError::MissingNomination.into());
+    };
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::NominationsAct {
+            answer_sender,
+            requesting_participant_credentials,
+            nomination_id,
+            action,
+        })
+        .await??;
+
+    ok_response()
+}
+
+async fn review(mut route_request: RouteRequest, review: NominationReview) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(nomination_id) = route_request.path_params.parse("nomination_id") else {
+        return Err(NominationError::MissingNomination.into());
+    };
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminReviewNomination {
+            answer_sender,
+            requesting_credentials,
+            nomination_id,
+            review,
+        })
+        .await??;
+
+    ok_response()
+}
@@ -0,0 +1,199 @@
+use hyper::{Method, StatusCode};
+use schemars::{JsonSchema, SchemaGenerator, generate::SchemaSettings};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    admin::{ADMIN_SESSION_ID_COOKIE_KEY, ADMIN_TOKEN_COOKIE_KEY},
+    api_token::ApiTokenScope,
+    error::ApiError,
+    participant::{PARTICIPANT_ID_COOKIE_KEY, TOKEN_COOKIE_KEY},
+};
+
+pub const API_VERSION: &str = "v1";
+
+type SchemaFn = fn(&mut SchemaGenerator) -> Value;
+
+#[derive(Clone, Copy)]
+pub enum Auth {
+    None,
+    Participant,
+    AdminSession,
+    /// An admin session of an account with the `superAdmin` role.
+    SuperAdmin,
+    /// An admin session or an API token with the given scope.
+    Admin(ApiTokenScope),
+    /// Any participant, an admin session or an API token with the given scope.
+    Any(ApiTokenScope),
+}
+
+impl Auth {
+    fn security_requirements(self) -> Value {
+        let participant = json!({ "participantId": [], "participantToken": [] });
+        let admin = json!({ "adminSessionId": [], "adminToken": [] });
+        let api_token = json!({ "apiToken": [] });
+
+        match self {
+            Auth::None => json!([]),
+            Auth::Participant => json!([participant]),
+            Auth::AdminSession | Auth::SuperAdmin => json!([admin]),
+            Auth::Admin(_) => json!([admin, api_token]),
+            Auth::Any(_) => json!([participant, admin, api_token]),
+        }
+    }
+
+    fn required_scope(self) -> Option<ApiTokenScope> {
+        match self {
+            Auth::None | Auth::Participant | Auth::AdminSession | Auth::SuperAdmin => None,
+            Auth::Admin(scope) | Auth::Any(scope) => Some(scope),
+        }
+    }
+}
+
+/// OpenAPI description of a single route. Request and response schemas are derived from the
+/// Rust types, so the document cannot drift from what the handlers actually (de)serialize.
+pub struct ApiDoc {
+    summary: &'static str,
+    auth: Auth,
+    request_body: Option<SchemaFn>,
+    success_status: StatusCode,
+    success_body: Option<SchemaFn>,
+    success_content_type: &'static str,
+}
+
+impl ApiDoc {
+    pub fn new(summary: &'static str, auth: Auth) -> Self {
+        Self {
+            summary,
+            auth,
+            request_body: None,
+            success_status: StatusCode::OK,
+            success_body: None,
+            success_content_type: "application/json",
+        }
+    }
+
+    pub fn request<T: JsonSchema>(mut self) -> Self {
+        self.request_body = Some(schema_of::<T>);
+        self
+    }
+
+    pub fn response<T: JsonSchema>(mut self) -> Self {
+        self.success_body = Some(schema_of::<T>);
+        self
+    }
+
+    /// For responses that are not JSON, e.g. `text/csv`.
+    pub fn response_text(mut self, content_type: &'static str) -> Self {
+        self.success_body = Some(schema_of::<String>);
+        self.success_content_type = content_type;
+        self
+    }
+
+    pub fn status(mut self, success_status: StatusCode) -> Self {
+        self.success_status = success_status;
+        self
+    }
+
+    fn to_operation(&self, path_params: &[&str], generator: &mut SchemaGenerator) -> Value {
+        let mut success_response = json!({ "description": "Success" });
+        if let Some(schema_fn) = self.success_body {
+            success_response["content"] =
+                json!({ self.success_content_type: { "schema": schema_fn(generator) } });
+        }
+
+        let mut operation = json!({
+            "summary": self.summary,
+            "security": self.auth.security_requirements(),
+            "parameters": path_params
+                .iter()
+                .map(|name| json!({
+                    "name": name,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "integer", "minimum": 0 },
+                }))
+                .collect::<Vec<_>>(),
+            "responses": {
+                self.success_status.as_str(): success_response,
+                "default": {
+                    "description": "Error",
+                    "content": {
+                        "application/json": { "schema": schema_of::<ApiError>(generator) },
+                    },
+                },
+            },
+        });
+
+        if let Some(scope) = self.auth.required_scope() {
+            operation["description"] = json!(format!(
+                "Admin roles and API tokens need the `{}` scope.",
+                json!(scope).as_str().unwrap_or_default()
+            ));
+        } else if let Auth::SuperAdmin = self.auth {
+            operation["description"] = json!("Only for admins with the `superAdmin` role.");
+        }
+
+        if let Some(schema_fn) = self.request_body {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": schema_fn(generator) } },
+            });
+        }
+
+        operation
+    }
+}
+
+pub struct DocumentedRoute<'a> {
+    pub method: &'a Method,
+    pub path: String,
+    pub path_params: Vec<&'a str>,
+    pub api_doc: &'a ApiDoc,
+}
+
+pub fn build_document<'a>(routes: impl Iterator<Item = DocumentedRoute<'a>>) -> Value {
+    let mut generator = SchemaSettings::openapi3().into_generator();
+    let mut paths = Map::new();
+
+    for route in routes {
+        let operation = route
+            .api_doc
+            .to_operation(&route.path_params, &mut generator);
+
+        let path_item = paths
+            .entry(route.path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        path_item[route.method.as_str().to_lowercase()] = operation;
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "coco - Convenient Conventions",
+            "version": API_VERSION,
+        },
+        "paths": paths,
+        "components": {
+            "schemas": generator.take_definitions(true),
+            "securitySchemes": {
+                "participantId": cookie_scheme(PARTICIPANT_ID_COOKIE_KEY),
+                "participantToken": cookie_scheme(TOKEN_COOKIE_KEY),
+                "adminSessionId": cookie_scheme(ADMIN_SESSION_ID_COOKIE_KEY),
+                "adminToken": cookie_scheme(ADMIN_TOKEN_COOKIE_KEY),
+                "apiToken": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "An API token issued by an admin, formatted as `<id>.<token>`.",
+                },
+            },
+        },
+    })
+}
+
+fn schema_of<T: JsonSchema>(generator: &mut SchemaGenerator) -> Value {
+    generator.subschema_for::<T>().to_value()
+}
+
+fn cookie_scheme(cookie_name: &str) -> Value {
+    json!({ "type": "apiKey", "in": "cookie", "name": cookie_name })
+}
@@ -1,23 +1,29 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+};
 
-use http_body_util::{BodyExt, Full};
-use hyper::{Request, Response, StatusCode, body::Bytes, header::SET_COOKIE};
-use tokio::sync::{mpsc, oneshot};
-use tracing::{error, info, warn};
+use http_body_util::Full;
+use hyper::{Response, StatusCode, body::Bytes, header::SET_COOKIE};
+use time::OffsetDateTime;
 
 use crate::{
-    common::{
-        ResponseResult, bad_request_response, extract_requesting_participant,
-        internal_error_response, unauthorized_response,
-    },
+    attendance::AttendanceChange,
+    common::{HandlerResult, ok_response},
     election::{BallotItemId, ElectionId},
-    state::Message,
+    error::{ApiError, ParticipantsManageError},
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
 };
 
 pub const PARTICIPANT_ID_COOKIE_KEY: &str = "coco_participant_id";
 pub const TOKEN_COOKIE_KEY: &str = "coco_token";
 
 pub type ParticipantId = usize;
+/// Name of a group of participants, e.g. `delegates-north`, used to restrict who may vote.
+pub type ParticipantGroup = String;
+
+const MAX_GROUP_NAME_LENGTH: usize = 64;
 
 #[derive(Copy, Clone)]
 pub struct ValidParticipantId(pub usize);
@@ -26,6 +32,42 @@ pub struct ValidParticipantId(pub usize);
 pub struct Participant {
     pub credentials: ParticipantCredentials,
     pub voted_ballot_item_ids_by_election_id: HashMap<ElectionId, BallotItemId>,
+    /// `None` while the participant is not checked in at the registration desk.
+    pub checked_in_since: Option<OffsetDateTime>,
+    pub attendance_history: Vec<AttendanceChange>,
+    pub groups: BTreeSet<ParticipantGroup>,
+}
+
+/// What admins get to see about a participant. Never contains the selected ballot items.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipantSummary {
+    pub id: ParticipantId,
+    pub voted_election_ids: Vec<ElectionId>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[schemars(with = "Option<String>")]
+    pub checked_in_since: Option<OffsetDateTime>,
+    pub attendance_history: Vec<AttendanceChange>,
+    pub groups: BTreeSet<ParticipantGroup>,
+}
+
+impl From<&Participant> for ParticipantSummary {
+    fn from(participant: &Participant) -> Self {
+        let mut voted_election_ids: Vec<_> = participant
+            .voted_ballot_item_ids_by_election_id
+            .keys()
+            .copied()
+            .collect();
+        voted_election_ids.sort();
+
+        Self {
+            id: participant.credentials.id,
+            voted_election_ids,
+            checked_in_since: participant.checked_in_since,
+            attendance_history: participant.attendance_history.clone(),
+            groups: participant.groups.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -34,57 +76,42 @@ pub struct ParticipantCredentials {
     pub token: String,
 }
 
-#[derive(Debug, serde::Deserialize)]
-struct AddParticipantBody {
-    password: String,
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+pub struct AdminSetParticipantGroupsBody {
+    /// Replaces all groups of the participant.
+    pub groups: BTreeSet<ParticipantGroup>,
 }
 
-pub async fn add(
-    request: Request<hyper::body::Incoming>,
-    to_central_state_authority_sender: mpsc::Sender<Message>,
-) -> ResponseResult {
-    let body_bytes = match request.into_body().collect().await {
-        Ok(collected) => collected.to_bytes(),
-        Err(e) => {
-            error!("{e:?}");
-            return internal_error_response();
-        }
-    };
-
-    let body: AddParticipantBody = match serde_json::from_slice(&body_bytes) {
-        Ok(body) => body,
-        Err(e) => {
-            warn!("Bad request: {e:?}");
-            return bad_request_response();
-        }
-    };
+pub fn is_valid_group_name(group: &str) -> bool {
+    !group.is_empty() && group.len() <= MAX_GROUP_NAME_LENGTH && group.trim() == group
+}
 
-    info!("{body:?}");
+#[derive(Clone, serde::Deserialize, schemars::JsonSchema)]
+pub struct AddParticipantBody {
+    /// The participant password announced at the event.
+    password: String,
+}
 
-    const PASSWORD: &str = "abc";
-    if body.password != PASSWORD {
-        return unauthorized_response();
+impl fmt::Debug for AddParticipantBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AddParticipantBody").finish_non_exhaustive()
     }
+}
 
-    let (answer_sender, answer_receiver) = oneshot::channel();
+pub async fn add(mut route_request: RouteRequest) -> HandlerResult {
+    let body = route_request.take_extension::<AddParticipantBody>()?;
 
-    if let Err(e) = to_central_state_authority_sender
-        .send(Message::ParticipantsAdd { answer_sender })
-        .await
-    {
-        error!("{e:?}");
-        return internal_error_response();
+    if !route_request.event.is_participant_password(&body.password) {
+        return Err(ApiError::wrong_password());
     }
 
-    let new_participant_credentials = match answer_receiver.await {
-        Ok(new_participant_credentials) => new_participant_credentials,
-        Err(e) => {
-            error!("{e:?}");
-            return internal_error_response();
-        }
-    };
+    let new_participant_credentials = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::ParticipantsAdd { answer_sender })
+        .await?;
 
-    Response::builder()
+    Ok(Response::builder()
         .header(
             SET_COOKIE,
             format!(
@@ -100,43 +127,58 @@ pub async fn add(
             ),
         )
         .status(StatusCode::CREATED)
-        .body(Full::new(Bytes::new()))
+        .body(Full::new(Bytes::new()))?)
 }
 
-pub async fn get_votes(
-    request: Request<hyper::body::Incoming>,
-    to_central_state_authority_sender: mpsc::Sender<Message>,
-) -> ResponseResult {
-    let Some(requesting_participant_credentials) = extract_requesting_participant(&request) else {
-        return unauthorized_response();
-    };
-
-    let (answer_sender, answer_receiver) = oneshot::channel();
+pub async fn get_votes(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_participant_credentials =
+        route_request.take_extension::<ParticipantCredentials>()?;
 
-    if let Err(e) = to_central_state_authority_sender
-        .send(Message::ParticipantsGetVotes {
+    let body = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::ParticipantsGetVotes {
             requesting_participant_credentials,
             answer_sender,
         })
-        .await
-    {
-        error!("{e:?}");
-        return internal_error_response();
-    }
+        .await??;
 
-    let answer = match answer_receiver.await {
-        Ok(answer) => answer,
-        Err(err) => {
-            error!("{err:?}");
-            return internal_error_response();
-        }
+    Ok(Response::builder().body(Full::new(body))?)
+}
+
+pub async fn get_all(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let body = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminGetParticipants {
+            answer_sender,
+            requesting_credentials,
+        })
+        .await??;
+
+    Ok(Response::builder().body(Full::new(body))?)
+}
+
+pub async fn set_groups(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+    let body = route_request.take_extension::<AdminSetParticipantGroupsBody>()?;
+
+    let Some(participant_id) = route_request.path_params.parse("participant_id") else {
+        return Err(ParticipantsManageError::MissingParticipant.into());
     };
 
-    match answer {
-        Ok(body) => Response::builder().body(Full::new(body)),
-        Err(err) => {
-            error!("{err:?}");
-            err.to_response()
-        }
-    }
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminSetParticipantGroups {
+            answer_sender,
+            requesting_credentials,
+            participant_id,
+            admin_set_participant_groups_body: body,
+        })
+        .await??;
+
+    ok_response()
 }
@@ -0,0 +1,110 @@
+use hyper::StatusCode;
+use time::OffsetDateTime;
+
+use crate::{
+    common::{HandlerResult, json_response},
+    election::{BallotItemId, Election, ElectionId, ElectionStatus, Outcome},
+    i18n::{BallotItemTranslation, ElectionTranslation, Translations},
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
+};
+
+/// What the projector in the convention hall shows.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Presentation {
+    /// The most recently created election or the one whose results were published last.
+    pub election: Option<PresentedElection>,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentedElection {
+    pub id: ElectionId,
+    pub name: String,
+    pub translations: Translations<ElectionTranslation>,
+    pub status: ElectionStatus,
+    #[serde(with = "time::serde::rfc3339::option")]
+    #[schemars(with = "Option<String>")]
+    pub voting_deadline: Option<OffsetDateTime>,
+    pub num_votes_cast: usize,
+    pub num_eligible_participants: usize,
+    /// Ordered by id.
+    pub ballot_items: Vec<PresentedBallotItem>,
+    /// Only present once the admin published the results of the closed election.
+    pub results: Option<PresentedResults>,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentedBallotItem {
+    pub id: BallotItemId,
+    pub name: String,
+    pub translations: Translations<BallotItemTranslation>,
+    /// Only present once the results are published.
+    pub num_votes: Option<usize>,
+}
+
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PresentedResults {
+    pub outcome: Outcome,
+}
+
+impl PresentedElection {
+    /// `num_participants` is used as the number of eligible participants while the election is open.
+    pub fn new(election: &Election, num_participants: usize) -> Self {
+        let num_eligible_participants = match election.status {
+            ElectionStatus::Nominating { .. }
+            | ElectionStatus::AwaitingAmendments { .. }
+            | ElectionStatus::Open => num_participants,
+            ElectionStatus::Closed {
+                num_eligible_participants,
+                ..
+            } => num_eligible_participants,
+        };
+
+        let results_visible = election.results_published && election.is_closed();
+
+        let mut ballot_items: Vec<_> = election
+            .ballot_items_by_id
+            .values()
+            .map(|ballot_item| PresentedBallotItem {
+                id: ballot_item.id,
+                name: ballot_item.name.clone(),
+                translations: ballot_item.translations.clone(),
+                num_votes: results_visible.then_some(ballot_item.num_votes),
+            })
+            .collect();
+        ballot_items.sort_by_key(|ballot_item| ballot_item.id);
+
+        Self {
+            id: election.id,
+            name: election.name.clone(),
+            translations: election.translations.clone(),
+            status: election.status.clone(),
+            voting_deadline: election.voting_deadline,
+            num_votes_cast: election.participant_ids_who_voted.len(),
+            num_eligible_participants,
+            ballot_items,
+            results: results_visible.then(|| PresentedResults {
+                outcome: election.outcome(),
+            }),
+        }
+    }
+}
+
+pub async fn get(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let presentation = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::PresentationGet {
+            answer_sender,
+            requesting_credentials,
+        })
+        .await??;
+
+    json_response(StatusCode::OK, &presentation)
+}
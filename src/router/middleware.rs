@@ -0,0 +1,97 @@
+use std::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use tracing::{info, warn};
+
+use crate::{
+    common::{
+        extract_requesting_admin_credentials, extract_requesting_admin_session,
+        extract_requesting_credentials, extract_requesting_participant,
+    },
+    error::InvalidCredentialsError,
+    router::{DEFAULT_JSON_BODY_LIMIT, MiddlewareResult, RouteRequest},
+};
+
+pub async fn require_participant(mut route_request: RouteRequest) -> MiddlewareResult {
+    let Some(participant_credentials) = extract_requesting_participant(&route_request.request)
+    else {
+        return Err(InvalidCredentialsError::Missing.into());
+    };
+
+    route_request
+        .request
+        .extensions_mut()
+        .insert(participant_credentials);
+
+    Ok(route_request)
+}
+
+pub async fn require_admin_session(mut route_request: RouteRequest) -> MiddlewareResult {
+    let Some(admin_session) = extract_requesting_admin_session(&route_request.request) else {
+        return Err(InvalidCredentialsError::Missing.into());
+    };
+
+    route_request.request.extensions_mut().insert(admin_session);
+
+    Ok(route_request)
+}
+
+/// Accepts admin sessions and API tokens. Whether a token has the required scope is
+/// checked by the state authority.
+pub async fn require_admin_credentials(mut route_request: RouteRequest) -> MiddlewareResult {
+    let Some(requesting_credentials) = extract_requesting_admin_credentials(&route_request.request)
+    else {
+        return Err(InvalidCredentialsError::Missing.into());
+    };
+
+    route_request
+        .request
+        .extensions_mut()
+        .insert(requesting_credentials);
+
+    Ok(route_request)
+}
+
+pub async fn require_credentials(mut route_request: RouteRequest) -> MiddlewareResult {
+    let Some(requesting_credentials) = extract_requesting_credentials(&route_request.request)
+    else {
+        return Err(InvalidCredentialsError::Missing.into());
+    };
+
+    route_request
+        .request
+        .extensions_mut()
+        .insert(requesting_credentials);
+
+    Ok(route_request)
+}
+
+/// Parses the request body as JSON into `T` and stores it in the request extensions.
+pub async fn json_body<T>(route_request: RouteRequest) -> MiddlewareResult
+where
+    T: DeserializeOwned + Debug + Clone + Send + Sync + 'static,
+{
+    json_body_limited::<T, DEFAULT_JSON_BODY_LIMIT>(route_request).await
+}
+
+/// Like [`json_body`], but with a route specific upper bound for the body size in bytes.
+pub async fn json_body_limited<T, const LIMIT: usize>(
+    mut route_request: RouteRequest,
+) -> MiddlewareResult
+where
+    T: DeserializeOwned + Debug + Clone + Send + Sync + 'static,
+{
+    let body: T = match route_request.json_body(LIMIT).await {
+        Ok(body) => body,
+        Err(err) => {
+            warn!("Bad request: {err:?}");
+            return Err(err.into());
+        }
+    };
+
+    info!("{body:?}");
+
+    route_request.request.extensions_mut().insert(body);
+
+    Ok(route_request)
+}
@@ -0,0 +1,370 @@
+pub mod middleware;
+
+use std::{future::Future, pin::Pin, str::FromStr, sync::Arc};
+
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::{
+    Method, Request, Response, StatusCode,
+    body::{Bytes, Incoming},
+    header::{ALLOW, CONTENT_TYPE, HeaderValue},
+};
+use serde::de::DeserializeOwned;
+use tracing::{error, warn};
+
+use crate::{
+    common::{HandlerResult, ResponseResult},
+    error::{ApiError, ErrorCode, JsonBodyError},
+    event::{Event, Events},
+    i18n::Locale,
+    openapi::{self, ApiDoc, DocumentedRoute},
+};
+
+/// Upper bound for JSON request bodies unless a route asks for a different one.
+pub const DEFAULT_JSON_BODY_LIMIT: usize = 64 * 1024;
+
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// A middleware either passes the (possibly enriched) request on to the next stage
+/// or short-circuits with an error.
+pub type MiddlewareResult = Result<RouteRequest, ApiError>;
+
+type BoxedHandler = Box<dyn Fn(RouteRequest) -> BoxFuture<HandlerResult> + Send + Sync>;
+type BoxedMiddleware = Box<dyn Fn(RouteRequest) -> BoxFuture<MiddlewareResult> + Send + Sync>;
+
+pub struct RouteRequest {
+    /// The body is `None` once it has been consumed by a middleware.
+    pub request: Request<Option<Incoming>>,
+    pub path_params: PathParams,
+    /// The event selected by the host of the request, the default event for server-wide routes.
+    pub event: Arc<Event>,
+    pub events: Events,
+}
+
+impl RouteRequest {
+    /// Takes a value that a middleware stored in the request extensions.
+    /// A missing value means the route was registered without the required middleware.
+    pub fn take_extension<T: Clone + Send + Sync + 'static>(&mut self) -> Result<T, ApiError> {
+        self.request.extensions_mut().remove::<T>().ok_or_else(|| {
+            error!(
+                "Missing request extension {}, is the route missing a middleware?",
+                std::any::type_name::<T>()
+            );
+            ApiError::unexpected()
+        })
+    }
+
+    pub fn take_body(&mut self) -> Option<Incoming> {
+        self.request.body_mut().take()
+    }
+
+    /// Consumes the body and parses it as JSON, rejecting bodies larger than `limit` bytes.
+    pub async fn json_body<T: DeserializeOwned>(
+        &mut self,
+        limit: usize,
+    ) -> Result<T, JsonBodyError> {
+        let body_bytes = self.body_bytes(limit).await?;
+
+        Ok(serde_json::from_slice(&body_bytes)?)
+    }
+
+    /// Consumes the body as is, rejecting bodies larger than `limit` bytes.
+    pub async fn body_bytes(&mut self, limit: usize) -> Result<Bytes, JsonBodyError> {
+        let Some(body) = self.take_body() else {
+            error!("Request body was already consumed");
+            return Err(JsonBodyError::Unreadable);
+        };
+
+        match Limited::new(body, limit).collect().await {
+            Ok(collected) => Ok(collected.to_bytes()),
+            Err(e) if e.downcast_ref::<LengthLimitError>().is_some() => {
+                Err(JsonBodyError::TooLarge(limit))
+            }
+            Err(e) => {
+                error!("{e:?}");
+                Err(JsonBodyError::Unreadable)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PathParams(Vec<(&'static str, String)>);
+
+impl PathParams {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(param_name, _)| *param_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name)?.parse().ok()
+    }
+}
+
+enum Segment {
+    Literal(&'static str),
+    Param(&'static str),
+}
+
+pub struct Route {
+    method: Method,
+    pattern: &'static str,
+    segments: Vec<Segment>,
+    middlewares: Vec<BoxedMiddleware>,
+    handler: BoxedHandler,
+    api_doc: Option<ApiDoc>,
+    server_wide: bool,
+}
+
+impl Route {
+    /// Path parameters are written as `{name}` and match exactly one path segment,
+    /// e.g. `/elections/{election_id}`.
+    pub fn new<H, F>(method: Method, pattern: &'static str, handler: H) -> Self
+    where
+        H: Fn(RouteRequest) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        let segments = split_path(pattern)
+            .map(|segment| match segment.strip_prefix('{') {
+                Some(rest) => Segment::Param(rest.strip_suffix('}').unwrap_or(rest)),
+                None => Segment::Literal(segment),
+            })
+            .collect();
+
+        Route {
+            method,
+            pattern,
+            segments,
+            middlewares: Vec::new(),
+            handler: Box::new(move |route_request| Box::pin(handler(route_request))),
+            api_doc: None,
+            server_wide: false,
+        }
+    }
+
+    pub fn get<H, F>(pattern: &'static str, handler: H) -> Self
+    where
+        H: Fn(RouteRequest) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        Self::new(Method::GET, pattern, handler)
+    }
+
+    pub fn post<H, F>(pattern: &'static str, handler: H) -> Self
+    where
+        H: Fn(RouteRequest) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        Self::new(Method::POST, pattern, handler)
+    }
+
+    pub fn put<H, F>(pattern: &'static str, handler: H) -> Self
+    where
+        H: Fn(RouteRequest) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        Self::new(Method::PUT, pattern, handler)
+    }
+
+    pub fn delete<H, F>(pattern: &'static str, handler: H) -> Self
+    where
+        H: Fn(RouteRequest) -> F + Send + Sync + 'static,
+        F: Future<Output = HandlerResult> + Send + 'static,
+    {
+        Self::new(Method::DELETE, pattern, handler)
+    }
+
+    /// Middlewares run in the order they were added, before the handler.
+    pub fn with<M, F>(mut self, middleware: M) -> Self
+    where
+        M: Fn(RouteRequest) -> F + Send + Sync + 'static,
+        F: Future<Output = MiddlewareResult> + Send + 'static,
+    {
+        self.middlewares.push(Box::new(move |route_request| {
+            Box::pin(middleware(route_request))
+        }));
+        self
+    }
+
+    /// Only documented routes are part of the OpenAPI document.
+    pub fn doc(mut self, api_doc: ApiDoc) -> Self {
+        self.api_doc = Some(api_doc);
+        self
+    }
+
+    /// The route concerns the whole server rather than one event, so it answers on every host,
+    /// e.g. probes and scrapers addressing a pod IP or a service name.
+    pub fn server_wide(mut self) -> Self {
+        self.server_wide = true;
+        self
+    }
+
+    async fn run(&self, mut route_request: RouteRequest) -> HandlerResult {
+        for middleware in &self.middlewares {
+            route_request = middleware(route_request).await?;
+        }
+
+        (self.handler)(route_request).await
+    }
+
+    fn documented(&self) -> Option<DocumentedRoute<'_>> {
+        let api_doc = self.api_doc.as_ref()?;
+
+        let path = self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(literal) => literal.to_string(),
+                Segment::Param(name) => format!("{{{name}}}"),
+            })
+            .fold(String::new(), |path, segment| path + "/" + &segment);
+
+        let path_params = self
+            .segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Literal(_) => None,
+                Segment::Param(name) => Some(*name),
+            })
+            .collect();
+
+        Some(DocumentedRoute {
+            method: &self.method,
+            path,
+            path_params,
+            api_doc,
+        })
+    }
+
+    fn match_path(&self, path: &str) -> Option<PathParams> {
+        let mut path_segments = split_path(path);
+        let mut params = Vec::new();
+
+        for segment in &self.segments {
+            let path_segment = path_segments.next()?;
+
+            match segment {
+                Segment::Literal(literal) if *literal == path_segment => {}
+                Segment::Literal(_) => return None,
+                Segment::Param(name) => params.push((*name, path_segment.to_string())),
+            }
+        }
+
+        if path_segments.next().is_some() {
+            return None;
+        }
+
+        Some(PathParams(params))
+    }
+}
+
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn route(mut self, route: Route) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// Serves the OpenAPI document of all documented routes registered so far at `path`.
+    pub fn openapi_route(self, path: &'static str) -> Self {
+        let document = openapi::build_document(self.routes.iter().filter_map(Route::documented));
+        let document = Bytes::from_owner(serde_json::to_vec_pretty(&document).unwrap_or_default());
+
+        self.route(Route::get(path, move |_| {
+            let document = document.clone();
+            async move {
+                Ok(Response::builder()
+                    .header(CONTENT_TYPE, "application/json; charset=utf-8")
+                    .body(Full::new(document))?)
+            }
+        }))
+    }
+
+    /// The pattern of the route that handles requests with this method and path, e.g.
+    /// `/api/v1/elections/{election_id}`. Used to label metrics without one series per id.
+    pub fn matched_pattern(&self, method: &Method, path: &str) -> Option<&'static str> {
+        self.matched_route(method, path).map(|route| route.pattern)
+    }
+
+    /// Whether the request is for a [server-wide](Route::server_wide) route, which must not
+    /// depend on the event selected by the host.
+    pub fn is_server_wide(&self, method: &Method, path: &str) -> bool {
+        self.matched_route(method, path)
+            .is_some_and(|route| route.server_wide)
+    }
+
+    fn matched_route(&self, method: &Method, path: &str) -> Option<&Route> {
+        self.routes
+            .iter()
+            .find(|route| route.method == method && route.match_path(path).is_some())
+    }
+
+    pub async fn handle(
+        &self,
+        request: Request<Incoming>,
+        event: Arc<Event>,
+        events: Events,
+    ) -> ResponseResult {
+        let locale = Locale::negotiate(request.headers());
+        let mut allowed_methods = Vec::new();
+
+        for route in &self.routes {
+            let Some(path_params) = route.match_path(request.uri().path()) else {
+                continue;
+            };
+
+            if route.method != request.method() {
+                allowed_methods.push(route.method.as_str());
+                continue;
+            }
+
+            let route_request = RouteRequest {
+                request: request.map(Some),
+                path_params,
+                event,
+                events,
+            };
+
+            return match route.run(route_request).await {
+                Ok(response) => Ok(response),
+                Err(api_error) => api_error.localized(locale).to_response(),
+            };
+        }
+
+        if allowed_methods.is_empty() {
+            warn!("Unable to handle request");
+            return ApiError::not_found().localized(locale).to_response();
+        }
+
+        warn!("Method not allowed");
+        let allow = allowed_methods.join(", ");
+        let mut response = ApiError::new(
+            StatusCode::METHOD_NOT_ALLOWED,
+            ErrorCode::MethodNotAllowed,
+            "This page does not support the request method.",
+        )
+        .with_details(serde_json::json!({ "allowedMethods": allowed_methods }))
+        .localized(locale)
+        .to_response()?;
+
+        if let Ok(allow) = HeaderValue::from_str(&allow) {
+            response.headers_mut().insert(ALLOW, allow);
+        }
+
+        Ok(response)
+    }
+}
+
+fn split_path(path: &str) -> std::str::Split<'_, char> {
+    path.strip_prefix('/').unwrap_or(path).split('/')
+}
@@ -0,0 +1,76 @@
+//! Headers that tell browsers to restrict what the pages may do, sent with every response.
+
+use std::{env, sync::LazyLock};
+
+use hyper::{
+    HeaderMap,
+    header::{
+        CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY_REPORT_ONLY, HeaderName, HeaderValue,
+        REFERRER_POLICY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+    },
+};
+use tracing::{error, info};
+
+use crate::frontend;
+
+/// Replaces [`DEFAULT_CONTENT_SECURITY_POLICY`], e.g. to allow images from another host.
+const CONTENT_SECURITY_POLICY_ENV_VAR: &str = "COCO_CONTENT_SECURITY_POLICY";
+
+/// The frontend only loads scripts, styles and images from the server itself and only talks
+/// to its API.
+const DEFAULT_CONTENT_SECURITY_POLICY: &str = "default-src 'self'; script-src 'self'; \
+     style-src 'self'; img-src 'self'; connect-src 'self'; object-src 'none'; \
+     base-uri 'self'; form-action 'self'; frame-ancestors 'none'";
+
+const PERMISSIONS_POLICY: HeaderName = HeaderName::from_static("permissions-policy");
+const DENIED_PERMISSIONS: &str = "camera=(), microphone=(), geolocation=(), payment=(), usb=()";
+
+struct ContentSecurityPolicy {
+    /// While the frontend is served from disk for development, violations are only reported
+    /// in the browser console.
+    header_name: HeaderName,
+    value: HeaderValue,
+}
+
+static CONTENT_SECURITY_POLICY_HEADER: LazyLock<ContentSecurityPolicy> = LazyLock::new(|| {
+    let default_value = HeaderValue::from_static(DEFAULT_CONTENT_SECURITY_POLICY);
+    let value = match env::var(CONTENT_SECURITY_POLICY_ENV_VAR) {
+        Ok(policy) => HeaderValue::from_str(&policy).unwrap_or_else(|_| {
+            error!(
+                "{CONTENT_SECURITY_POLICY_ENV_VAR} is not a valid header value, using the default"
+            );
+            default_value
+        }),
+        Err(_) => default_value,
+    };
+
+    let header_name = if frontend::is_served_from_disk() {
+        info!("Only reporting Content-Security-Policy violations during development");
+        CONTENT_SECURITY_POLICY_REPORT_ONLY
+    } else {
+        CONTENT_SECURITY_POLICY
+    };
+
+    ContentSecurityPolicy { header_name, value }
+});
+
+/// Adds the security headers that the handler did not set itself.
+pub fn add_security_headers(headers: &mut HeaderMap) {
+    let content_security_policy = &*CONTENT_SECURITY_POLICY_HEADER;
+
+    headers
+        .entry(content_security_policy.header_name.clone())
+        .or_insert_with(|| content_security_policy.value.clone());
+    headers
+        .entry(X_CONTENT_TYPE_OPTIONS)
+        .or_insert(HeaderValue::from_static("nosniff"));
+    headers
+        .entry(REFERRER_POLICY)
+        .or_insert(HeaderValue::from_static("same-origin"));
+    headers
+        .entry(X_FRAME_OPTIONS)
+        .or_insert(HeaderValue::from_static("DENY"));
+    headers
+        .entry(PERMISSIONS_POLICY)
+        .or_insert(HeaderValue::from_static(DENIED_PERMISSIONS));
+}
@@ -0,0 +1,256 @@
+use hyper::StatusCode;
+use time::OffsetDateTime;
+
+use crate::{
+    agenda::AgendaItemId,
+    common::{HandlerResult, json_response, ok_response},
+    error::SpeakersError,
+    participant::{ParticipantCredentials, ParticipantId},
+    router::RouteRequest,
+    state::{Message, RequestingCredentials},
+};
+
+pub type SpeakerRequestId = usize;
+
+/// On which side of the debate a speaker wants to speak.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum SpeakerStance {
+    #[default]
+    Neutral,
+    Pro,
+    Contra,
+}
+
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum SpeakerRequestStatus {
+    Waiting,
+    Speaking {
+        #[serde(with = "time::serde::rfc3339")]
+        #[schemars(with = "String")]
+        started_at: OffsetDateTime,
+        /// When the speaking time runs out, if the chair set one. Speakers are not cut off
+        /// automatically.
+        #[serde(with = "time::serde::rfc3339::option")]
+        #[schemars(with = "Option<String>")]
+        speaking_deadline: Option<OffsetDateTime>,
+    },
+    Finished {
+        #[serde(with = "time::serde::rfc3339")]
+        #[schemars(with = "String")]
+        started_at: OffsetDateTime,
+        #[serde(with = "time::serde::rfc3339")]
+        #[schemars(with = "String")]
+        finished_at: OffsetDateTime,
+    },
+    /// Withdrawn by the participant or removed by an admin before speaking.
+    Withdrawn,
+}
+
+/// A participant's request to speak on an agenda item.
+#[derive(Debug, Clone, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeakerRequest {
+    pub id: SpeakerRequestId,
+    pub agenda_item_id: AgendaItemId,
+    pub participant_id: ParticipantId,
+    pub stance: SpeakerStance,
+    /// Points of order are queued before all other requests.
+    pub point_of_order: bool,
+    pub status: SpeakerRequestStatus,
+    #[serde(with = "time::serde::rfc3339")]
+    #[schemars(with = "String")]
+    pub requested_at: OffsetDateTime,
+}
+
+/// The speakers list of an agenda item.
+#[derive(Debug, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeakersList {
+    pub agenda_item_id: AgendaItemId,
+    pub current_speaker: Option<SpeakerRequest>,
+    /// In the order they will be called.
+    pub waiting: Vec<SpeakerRequest>,
+    /// Ordered by when they finished.
+    pub finished: Vec<SpeakerRequest>,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestToSpeakBody {
+    #[serde(default)]
+    pub stance: SpeakerStance,
+    #[serde(default)]
+    pub point_of_order: bool,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminCallSpeakerBody {
+    /// If set, the speaker is shown a countdown of this many seconds.
+    #[serde(default)]
+    pub speaking_time_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminOrderSpeakersBody {
+    /// All waiting requests of the agenda item, in their new order.
+    pub speaker_request_ids: Vec<SpeakerRequestId>,
+}
+
+/// What the chair can do with a request to speak.
+#[derive(Debug, Clone, Copy)]
+pub enum AdminSpeakerAction {
+    /// Gives the floor to a waiting speaker, finishing the current speaker of the agenda item.
+    Call {
+        speaking_time_seconds: Option<u64>,
+    },
+    Finish,
+    Remove,
+}
+
+pub async fn get_list(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(agenda_item_id) = route_request.path_params.parse("agenda_item_id") else {
+        return Err(SpeakersError::MissingAgendaItem.into());
+    };
+
+    let speakers_list = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::SpeakersGet {
+            answer_sender,
+            requesting_credentials,
+            agenda_item_id,
+        })
+        .await??;
+
+    json_response(StatusCode::OK, &speakers_list)
+}
+
+pub async fn request_to_speak(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_participant_credentials =
+        route_request.take_extension::<ParticipantCredentials>()?;
+    let body = route_request.take_extension::<RequestToSpeakBody>()?;
+
+    let Some(agenda_item_id) = route_request.path_params.parse("agenda_item_id") else {
+        return Err(SpeakersError::MissingAgendaItem.into());
+    };
+
+    let speaker_request = route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::SpeakersRequest {
+            answer_sender,
+            requesting_participant_credentials,
+            agenda_item_id,
+            request_to_speak_body: body,
+        })
+        .await??;
+
+    json_response(StatusCode::CREATED, &speaker_request)
+}
+
+pub async fn withdraw(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_participant_credentials =
+        route_request.take_extension::<ParticipantCredentials>()?;
+
+    let Some(speaker_request_id) = route_request.path_params.parse("speaker_request_id") else {
+        return Err(SpeakersError::MissingSpeakerRequest.into());
+    };
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::SpeakersWithdraw {
+            answer_sender,
+            requesting_participant_credentials,
+            speaker_request_id,
+        })
+        .await??;
+
+    ok_response()
+}
+
+pub async fn order(mut route_request: RouteRequest) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+    let body = route_request.take_extension::<AdminOrderSpeakersBody>()?;
+
+    let Some(agenda_item_id) = route_request.path_params.parse("agenda_item_id") else {
+        return Err(SpeakersError::MissingAgendaItem.into());
+    };
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminOrderSpeakers {
+            answer_sender,
+            requesting_credentials,
+            agenda_item_id,
+            admin_order_speakers_body: body,
+        })
+        .await??;
+
+    ok_response()
+}
+
+pub async fn call(mut route_request: RouteRequest) -> HandlerResult {
+    let body = route_request.take_extension::<AdminCallSpeakerBody>()?;
+
+    act_on_speaker_request(
+        route_request,
+        AdminSpeakerAction::Call {
+            speaking_time_seconds: body.speaking_time_seconds,
+        },
+    )
+    .await
+}
+
+pub async fn finish(route_request: RouteRequest) -> HandlerResult {
+    act_on_speaker_request(route_request, AdminSpeakerAction::Finish).await
+}
+
+pub async fn remove(route_request: RouteRequest) -> HandlerResult {
+    act_on_speaker_request(route_request, AdminSpeakerAction::Remove).await
+}
+
+async fn act_on_speaker_request(
+    mut route_request: RouteRequest,
+    action: AdminSpeakerAction,
+) -> HandlerResult {
+    let requesting_credentials = route_request.take_extension::<RequestingCredentials>()?;
+
+    let Some(speaker_request_id) = route_request.path_params.parse("speaker_request_id") else {
+        return Err(SpeakersError::MissingSpeakerRequest.into());
+    };
+
+    route_request
+        .event
+        .state_authority
+        .ask(|answer_sender| Message::AdminActOnSpeakerRequest {
+            answer_sender,
+            requesting_credentials,
+            speaker_request_id,
+            action,
+        })
+        .await??;
+
+    ok_response()
+}
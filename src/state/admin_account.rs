@@ -0,0 +1,247 @@
+use std::sync::LazyLock;
+
+use argon2::{
+    Algorithm, Argon2, Params, Version,
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString, rand_core::OsRng},
+};
+use tracing::info;
+
+use crate::{
+    admin::{
+        AdminAccount, AdminAccountId, AdminCreateAccountBody, AdminLoginAttemptBody, AdminRole,
+        AdminSession,
+    },
+    api_token::ApiTokenScope,
+    error::{AdminAccountError, InvalidCredentialsError},
+    state::{State, ValidAdminCredentials, generate_token},
+};
+
+/// Verified instead of an account's hash when the username is unknown, so that the time of a
+/// failed login does not tell whether the account exists.
+static UNKNOWN_ACCOUNT_PASSWORD_HASH: LazyLock<String> = LazyLock::new(|| hash_password(""));
+
+/// An admin session as stored by the state authority, bound to the account that started it.
+#[derive(Debug)]
+pub(super) struct StoredAdminSession {
+    token: String,
+    admin_account_id: AdminAccountId,
+}
+
+impl State {
+    pub(super) fn insert_admin_account(
+        &mut self,
+        admin_create_account_body: AdminCreateAccountBody,
+    ) -> Result<AdminAccount, AdminAccountError> {
+        if self
+            .admin_accounts_by_id
+            .values()
+            .any(|admin_account| admin_account.username == admin_create_account_body.username)
+        {
+            return Err(AdminAccountError::UsernameTaken);
+        }
+
+        let id = self.next_admin_account_id;
+        self.next_admin_account_id += 1;
+
+        let new_admin_account = AdminAccount {
+            id,
+            username: admin_create_account_body.username,
+            role: admin_create_account_body.role,
+            password_hash: hash_password(&admin_create_account_body.password),
+        };
+        self.admin_accounts_by_id
+            .insert(id, new_admin_account.clone());
+
+        Ok(new_admin_account)
+    }
+
+    pub(super) fn start_admin_session(
+        &mut self,
+        admin_login_attempt_body: AdminLoginAttemptBody,
+    ) -> Result<AdminSession, InvalidCredentialsError> {
+        let admin_account = self
+            .admin_accounts_by_id
+            .values()
+            .find(|admin_account| admin_account.username == admin_login_attempt_body.username);
+        let password_hash = admin_account
+            .map_or(&*UNKNOWN_ACCOUNT_PASSWORD_HASH, |admin_account| {
+                &admin_account.password_hash
+            });
+
+        let password_matches = verify_password(&admin_login_attempt_body.password, password_hash);
+
+        let Some(admin_account) = admin_account.filter(|_| password_matches) else {
+            return Err(InvalidCredentialsError::WrongPassword);
+        };
+
+        info!("Admin {} logged in", admin_account.username);
+
+        let id = self.admin_sessions_by_id.len();
+        let new_admin_session = AdminSession {
+            id,
+            token: generate_token(),
+        };
+        self.admin_sessions_by_id.insert(
+            id,
+            StoredAdminSession {
+                token: new_admin_session.token.clone(),
+                admin_account_id: admin_account.id,
+            },
+        );
+
+        Ok(new_admin_session)
+    }
+
+    /// Sessions of deleted accounts are rejected.
+    fn admin_account_of_session(
+        &self,
+        admin_session: &AdminSession,
+    ) -> Result<&AdminAccount, InvalidCredentialsError> {
+        let Some(stored_admin_session) = self.admin_sessions_by_id.get(&admin_session.id) else {
+            return Err(InvalidCredentialsError::Missing);
+        };
+
+        if stored_admin_session.token != admin_session.token {
+            return Err(InvalidCredentialsError::WrongToken);
+        }
+
+        self.admin_accounts_by_id
+            .get(&stored_admin_session.admin_account_id)
+            .ok_or(InvalidCredentialsError::Missing)
+    }
+
+    pub(super) fn check_admin_session_validity(
+        &self,
+        admin_session: &AdminSession,
+        required_scope: ApiTokenScope,
+    ) -> Result<ValidAdminCredentials, InvalidCredentialsError> {
+        let admin_account = self.admin_account_of_session(admin_session)?;
+
+        if admin_account.role.grants(required_scope) {
+            Ok(ValidAdminCredentials {
+                actor: admin_account.username.clone(),
+            })
+        } else {
+            Err(InvalidCredentialsError::InsufficientRole)
+        }
+    }
+
+    pub(super) fn check_super_admin(
+        &self,
+        admin_session: &AdminSession,
+    ) -> Result<ValidAdminCredentials, InvalidCredentialsError> {
+        let admin_account = self.admin_account_of_session(admin_session)?;
+
+        if admin_account.role == AdminRole::SuperAdmin {
+            Ok(ValidAdminCredentials {
+                actor: admin_account.username.clone(),
+            })
+        } else {
+            Err(InvalidCredentialsError::InsufficientRole)
+        }
+    }
+}
+
+pub(super) fn create_admin_account(
+    state: &mut State,
+    requesting_admin_session: AdminSession,
+    admin_create_account_body: AdminCreateAccountBody,
+) -> Result<AdminAccount, AdminAccountError> {
+    let valid_admin_credentials = state.check_super_admin(&requesting_admin_session)?;
+
+    let new_admin_account = state.insert_admin_account(admin_create_account_body)?;
+
+    info!(
+        "{} created admin account {} with role {:?}",
+        valid_admin_credentials.actor, new_admin_account.username, new_admin_account.role
+    );
+
+    Ok(new_admin_account)
+}
+
+pub(super) fn get_admin_accounts(
+    state: &State,
+    requesting_admin_session: AdminSession,
+) -> Result<Vec<AdminAccount>, InvalidCredentialsError> {
+    state.check_super_admin(&requesting_admin_session)?;
+
+    let mut admin_accounts: Vec<_> = state.admin_accounts_by_id.values().cloned().collect();
+    admin_accounts.sort_by_key(|admin_account| admin_account.id);
+
+    Ok(admin_accounts)
+}
+
+pub(super) fn get_own_admin_account(
+    state: &State,
+    requesting_admin_session: AdminSession,
+) -> Result<AdminAccount, InvalidCredentialsError> {
+    state
+        .admin_account_of_session(&requesting_admin_session)
+        .cloned()
+}
+
+pub(super) fn delete_admin_account(
+    state: &mut State,
+    requesting_admin_session: AdminSession,
+    admin_account_id: AdminAccountId,
+) -> Result<(), AdminAccountError> {
+    let valid_admin_credentials = state.check_super_admin(&requesting_admin_session)?;
+
+    let Some(admin_account) = state.admin_accounts_by_id.get(&admin_account_id) else {
+        return Err(AdminAccountError::MissingAccount);
+    };
+
+    let num_super_admins = state
+        .admin_accounts_by_id
+        .values()
+        .filter(|admin_account| admin_account.role == AdminRole::SuperAdmin)
+        .count();
+
+    if admin_account.role == AdminRole::SuperAdmin && num_super_admins == 1 {
+        return Err(AdminAccountError::LastSuperAdmin);
+    }
+
+    if let Some(admin_account) = state.admin_accounts_by_id.remove(&admin_account_id) {
+        info!(
+            "{} deleted admin account {}",
+            valid_admin_credentials.actor, admin_account.username
+        );
+    }
+
+    Ok(())
+}
+
+fn hash_password(password: &str) -> String {
+    let salt = SaltString::generate(&mut OsRng);
+
+    argon2()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("hashing with the default parameters and a generated salt cannot fail")
+        .to_string()
+}
+
+/// The parameters are read from the hash. The comparison of the hashes takes constant time.
+fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash).is_ok_and(|password_hash| {
+        Argon2::default()
+            .verify_password(password.as_bytes(), &password_hash)
+            .is_ok()
+    })
+}
+
+/// The property-based tests log in for every generated case, so they hash with the cheapest
+/// parameters.
+fn argon2() -> Argon2<'static> {
+    if cfg!(test) {
+        let params = Params::new(
+            Params::MIN_M_COST,
+            Params::MIN_T_COST,
+            Params::MIN_P_COST,
+            None,
+        )
+        .expect("the minimum parameters are valid");
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+    } else {
+        Argon2::default()
+    }
+}
@@ -0,0 +1,168 @@
+use tracing::info;
+
+use crate::{
+    agenda::{
+        AdminCreateAgendaItemBody, AdminCreateAgendaSessionBody, AgendaItem,
+        AgendaItemWithElections, AgendaSession, AgendaSessionWithItems, ElectionsByAgenda,
+    },
+    api_token::ApiTokenScope,
+    election::Election,
+    error::{AgendaError, InvalidCredentialsError},
+    state::{RequestingCredentials, State},
+};
+
+impl State {
+    pub(super) fn insert_agenda_session(
+        &mut self,
+        admin_create_agenda_session_body: AdminCreateAgendaSessionBody,
+    ) -> AgendaSession {
+        let id = self.agenda_sessions_by_id.len();
+        let position = admin_create_agenda_session_body
+            .position
+            .unwrap_or_else(|| {
+                self.agenda_sessions_by_id
+                    .values()
+                    .map(|agenda_session| agenda_session.position + 1)
+                    .max()
+                    .unwrap_or(0)
+            });
+
+        let new_agenda_session = AgendaSession {
+            id,
+            title: admin_create_agenda_session_body.title,
+            description: admin_create_agenda_session_body.description,
+            position,
+        };
+        self.agenda_sessions_by_id
+            .insert(id, new_agenda_session.clone());
+
+        new_agenda_session
+    }
+
+    pub(super) fn insert_agenda_item(
+        &mut self,
+        admin_create_agenda_item_body: AdminCreateAgendaItemBody,
+    ) -> Result<AgendaItem, AgendaError> {
+        let session_id = admin_create_agenda_item_body.session_id;
+        if !self.agenda_sessions_by_id.contains_key(&session_id) {
+            return Err(AgendaError::MissingSession);
+        }
+
+        if !admin_create_agenda_item_body
+            .documents
+            .iter()
+            .all(|document| document.has_allowed_url())
+        {
+            return Err(AgendaError::InvalidDocumentUrl);
+        }
+
+        let id = self.agenda_items_by_id.len();
+        let position = admin_create_agenda_item_body.position.unwrap_or_else(|| {
+            self.agenda_items_by_id
+                .values()
+                .filter(|agenda_item| agenda_item.session_id == session_id)
+                .map(|agenda_item| agenda_item.position + 1)
+                .max()
+                .unwrap_or(0)
+        });
+
+        let new_agenda_item = AgendaItem {
+            id,
+            session_id,
+            number: admin_create_agenda_item_body.number,
+            title: admin_create_agenda_item_body.title,
+            description: admin_create_agenda_item_body.description,
+            position,
+            documents: admin_create_agenda_item_body.documents,
+        };
+        self.agenda_items_by_id.insert(id, new_agenda_item.clone());
+
+        Ok(new_agenda_item)
+    }
+
+    /// Only elections for which `is_visible` returns `true` are listed.
+    pub(super) fn elections_by_agenda(
+        &self,
+        is_visible: impl Fn(&Election) -> bool,
+    ) -> ElectionsByAgenda<'_> {
+        let mut sessions: Vec<_> = self
+            .agenda_sessions_by_id
+            .values()
+            .map(|session| {
+                let mut agenda_items: Vec<_> = self
+                    .agenda_items_by_id
+                    .values()
+                    .filter(|agenda_item| agenda_item.session_id == session.id)
+                    .map(|agenda_item| {
+                        let mut elections: Vec<_> = self
+                            .elections_by_id
+                            .values()
+                            .filter(|election| election.agenda_item_id == Some(agenda_item.id))
+                            .filter(|election| is_visible(election))
+                            .collect();
+                        elections.sort_by_key(|election| election.id);
+
+                        AgendaItemWithElections {
+                            agenda_item,
+                            elections,
+                        }
+                    })
+                    .collect();
+                agenda_items.sort_by_key(|item| (item.agenda_item.position, item.agenda_item.id));
+
+                AgendaSessionWithItems {
+                    session,
+                    agenda_items,
+                }
+            })
+            .collect();
+        sessions.sort_by_key(|session| (session.session.position, session.session.id));
+
+        let mut unscheduled_elections: Vec<_> = self
+            .elections_by_id
+            .values()
+            .filter(|election| election.agenda_item_id.is_none())
+            .filter(|election| is_visible(election))
+            .collect();
+        unscheduled_elections.sort_by_key(|election| election.id);
+
+        ElectionsByAgenda {
+            sessions,
+            unscheduled_elections,
+        }
+    }
+}
+
+pub(super) fn create_agenda_session(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    admin_create_agenda_session_body: AdminCreateAgendaSessionBody,
+) -> Result<AgendaSession, InvalidCredentialsError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
+
+    let new_agenda_session = state.insert_agenda_session(admin_create_agenda_session_body);
+    info!(
+        "{} added agenda session {}",
+        valid_admin_credentials.actor, new_agenda_session.id
+    );
+
+    Ok(new_agenda_session)
+}
+
+pub(super) fn create_agenda_item(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    admin_create_agenda_item_body: AdminCreateAgendaItemBody,
+) -> Result<AgendaItem, AgendaError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
+
+    let new_agenda_item = state.insert_agenda_item(admin_create_agenda_item_body)?;
+    info!(
+        "{} added agenda item {}",
+        valid_admin_credentials.actor, new_agenda_item.id
+    );
+
+    Ok(new_agenda_item)
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeSet;
+
+use tracing::info;
+
+use crate::{
+    admin::AdminSession,
+    api_token::{
+        AdminCreateApiTokenBody, ApiToken, ApiTokenCredentials, ApiTokenId, ApiTokenScope,
+    },
+    error::{ApiTokenError, InvalidCredentialsError},
+    state::{State, ValidAdminCredentials, generate_token},
+};
+
+impl State {
+    pub(super) fn check_api_token_validity(
+        &self,
+        api_token_credentials: &ApiTokenCredentials,
+        required_scope: ApiTokenScope,
+    ) -> Result<ValidAdminCredentials, InvalidCredentialsError> {
+        let Some(existing_api_token) = self.api_tokens_by_id.get(&api_token_credentials.id) else {
+            return Err(InvalidCredentialsError::Missing);
+        };
+
+        if existing_api_token.token != api_token_credentials.token {
+            return Err(InvalidCredentialsError::WrongToken);
+        }
+
+        if existing_api_token.scopes.contains(&required_scope) {
+            Ok(ValidAdminCredentials {
+                actor: format!("API token {}", existing_api_token.name),
+            })
+        } else {
+            Err(InvalidCredentialsError::InsufficientScope)
+        }
+    }
+
+    fn insert_api_token(&mut self, name: String, scopes: BTreeSet<ApiTokenScope>) -> ApiToken {
+        let id = self.next_api_token_id;
+        self.next_api_token_id += 1;
+
+        let new_api_token = ApiToken {
+            id,
+            name,
+            scopes,
+            token: generate_token(),
+        };
+        self.api_tokens_by_id.insert(id, new_api_token.clone());
+
+        new_api_token
+    }
+}
+
+pub(super) fn create_api_token(
+    state: &mut State,
+    requesting_admin_session: AdminSession,
+    admin_create_api_token_body: AdminCreateApiTokenBody,
+) -> Result<ApiToken, InvalidCredentialsError> {
+    let valid_admin_credentials = state.check_super_admin(&requesting_admin_session)?;
+
+    let new_api_token = state.insert_api_token(
+        admin_create_api_token_body.name,
+        admin_create_api_token_body.scopes,
+    );
+    info!(
+        "{} issued API token {}",
+        valid_admin_credentials.actor, new_api_token.name
+    );
+
+    Ok(new_api_token)
+}
+
+pub(super) fn get_api_tokens(
+    state: &State,
+    requesting_admin_session: AdminSession,
+) -> Result<Vec<ApiToken>, InvalidCredentialsError> {
+    state.check_super_admin(&requesting_admin_session)?;
+
+    let mut api_tokens: Vec<_> = state.api_tokens_by_id.values().cloned().collect();
+    api_tokens.sort_by_key(|api_token| api_token.id);
+
+    Ok(api_tokens)
+}
+
+pub(super) fn revoke_api_token(
+    state: &mut State,
+    requesting_admin_session: AdminSession,
+    api_token_id: ApiTokenId,
+) -> Result<(), ApiTokenError> {
+    let valid_admin_credentials = state.check_super_admin(&requesting_admin_session)?;
+
+    match state.api_tokens_by_id.remove(&api_token_id) {
+        Some(api_token) => {
+            info!(
+                "{} revoked API token {}",
+                valid_admin_credentials.actor, api_token.name
+            );
+            Ok(())
+        }
+        None => Err(ApiTokenError::MissingApiToken),
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+
+use hyper::body::Bytes;
+use tracing::info;
+
+use crate::{
+    api_token::ApiTokenScope,
+    attachment::{Attachment, AttachmentId, AttachmentKind},
+    error::{AttachmentError, InvalidCredentialsError},
+    state::{RequestingCredentials, State},
+};
+
+/// Attachments are uploaded while creating elections.
+const UPLOAD_SCOPE: ApiTokenScope = ApiTokenScope::ManageElections;
+
+pub(super) struct StoredAttachment {
+    pub(super) attachment: Attachment,
+    pub(super) content: Bytes,
+}
+
+/// The content is left out, it can be several megabytes.
+impl fmt::Debug for StoredAttachment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoredAttachment")
+            .field("attachment", &self.attachment)
+            .finish_non_exhaustive()
+    }
+}
+
+pub(super) fn check_attachment_upload(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+) -> Result<(), InvalidCredentialsError> {
+    state
+        .check_admin_credentials(&requesting_credentials, UPLOAD_SCOPE)
+        .map(|_| ())
+}
+
+/// `content` has to be validated against `kind` by the caller.
+pub(super) fn upload_attachment(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    kind: AttachmentKind,
+    content: Bytes,
+) -> Result<Attachment, InvalidCredentialsError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, UPLOAD_SCOPE)?;
+
+    let id = state.attachments_by_id.len();
+    let attachment = Attachment {
+        id,
+        content_type: kind,
+        size: content.len(),
+        url: format!("/api/v1/attachments/{id}"),
+    };
+
+    state.attachments_by_id.insert(
+        id,
+        StoredAttachment {
+            attachment: attachment.clone(),
+            content,
+        },
+    );
+    info!(
+        "{} uploaded attachment {id} ({}, {} bytes)",
+        valid_admin_credentials.actor,
+        kind.content_type(),
+        attachment.size
+    );
+
+    Ok(attachment)
+}
+
+pub(super) fn get_attachment(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+    attachment_id: AttachmentId,
+) -> Result<(AttachmentKind, Bytes), AttachmentError> {
+    state.check_credentials(&requesting_credentials, ApiTokenScope::ReadElections)?;
+
+    let Some(stored_attachment) = state.attachments_by_id.get(&attachment_id) else {
+        return Err(AttachmentError::MissingAttachment);
+    };
+
+    Ok((
+        stored_attachment.attachment.content_type,
+        stored_attachment.content.clone(),
+    ))
+}
@@ -0,0 +1,136 @@
+use time::OffsetDateTime;
+use tracing::info;
+
+use crate::{
+    api_token::ApiTokenScope,
+    attendance::{AttendanceChange, AttendanceChangeKind, AttendanceSettings, AttendanceSummary},
+    election::{Election, Eligibility, QuorumCheck},
+    error::{AttendanceError, InvalidCredentialsError},
+    participant::{Participant, ParticipantId},
+    state::{RequestingCredentials, State},
+};
+
+impl State {
+    pub(super) fn num_present_participants(&self) -> usize {
+        self.participants_by_id
+            .values()
+            .filter(|participant| participant.checked_in_since.is_some())
+            .count()
+    }
+
+    /// `None` if no quorum is set.
+    pub(super) fn quorum_check(&self) -> Option<QuorumCheck> {
+        self.attendance_settings.quorum.map(|quorum| {
+            let num_present = self.num_present_participants();
+
+            QuorumCheck {
+                quorum,
+                num_present,
+                is_met: num_present >= quorum,
+            }
+        })
+    }
+
+    /// Fails with the check if the quorum is set and not met.
+    pub(super) fn check_quorum(&self) -> Result<(), QuorumCheck> {
+        match self.quorum_check() {
+            Some(quorum_check) if !quorum_check.is_met => Err(quorum_check),
+            _ => Ok(()),
+        }
+    }
+
+    /// Participants admitted by `eligibility`. While check-in is required to vote, only present
+    /// participants are counted, so that turnout reflects the people in the room.
+    pub(super) fn num_eligible_participants(&self, eligibility: &Eligibility) -> usize {
+        self.participants_by_id
+            .values()
+            .filter(|participant| self.counts_as_eligible(participant, eligibility))
+            .count()
+    }
+
+    /// Like [`Self::num_eligible_participants`], but also counts the participants who already
+    /// voted in `election`, e.g. before they were checked out, so that turnout never exceeds
+    /// 100 %.
+    pub(super) fn num_eligible_participants_in(&self, election: &Election) -> usize {
+        self.participants_by_id
+            .values()
+            .filter(|participant| {
+                self.counts_as_eligible(participant, &election.eligibility)
+                    || participant
+                        .voted_ballot_item_ids_by_election_id
+                        .contains_key(&election.id)
+            })
+            .count()
+    }
+
+    fn counts_as_eligible(&self, participant: &Participant, eligibility: &Eligibility) -> bool {
+        eligibility.admits(&participant.groups)
+            && (!self.attendance_settings.require_check_in_to_vote
+                || participant.checked_in_since.is_some())
+    }
+}
+
+pub(super) fn get_attendance_summary(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+) -> Result<AttendanceSummary, InvalidCredentialsError> {
+    state.check_credentials(&requesting_credentials, ApiTokenScope::ReadElections)?;
+
+    Ok(AttendanceSummary {
+        num_registered: state.participants_by_id.len(),
+        num_present: state.num_present_participants(),
+        num_eligible: state.num_eligible_participants(&Eligibility::Everyone),
+        settings: state.attendance_settings,
+    })
+}
+
+pub(super) fn update_attendance_settings(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    attendance_settings: AttendanceSettings,
+) -> Result<(), InvalidCredentialsError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
+
+    state.attendance_settings = attendance_settings;
+    info!(
+        "{} changed the attendance settings to {attendance_settings:?}",
+        valid_admin_credentials.actor
+    );
+
+    Ok(())
+}
+
+pub(super) fn change_attendance(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    participant_id: ParticipantId,
+    kind: AttendanceChangeKind,
+) -> Result<(), AttendanceError> {
+    let valid_admin_credentials = state
+        .check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageParticipants)?;
+
+    let Some(participant) = state.participants_by_id.get_mut(&participant_id) else {
+        return Err(AttendanceError::MissingParticipant);
+    };
+
+    let now = OffsetDateTime::now_utc();
+
+    match (kind, participant.checked_in_since) {
+        (AttendanceChangeKind::CheckIn, Some(_)) => return Err(AttendanceError::AlreadyCheckedIn),
+        (AttendanceChangeKind::CheckOut, None) => return Err(AttendanceError::AlreadyCheckedOut),
+        (AttendanceChangeKind::CheckIn, None) => participant.checked_in_since = Some(now),
+        (AttendanceChangeKind::CheckOut, Some(_)) => participant.checked_in_since = None,
+    }
+
+    participant
+        .attendance_history
+        .push(AttendanceChange { kind, at: now });
+
+    info!(
+        "{} recorded {kind:?} of participant {participant_id}",
+        valid_admin_credentials.actor
+    );
+
+    Ok(())
+}
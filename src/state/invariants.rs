@@ -0,0 +1,615 @@
+//! Consistency of the vote tally. A vote is recorded in three places, which must always agree:
+//! the ballot item's `num_votes`, the election's `participant_ids_who_voted` and the
+//! participant's `voted_ballot_item_ids_by_election_id`.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use super::State;
+use crate::{
+    election::{BallotItemId, ElectionId},
+    participant::ParticipantId,
+};
+
+#[derive(Debug, Error)]
+pub enum TallyInvariantViolation {
+    #[error("participant {participant_id} voted in the missing election {election_id}")]
+    VoteInMissingElection {
+        participant_id: ParticipantId,
+        election_id: ElectionId,
+    },
+    #[error(
+        "participant {participant_id} voted for the missing ballot item {ballot_item_id} in election {election_id}"
+    )]
+    VoteForMissingBallotItem {
+        participant_id: ParticipantId,
+        election_id: ElectionId,
+        ballot_item_id: BallotItemId,
+    },
+    #[error(
+        "participant {participant_id} voted in election {election_id} without being counted as having voted"
+    )]
+    UncountedVoter {
+        participant_id: ParticipantId,
+        election_id: ElectionId,
+    },
+    #[error(
+        "participant {participant_id} is counted as having voted in election {election_id} without a recorded vote"
+    )]
+    VoterWithoutVote {
+        participant_id: ParticipantId,
+        election_id: ElectionId,
+    },
+    #[error(
+        "ballot item {ballot_item_id} of election {election_id} has {num_votes} votes, but {num_recorded_votes} were recorded"
+    )]
+    TallyMismatch {
+        election_id: ElectionId,
+        ballot_item_id: BallotItemId,
+        num_votes: usize,
+        num_recorded_votes: usize,
+    },
+}
+
+impl State {
+    /// Panics on an inconsistent tally in debug builds, the check walks every recorded vote.
+    pub(super) fn debug_assert_tally_invariants(&self) {
+        if cfg!(debug_assertions)
+            && let Err(violation) = self.check_tally_invariants()
+        {
+            panic!("inconsistent vote tally: {violation:?}");
+        }
+    }
+
+    /// Checks that every ballot item's tally equals the number of participants who recorded a
+    /// vote for it, and that every participant who voted in an election is counted exactly once.
+    pub(super) fn check_tally_invariants(&self) -> Result<(), TallyInvariantViolation> {
+        let mut num_recorded_votes_by_ballot_item: HashMap<(ElectionId, BallotItemId), usize> =
+            HashMap::new();
+
+        for (&participant_id, participant) in &self.participants_by_id {
+            for (&election_id, &ballot_item_id) in &participant.voted_ballot_item_ids_by_election_id
+            {
+                let Some(election) = self.elections_by_id.get(&election_id) else {
+                    return Err(TallyInvariantViolation::VoteInMissingElection {
+                        participant_id,
+                        election_id,
+                    });
+                };
+
+                if !election.ballot_items_by_id.contains_key(&ballot_item_id) {
+                    return Err(TallyInvariantViolation::VoteForMissingBallotItem {
+                        participant_id,
+                        election_id,
+                        ballot_item_id,
+                    });
+                }
+
+                if !election.participant_ids_who_voted.contains(&participant_id) {
+                    return Err(TallyInvariantViolation::UncountedVoter {
+                        participant_id,
+                        election_id,
+                    });
+                }
+
+                *num_recorded_votes_by_ballot_item
+                    .entry((election_id, ballot_item_id))
+                    .or_default() += 1;
+            }
+        }
+
+        for (&election_id, election) in &self.elections_by_id {
+            for &participant_id in &election.participant_ids_who_voted {
+                let has_recorded_vote =
+                    self.participants_by_id
+                        .get(&participant_id)
+                        .is_some_and(|participant| {
+                            participant
+                                .voted_ballot_item_ids_by_election_id
+                                .contains_key(&election_id)
+                        });
+
+                if !has_recorded_vote {
+                    return Err(TallyInvariantViolation::VoterWithoutVote {
+                        participant_id,
+                        election_id,
+                    });
+                }
+            }
+
+            for (&ballot_item_id, ballot_item) in &election.ballot_items_by_id {
+                let num_recorded_votes = num_recorded_votes_by_ballot_item
+                    .get(&(election_id, ballot_item_id))
+                    .copied()
+                    .unwrap_or_default();
+
+                if ballot_item.num_votes != num_recorded_votes {
+                    return Err(TallyInvariantViolation::TallyMismatch {
+                        election_id,
+                        ballot_item_id,
+                        num_votes: ballot_item.num_votes,
+                        num_recorded_votes,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+
+    use proptest::{prelude::*, sample::Index};
+    use tokio::sync::oneshot;
+
+    use super::{
+        super::{Message, RequestingCredentials, State, handle_message},
+        TallyInvariantViolation,
+    };
+    use crate::{
+        admin::{
+            AdminBallotItemBody, AdminCreateAccountBody, AdminCreateElectionBody,
+            AdminLoginAttemptBody, AdminRole, AdminSession,
+        },
+        attendance::{AttendanceChangeKind, AttendanceSettings},
+        election::{BallotItemId, ElectionId, ElectionsVoteBody, Eligibility, VotingMethod},
+        i18n::Translations,
+        nomination::{
+            NominateBody, NominationAction, NominationId, NominationReview, NominationSettings,
+        },
+        participant::{AdminSetParticipantGroupsBody, ParticipantCredentials, ParticipantId},
+    };
+
+    const GROUP: &str = "delegates";
+
+    /// What the generated sequences do. Participants, elections and nominations are picked
+    /// among the existing ones, so that most messages are valid.
+    #[derive(Debug, Clone)]
+    enum Action {
+        AddParticipant,
+        CreateElection {
+            num_ballot_items: usize,
+            nominating: bool,
+            restricted_to_group: bool,
+        },
+        Vote {
+            participant: Index,
+            election: Index,
+            ballot_item_id: BallotItemId,
+            with_wrong_token: bool,
+        },
+        CloseElection {
+            election: Index,
+        },
+        OpenElection {
+            election: Index,
+        },
+        Nominate {
+            nominator: Index,
+            election: Index,
+            candidate: Index,
+        },
+        AcceptNomination {
+            nomination: Index,
+        },
+        ApproveNomination {
+            nomination: Index,
+        },
+        SetGroups {
+            participant: Index,
+            in_group: bool,
+        },
+        ChangeAttendance {
+            participant: Index,
+            check_in: bool,
+        },
+        RequireCheckInToVote(bool),
+    }
+
+    fn action() -> impl Strategy<Value = Action> {
+        prop_oneof![
+            3 => Just(Action::AddParticipant),
+            2 => (0..4usize, any::<bool>(), any::<bool>()).prop_map(
+                |(num_ballot_items, nominating, restricted_to_group)| Action::CreateElection {
+                    num_ballot_items,
+                    nominating,
+                    restricted_to_group,
+                }
+            ),
+            10 => (any::<Index>(), any::<Index>(), 0..5usize, prop::bool::weighted(0.1)).prop_map(
+                |(participant, election, ballot_item_id, with_wrong_token)| Action::Vote {
+                    participant,
+                    election,
+                    ballot_item_id,
+                    with_wrong_token,
+                }
+            ),
+            1 => any::<Index>().prop_map(|election| Action::CloseElection { election }),
+            1 => any::<Index>().prop_map(|election| Action::OpenElection { election }),
+            2 => (any::<Index>(), any::<Index>(), any::<Index>()).prop_map(
+                |(nominator, election, candidate)| Action::Nominate {
+                    nominator,
+                    election,
+                    candidate,
+                }
+            ),
+            1 => any::<Index>().prop_map(|nomination| Action::AcceptNomination { nomination }),
+            1 => any::<Index>().prop_map(|nomination| Action::ApproveNomination { nomination }),
+            1 => (any::<Index>(), any::<bool>()).prop_map(|(participant, in_group)| {
+                Action::SetGroups {
+                    participant,
+                    in_group,
+                }
+            }),
+            1 => (any::<Index>(), any::<bool>()).prop_map(|(participant, check_in)| {
+                Action::ChangeAttendance {
+                    participant,
+                    check_in,
+                }
+            }),
+            1 => any::<bool>().prop_map(Action::RequireCheckInToVote),
+        ]
+    }
+
+    /// Sends the message built by `make_message` to `state` and returns the answer.
+    fn ask<A>(state: &mut State, make_message: impl FnOnce(oneshot::Sender<A>) -> Message) -> A {
+        let (answer_sender, mut answer_receiver) = oneshot::channel();
+        handle_message(state, make_message(answer_sender));
+        answer_receiver
+            .try_recv()
+            .expect("every message is answered right away")
+    }
+
+    /// Drives a [`State`] like the state authority does and remembers every accepted vote.
+    struct Convention {
+        state: State,
+        admin: RequestingCredentials,
+        participants: Vec<ParticipantCredentials>,
+        election_ids: Vec<ElectionId>,
+        nominations: Vec<(NominationId, ParticipantId)>,
+        accepted_votes: HashMap<(ParticipantId, ElectionId), BallotItemId>,
+    }
+
+    impl Convention {
+        fn new() -> Self {
+            let mut state = State::default();
+            state
+                .insert_admin_account(AdminCreateAccountBody {
+                    username: String::from("admin"),
+                    password: String::from("admin"),
+                    role: AdminRole::SuperAdmin,
+                })
+                .unwrap();
+            let admin_session: AdminSession =
+                ask(&mut state, |answer_sender| Message::AdminStartSession {
+                    answer_sender,
+                    admin_login_attempt_body: AdminLoginAttemptBody {
+                        username: String::from("admin"),
+                        password: String::from("admin"),
+                    },
+                })
+                .unwrap();
+
+            Self {
+                state,
+                admin: RequestingCredentials::Admin(admin_session),
+                participants: Vec::new(),
+                election_ids: Vec::new(),
+                nominations: Vec::new(),
+                accepted_votes: HashMap::new(),
+            }
+        }
+
+        fn participant(&self, index: Index) -> Option<ParticipantCredentials> {
+            (!self.participants.is_empty()).then(|| index.get(&self.participants).clone())
+        }
+
+        fn election_id(&self, index: Index) -> Option<ElectionId> {
+            (!self.election_ids.is_empty()).then(|| *index.get(&self.election_ids))
+        }
+
+        fn nomination(&self, index: Index) -> Option<(NominationId, ParticipantId)> {
+            (!self.nominations.is_empty()).then(|| *index.get(&self.nominations))
+        }
+
+        fn apply(&mut self, action: Action) -> Result<(), TestCaseError> {
+            match action {
+                Action::AddParticipant => {
+                    let credentials = ask(&mut self.state, |answer_sender| {
+                        Message::ParticipantsAdd { answer_sender }
+                    });
+                    self.participants.push(credentials);
+                }
+                Action::CreateElection {
+                    num_ballot_items,
+                    nominating,
+                    restricted_to_group,
+                } => {
+                    let eligibility = if restricted_to_group {
+                        Eligibility::Groups {
+                            groups: BTreeSet::from([String::from(GROUP)]),
+                        }
+                    } else {
+                        Eligibility::Everyone
+                    };
+                    let admin_create_election_body = AdminCreateElectionBody {
+                        name: format!("Election {}", self.election_ids.len()),
+                        translations: Translations::new(),
+                        ballot_items: (0..num_ballot_items)
+                            .map(|i| AdminBallotItemBody::Name(format!("Option {i}")))
+                            .collect(),
+                        method: VotingMethod::Plurality,
+                        eligibility,
+                        duration_seconds: None,
+                        agenda_item_id: None,
+                        nominations: nominating.then_some(NominationSettings {
+                            required_seconders: 0,
+                        }),
+                        motion: None,
+                    };
+                    let answer = ask(&mut self.state, |answer_sender| {
+                        Message::AdminCreateElection {
+                            answer_sender,
+                            requesting_credentials: self.admin.clone(),
+                            admin_create_election_body,
+                        }
+                    });
+                    prop_assert!(answer.is_ok(), "creating an election failed: {answer:?}");
+                    self.election_ids.push(self.election_ids.len());
+                }
+                Action::Vote {
+                    participant,
+                    election,
+                    ballot_item_id,
+                    with_wrong_token,
+                } => {
+                    let (Some(mut credentials), Some(election_id)) =
+                        (self.participant(participant), self.election_id(election))
+                    else {
+                        return Ok(());
+                    };
+                    if with_wrong_token {
+                        credentials.token.push('x');
+                    }
+                    let participant_id = credentials.id;
+
+                    let answer = ask(&mut self.state, |answer_sender| Message::ElectionsVote {
+                        answer_sender,
+                        requesting_participant_credentials: credentials,
+                        elections_vote_body: ElectionsVoteBody {
+                            election_id,
+                            selected_ballot_item_id: ballot_item_id,
+                        },
+                    });
+
+                    if answer.is_ok() {
+                        prop_assert!(!with_wrong_token, "a vote with a wrong token was accepted");
+                        let earlier_vote = self
+                            .accepted_votes
+                            .insert((participant_id, election_id), ballot_item_id);
+                        prop_assert_eq!(
+                            earlier_vote,
+                            None,
+                            "participant {} voted twice in election {}",
+                            participant_id,
+                            election_id
+                        );
+                    }
+                }
+                Action::CloseElection { election } => {
+                    if let Some(election_id) = self.election_id(election) {
+                        let _ = ask(&mut self.state, |answer_sender| {
+                            Message::AdminCloseElection {
+                                answer_sender,
+                                requesting_credentials: self.admin.clone(),
+                                election_id,
+                            }
+                        });
+                    }
+                }
+                Action::OpenElection { election } => {
+                    if let Some(election_id) = self.election_id(election) {
+                        let _ = ask(&mut self.state, |answer_sender| {
+                            Message::AdminOpenElection {
+                                answer_sender,
+                                requesting_credentials: self.admin.clone(),
+                                election_id,
+                            }
+                        });
+                    }
+                }
+                Action::Nominate {
+                    nominator,
+                    election,
+                    candidate,
+                } => {
+                    let (Some(nominator), Some(election_id), Some(candidate)) = (
+                        self.participant(nominator),
+                        self.election_id(election),
+                        self.participant(candidate),
+                    ) else {
+                        return Ok(());
+                    };
+                    let answer = ask(&mut self.state, |answer_sender| Message::NominationsAdd {
+                        answer_sender,
+                        requesting_participant_credentials: nominator,
+                        election_id,
+                        nominate_body: NominateBody {
+                            candidate_name: format!("Candidate {}", candidate.id),
+                            candidate_participant_id: candidate.id,
+                        },
+                    });
+                    if let Ok(nomination) = answer {
+                        self.nominations
+                            .push((nomination.id, nomination.candidate_participant_id));
+                    }
+                }
+                Action::AcceptNomination { nomination } => {
+                    if let Some((nomination_id, candidate_id)) = self.nomination(nomination) {
+                        let candidate = self.participants[candidate_id].clone();
+                        let _ = ask(&mut self.state, |answer_sender| Message::NominationsAct {
+                            answer_sender,
+                            requesting_participant_credentials: candidate,
+                            nomination_id,
+                            action: NominationAction::Accept,
+                        });
+                    }
+                }
+                Action::ApproveNomination { nomination } => {
+                    if let Some((nomination_id, _)) = self.nomination(nomination) {
+                        let _ = ask(&mut self.state, |answer_sender| {
+                            Message::AdminReviewNomination {
+                                answer_sender,
+                                requesting_credentials: self.admin.clone(),
+                                nomination_id,
+                                review: NominationReview::Approved,
+                            }
+                        });
+                    }
+                }
+                Action::SetGroups {
+                    participant,
+                    in_group,
+                } => {
+                    if let Some(participant) = self.participant(participant) {
+                        let groups = if in_group {
+                            BTreeSet::from([String::from(GROUP)])
+                        } else {
+                            BTreeSet::new()
+                        };
+                        let answer = ask(&mut self.state, |answer_sender| {
+                            Message::AdminSetParticipantGroups {
+                                answer_sender,
+                                requesting_credentials: self.admin.clone(),
+                                participant_id: participant.id,
+                                admin_set_participant_groups_body: AdminSetParticipantGroupsBody {
+                                    groups,
+                                },
+                            }
+                        });
+                        prop_assert!(answer.is_ok(), "setting groups failed: {answer:?}");
+                    }
+                }
+                Action::ChangeAttendance {
+                    participant,
+                    check_in,
+                } => {
+                    if let Some(participant) = self.participant(participant) {
+                        let _ = ask(&mut self.state, |answer_sender| {
+                            Message::AdminChangeAttendance {
+                                answer_sender,
+                                requesting_credentials: self.admin.clone(),
+                                participant_id: participant.id,
+                                kind: if check_in {
+                                    AttendanceChangeKind::CheckIn
+                                } else {
+                                    AttendanceChangeKind::CheckOut
+                                },
+                            }
+                        });
+                    }
+                }
+                Action::RequireCheckInToVote(require_check_in_to_vote) => {
+                    let answer = ask(&mut self.state, |answer_sender| {
+                        Message::AdminUpdateAttendanceSettings {
+                            answer_sender,
+                            requesting_credentials: self.admin.clone(),
+                            attendance_settings: AttendanceSettings {
+                                require_check_in_to_vote,
+                                quorum: None,
+                            },
+                        }
+                    });
+                    prop_assert!(answer.is_ok(), "updating the settings failed: {answer:?}");
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Compares the tallies with the votes that were accepted.
+        fn check_tallies_match_accepted_votes(&self) -> Result<(), TestCaseError> {
+            for &election_id in &self.election_ids {
+                let election = &self.state.elections_by_id[&election_id];
+
+                for (&ballot_item_id, ballot_item) in &election.ballot_items_by_id {
+                    let num_accepted_votes = self
+                        .accepted_votes
+                        .iter()
+                        .filter(|&(&(_, voted_election_id), &voted_ballot_item_id)| {
+                            voted_election_id == election_id
+                                && voted_ballot_item_id == ballot_item_id
+                        })
+                        .count();
+                    prop_assert_eq!(
+                        ballot_item.num_votes,
+                        num_accepted_votes,
+                        "tally of ballot item {} in election {}",
+                        ballot_item_id,
+                        election_id
+                    );
+                }
+
+                let num_accepted_voters = self
+                    .accepted_votes
+                    .keys()
+                    .filter(|&&(_, voted_election_id)| voted_election_id == election_id)
+                    .count();
+                prop_assert_eq!(
+                    election.participant_ids_who_voted.len(),
+                    num_accepted_voters
+                );
+            }
+
+            Ok(())
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn tally_stays_consistent(actions in prop::collection::vec(action(), 1..200)) {
+            let mut convention = Convention::new();
+
+            for action in actions {
+                convention.apply(action)?;
+
+                if let Err(violation) = convention.state.check_tally_invariants() {
+                    prop_assert!(false, "{}", violation);
+                }
+            }
+
+            convention.check_tallies_match_accepted_votes()?;
+        }
+    }
+
+    #[test]
+    fn check_detects_inconsistent_tally() {
+        let mut convention = Convention::new();
+        convention.apply(Action::AddParticipant).unwrap();
+        convention
+            .apply(Action::CreateElection {
+                num_ballot_items: 2,
+                nominating: false,
+                restricted_to_group: false,
+            })
+            .unwrap();
+        assert!(convention.state.check_tally_invariants().is_ok());
+
+        let election = convention.state.elections_by_id.get_mut(&0).unwrap();
+        election.ballot_items_by_id.get_mut(&1).unwrap().num_votes += 1;
+
+        assert!(matches!(
+            convention.state.check_tally_invariants(),
+            Err(TallyInvariantViolation::TallyMismatch {
+                election_id: 0,
+                ballot_item_id: 1,
+                num_votes: 1,
+                num_recorded_votes: 0,
+            })
+        ));
+    }
+}
@@ -1,43 +1,154 @@
-use std::collections::{HashMap, HashSet};
+mod admin_account;
+mod agenda;
+mod api_token;
+mod attachment;
+mod attendance;
+mod invariants;
+mod motion;
+mod nomination;
+mod presentation;
+mod speaker;
+
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use hyper::body::Bytes;
 use rand::seq::IndexedRandom;
+use time::{Duration, OffsetDateTime};
 use tokio::sync::{mpsc, oneshot};
-use tracing::error;
+use tracing::{error, info, info_span};
 
+use self::{admin_account::StoredAdminSession, attachment::StoredAttachment};
 use crate::{
-    admin::{AdminCreateElectionBody, AdminSession, AdminSessionId},
-    election::{BallotItem, Election, ElectionId, ElectionsVoteBody},
-    error::{ElectionsVoteError, InvalidCredentialsError},
-    participant::{Participant, ParticipantCredentials, ParticipantId, ValidParticipantId},
+    admin::{
+        AdminAccount, AdminAccountId, AdminBallotItemBody, AdminCreateAccountBody,
+        AdminCreateElectionBody, AdminDetailedBallotItemBody, AdminLoginAttemptBody,
+        AdminMotionBody, AdminSession, AdminSessionId,
+    },
+    agenda::{
+        AdminCreateAgendaItemBody, AdminCreateAgendaSessionBody, AgendaItem, AgendaItemId,
+        AgendaSession, AgendaSessionId,
+    },
+    api_token::{
+        AdminCreateApiTokenBody, ApiToken, ApiTokenCredentials, ApiTokenId, ApiTokenScope,
+    },
+    attachment::{Attachment, AttachmentId, AttachmentKind},
+    attendance::{AttendanceChangeKind, AttendanceSettings, AttendanceSummary},
+    election::{
+        BallotItem, Election, ElectionId, ElectionResults, ElectionStatus, ElectionsVoteBody,
+        Eligibility, VotingMethod,
+    },
+    error::{
+        AdminAccountError, AgendaError, ApiTokenError, AttachmentError, AttendanceError,
+        ElectionsCreateError, ElectionsGetError, ElectionsManageError, ElectionsVoteError,
+        InvalidCredentialsError, NominationError, ParticipantsManageError, SpeakersError,
+        StateAuthorityError,
+    },
+    export::ElectionReport,
+    i18n::{ElectionTranslation, Locale, Translations},
+    logging::RequestId,
+    motion::Motion,
+    nomination::{NominateBody, Nomination, NominationAction, NominationId, NominationReview},
+    participant::{
+        AdminSetParticipantGroupsBody, Participant, ParticipantCredentials, ParticipantGroup,
+        ParticipantId, ParticipantSummary, ValidParticipantId, is_valid_group_name,
+    },
+    presentation::Presentation,
+    speaker::{
+        AdminOrderSpeakersBody, AdminSpeakerAction, RequestToSpeakBody, SpeakerRequest,
+        SpeakerRequestId, SpeakersList,
+    },
 };
 
 #[derive(Debug, Default)]
 pub struct State {
     participants_by_id: HashMap<ParticipantId, Participant>,
     elections_by_id: HashMap<ElectionId, Election>,
-    admin_sessions_by_id: HashMap<AdminSessionId, AdminSession>,
+    agenda_sessions_by_id: HashMap<AgendaSessionId, AgendaSession>,
+    agenda_items_by_id: HashMap<AgendaItemId, AgendaItem>,
+    nominations_by_id: HashMap<NominationId, Nomination>,
+    attachments_by_id: HashMap<AttachmentId, StoredAttachment>,
+    speaker_requests_by_id: HashMap<SpeakerRequestId, SpeakerRequest>,
+    /// Waiting requests to speak, in the order they will be called.
+    waiting_speaker_request_ids_by_agenda_item_id: HashMap<AgendaItemId, Vec<SpeakerRequestId>>,
+    admin_accounts_by_id: HashMap<AdminAccountId, AdminAccount>,
+    next_admin_account_id: AdminAccountId,
+    admin_sessions_by_id: HashMap<AdminSessionId, StoredAdminSession>,
+    api_tokens_by_id: HashMap<ApiTokenId, ApiToken>,
+    next_api_token_id: ApiTokenId,
+    attendance_settings: AttendanceSettings,
+    /// The most recently created election or the one whose results were published last.
+    presented_election_id: Option<ElectionId>,
 }
 
 struct ValidCredentials;
 
-struct ValidAdminSession;
+/// Proof of an admin session or an API token with the scope required for the action.
+struct ValidAdminCredentials {
+    /// Who performs the action, for the logs.
+    actor: String,
+}
 
 impl State {
+    /// Participants may always pass, admin roles and API tokens need the `required_scope`.
     fn check_credentials(
         &self,
         requesting_credentials: &RequestingCredentials,
+        required_scope: ApiTokenScope,
     ) -> Result<ValidCredentials, InvalidCredentialsError> {
         match requesting_credentials {
             RequestingCredentials::Normal(participant) => self
                 .check_participant_validity(participant)
                 .map(|_| ValidCredentials),
-            RequestingCredentials::Admin(admin_session) => self
-                .check_admin_session_validity(admin_session)
+            RequestingCredentials::Admin(_) | RequestingCredentials::ApiToken(_) => self
+                .check_admin_credentials(requesting_credentials, required_scope)
                 .map(|_| ValidCredentials),
         }
     }
 
+    /// The groups of a requesting participant, `None` for admins and API tokens.
+    fn participant_groups(
+        &self,
+        requesting_credentials: &RequestingCredentials,
+    ) -> Option<&BTreeSet<ParticipantGroup>> {
+        match requesting_credentials {
+            RequestingCredentials::Normal(participant_credentials) => self
+                .participants_by_id
+                .get(&participant_credentials.id)
+                .map(|participant| &participant.groups),
+            RequestingCredentials::Admin(_) | RequestingCredentials::ApiToken(_) => None,
+        }
+    }
+
+    /// Participants only see the elections they may vote in, admins see all of them.
+    fn check_election_visibility(
+        &self,
+        requesting_credentials: &RequestingCredentials,
+        election: &Election,
+    ) -> Result<(), ElectionsGetError> {
+        match self.participant_groups(requesting_credentials) {
+            Some(groups) if !election.eligibility.admits(groups) => {
+                Err(ElectionsGetError::NotEligible)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_admin_credentials(
+        &self,
+        requesting_credentials: &RequestingCredentials,
+        required_scope: ApiTokenScope,
+    ) -> Result<ValidAdminCredentials, InvalidCredentialsError> {
+        match requesting_credentials {
+            RequestingCredentials::Normal(_) => Err(InvalidCredentialsError::Missing),
+            RequestingCredentials::Admin(admin_session) => {
+                self.check_admin_session_validity(admin_session, required_scope)
+            }
+            RequestingCredentials::ApiToken(api_token_credentials) => {
+                self.check_api_token_validity(api_token_credentials, required_scope)
+            }
+        }
+    }
+
     fn check_participant_validity(
         &self,
         requesting_participant_credentials: &ParticipantCredentials,
@@ -56,21 +167,6 @@ impl State {
         }
     }
 
-    fn check_admin_session_validity(
-        &self,
-        admin_session: &AdminSession,
-    ) -> Result<ValidAdminSession, InvalidCredentialsError> {
-        let Some(existing_admin_session) = self.admin_sessions_by_id.get(&admin_session.id) else {
-            return Err(InvalidCredentialsError::Missing);
-        };
-
-        if existing_admin_session.token == admin_session.token {
-            Ok(ValidAdminSession)
-        } else {
-            Err(InvalidCredentialsError::WrongToken)
-        }
-    }
-
     fn create_participant(&mut self) -> ParticipantCredentials {
         let id = self.participants_by_id.len();
 
@@ -80,6 +176,9 @@ impl State {
                 token: generate_token(),
             },
             voted_ballot_item_ids_by_election_id: HashMap::new(),
+            checked_in_since: None,
+            attendance_history: Vec::new(),
+            groups: BTreeSet::new(),
         };
 
         let new_participant_credentials = new_participant.credentials.clone();
@@ -88,49 +187,250 @@ impl State {
         new_participant_credentials
     }
 
-    fn create_admin_session(&mut self) -> AdminSession {
-        let id = self.admin_sessions_by_id.len();
-        let new_admin_session = AdminSession {
-            id,
-            token: generate_token(),
-        };
-        self.admin_sessions_by_id
-            .insert(id, new_admin_session.clone());
+    fn create_election(
+        &mut self,
+        valid_admin_credentials: ValidAdminCredentials,
+        admin_create_election_body: AdminCreateElectionBody,
+    ) -> Result<(), ElectionsCreateError> {
+        if let Some(agenda_item_id) = admin_create_election_body.agenda_item_id
+            && !self.agenda_items_by_id.contains_key(&agenda_item_id)
+        {
+            return Err(ElectionsCreateError::MissingAgendaItem);
+        }
+
+        if let Eligibility::Groups { groups } = &admin_create_election_body.eligibility {
+            if groups.is_empty() {
+                return Err(ElectionsCreateError::NoEligibleGroups);
+            }
+
+            if !groups.iter().all(|group| is_valid_group_name(group)) {
+                return Err(ElectionsCreateError::InvalidGroupName);
+            }
+        }
 
-        new_admin_session
+        let all_attachments_exist = admin_create_election_body
+            .ballot_items
+            .iter()
+            .filter_map(|admin_ballot_item_body| match admin_ballot_item_body {
+                AdminBallotItemBody::Name(_) => None,
+                AdminBallotItemBody::Detailed(detailed) => Some(&detailed.attachment_ids),
+            })
+            .flatten()
+            .all(|attachment_id| self.attachments_by_id.contains_key(attachment_id));
+        if !all_attachments_exist {
+            return Err(ElectionsCreateError::MissingAttachment);
+        }
+
+        self.check_motion(&admin_create_election_body)?;
+
+        let opens_immediately = admin_create_election_body.nominations.is_none()
+            && !matches!(
+                admin_create_election_body.motion,
+                Some(AdminMotionBody::Main { .. })
+            );
+        if opens_immediately {
+            self.check_quorum()
+                .map_err(ElectionsCreateError::QuorumNotMet)?;
+        }
+
+        let election_id = self.insert_election(admin_create_election_body);
+        info!(
+            "{} created election {election_id}",
+            valid_admin_credentials.actor
+        );
+
+        Ok(())
     }
 
-    fn create_election(
+    fn insert_election(
         &mut self,
-        _valid_admin_session: ValidAdminSession,
         admin_create_election_body: AdminCreateElectionBody,
-    ) {
+    ) -> ElectionId {
         let id = self.elections_by_id.len();
         let name = admin_create_election_body.name;
-        let ballot_items_by_id = admin_create_election_body
-            .ballot_items
+
+        let mut admin_ballot_item_bodies = admin_create_election_body.ballot_items;
+        if admin_create_election_body.motion.is_some() && admin_ballot_item_bodies.is_empty() {
+            admin_ballot_item_bodies = vec![
+                AdminBallotItemBody::translated("Yes", &[(Locale::De, "Ja")]),
+                AdminBallotItemBody::translated("No", &[(Locale::De, "Nein")]),
+            ];
+        }
+
+        let ballot_items_by_id = admin_ballot_item_bodies
             .into_iter()
             .enumerate()
-            .map(|(id, name)| {
+            .map(|(id, admin_ballot_item_body)| {
+                let detailed = AdminDetailedBallotItemBody::from(admin_ballot_item_body);
+                let attachments = detailed
+                    .attachment_ids
+                    .iter()
+                    .filter_map(|attachment_id| self.attachments_by_id.get(attachment_id))
+                    .map(|stored_attachment| stored_attachment.attachment.clone())
+                    .collect();
+
                 (
                     id,
                     BallotItem {
                         id,
-                        name,
+                        name: detailed.name,
+                        description: detailed.description,
+                        affiliation: detailed.affiliation,
+                        attachments,
+                        translations: detailed.translations,
                         num_votes: 0,
                     },
                 )
             })
             .collect();
 
+        let created_at = OffsetDateTime::now_utc();
+        let (status, voting_deadline) = match (
+            admin_create_election_body.nominations,
+            &admin_create_election_body.motion,
+        ) {
+            (Some(nomination_settings), _) => (
+                ElectionStatus::Nominating {
+                    required_seconders: nomination_settings.required_seconders,
+                    voting_duration_seconds: admin_create_election_body.duration_seconds,
+                },
+                None,
+            ),
+            (None, Some(AdminMotionBody::Main { .. })) => (
+                ElectionStatus::AwaitingAmendments {
+                    voting_duration_seconds: admin_create_election_body.duration_seconds,
+                },
+                None,
+            ),
+            (None, _) => (
+                ElectionStatus::Open,
+                voting_deadline(created_at, admin_create_election_body.duration_seconds),
+            ),
+        };
+
+        let motion =
+            admin_create_election_body
+                .motion
+                .map(|admin_motion_body| match admin_motion_body {
+                    AdminMotionBody::Main { text } => Motion::Main {
+                        text,
+                        consolidated_text: None,
+                        unapplied_amendment_ids: Vec::new(),
+                    },
+                    AdminMotionBody::Amendment {
+                        parent_election_id,
+                        original_text,
+                        replacement_text,
+                    } => Motion::Amendment {
+                        parent_election_id,
+                        original_text,
+                        replacement_text,
+                    },
+                });
+
         let new_election = Election {
             id,
             name,
+            translations: admin_create_election_body.translations,
+            method: admin_create_election_body.method,
+            eligibility: admin_create_election_body.eligibility,
+            status,
+            agenda_item_id: admin_create_election_body.agenda_item_id,
+            motion,
+            created_at,
+            voting_deadline,
+            results_published: false,
             ballot_items_by_id,
             participant_ids_who_voted: HashSet::new(),
         };
 
         self.elections_by_id.insert(id, new_election);
+        self.presented_election_id = Some(id);
+
+        id
+    }
+
+    fn insert_demo_data(&mut self) {
+        let warm_up_session = self.insert_agenda_session(AdminCreateAgendaSessionBody {
+            title: String::from("Day 1, opening session"),
+            description: String::new(),
+            position: None,
+        });
+
+        let warm_up_agenda_item = self.insert_agenda_item(AdminCreateAgendaItemBody {
+            session_id: warm_up_session.id,
+            number: String::from("1"),
+            title: String::from("Warm-up"),
+            description: String::from("Trying out the voting system."),
+            position: None,
+            documents: Vec::new(),
+        });
+
+        let warm_up_agenda_item_id = warm_up_agenda_item.ok().map(|agenda_item| agenda_item.id);
+
+        self.insert_election(AdminCreateElectionBody {
+            name: String::from("What is your favorite pet?"),
+            translations: german_election_name("Was ist dein Lieblingstier?"),
+            ballot_items: vec![
+                AdminBallotItemBody::translated("Cat", &[(Locale::De, "Katze")]),
+                AdminBallotItemBody::translated("Dog", &[(Locale::De, "Hund")]),
+            ],
+            method: VotingMethod::Plurality,
+            eligibility: Eligibility::Everyone,
+            duration_seconds: None,
+            agenda_item_id: warm_up_agenda_item_id,
+            nominations: None,
+            motion: None,
+        });
+
+        self.insert_election(AdminCreateElectionBody {
+            name: String::from("What is your favorite color?"),
+            translations: german_election_name("Was ist deine Lieblingsfarbe?"),
+            ballot_items: vec![
+                AdminBallotItemBody::translated("Red", &[(Locale::De, "Rot")]),
+                AdminBallotItemBody::translated("Green", &[(Locale::De, "Grün")]),
+                AdminBallotItemBody::translated("Blue", &[(Locale::De, "Blau")]),
+            ],
+            method: VotingMethod::Plurality,
+            eligibility: Eligibility::Everyone,
+            duration_seconds: None,
+            agenda_item_id: warm_up_agenda_item_id,
+            nominations: None,
+            motion: None,
+        });
+    }
+
+    /// Closes all open elections whose voting deadline has passed. Called before every message,
+    /// so that no vote is accepted after the deadline.
+    fn close_expired_elections(&mut self, now: OffsetDateTime) {
+        let expired_elections: Vec<_> = self
+            .elections_by_id
+            .values()
+            .filter_map(
+                |election| match (&election.status, election.voting_deadline) {
+                    (ElectionStatus::Open, Some(voting_deadline)) if voting_deadline <= now => {
+                        Some((
+                            election.id,
+                            voting_deadline,
+                            self.num_eligible_participants_in(election),
+                        ))
+                    }
+                    _ => None,
+                },
+            )
+            .collect();
+
+        let quorum_check = self.quorum_check();
+
+        for (election_id, voting_deadline, num_eligible_participants) in expired_elections {
+            if let Some(election) = self.elections_by_id.get_mut(&election_id) {
+                election.status = ElectionStatus::Closed {
+                    closed_at: voting_deadline,
+                    num_eligible_participants,
+                    quorum_check,
+                };
+            }
+        }
     }
 
     fn apply_vote(
@@ -138,6 +438,16 @@ impl State {
         participant_id: ValidParticipantId,
         elections_vote_body: &ElectionsVoteBody,
     ) -> Result<(), ElectionsVoteError> {
+        let Some(participant) = self.participants_by_id.get(&participant_id.0) else {
+            return Err(InvalidCredentialsError::Missing.into());
+        };
+
+        if self.attendance_settings.require_check_in_to_vote
+            && participant.checked_in_since.is_none()
+        {
+            return Err(ElectionsVoteError::NotCheckedIn);
+        }
+
         let Some(election) = self
             .elections_by_id
             .get_mut(&elections_vote_body.election_id)
@@ -145,6 +455,18 @@ impl State {
             return Err(ElectionsVoteError::MissingElection);
         };
 
+        if !election.eligibility.admits(&participant.groups) {
+            return Err(ElectionsVoteError::NotEligible);
+        }
+
+        match election.status {
+            ElectionStatus::Nominating { .. } | ElectionStatus::AwaitingAmendments { .. } => {
+                return Err(ElectionsVoteError::NotYetOpen);
+            }
+            ElectionStatus::Open => {}
+            ElectionStatus::Closed { .. } => return Err(ElectionsVoteError::ElectionClosed),
+        }
+
         if election
             .participant_ids_who_voted
             .contains(&participant_id.0)
@@ -168,6 +490,8 @@ impl State {
 }
 
 pub enum Message {
+    /// Answered right away, to check that the state authority still handles messages.
+    Ping { answer_sender: oneshot::Sender<()> },
     ParticipantsAdd {
         answer_sender: oneshot::Sender<ParticipantCredentials>,
     },
@@ -179,139 +503,292 @@ pub enum Message {
         answer_sender: oneshot::Sender<Result<Bytes, InvalidCredentialsError>>,
         requesting_credentials: RequestingCredentials,
     },
+    ElectionsGetById {
+        answer_sender: oneshot::Sender<Result<Bytes, ElectionsGetError>>,
+        requesting_credentials: RequestingCredentials,
+        election_id: ElectionId,
+    },
     ElectionsVote {
         answer_sender: oneshot::Sender<Result<(), ElectionsVoteError>>,
         requesting_participant_credentials: ParticipantCredentials,
         elections_vote_body: ElectionsVoteBody,
     },
     AdminStartSession {
-        answer_sender: oneshot::Sender<AdminSession>,
+        answer_sender: oneshot::Sender<Result<AdminSession, InvalidCredentialsError>>,
+        admin_login_attempt_body: AdminLoginAttemptBody,
     },
-    AdminCreateElection {
-        answer_sender: oneshot::Sender<Result<(), InvalidCredentialsError>>,
+    /// Answers with the username of the super admin.
+    AdminCheckSuperAdmin {
+        answer_sender: oneshot::Sender<Result<String, InvalidCredentialsError>>,
         requesting_admin_session: AdminSession,
+    },
+    AdminCreateAccount {
+        answer_sender: oneshot::Sender<Result<AdminAccount, AdminAccountError>>,
+        requesting_admin_session: AdminSession,
+        admin_create_account_body: AdminCreateAccountBody,
+    },
+    AdminGetAccounts {
+        answer_sender: oneshot::Sender<Result<Vec<AdminAccount>, InvalidCredentialsError>>,
+        requesting_admin_session: AdminSession,
+    },
+    AdminGetOwnAccount {
+        answer_sender: oneshot::Sender<Result<AdminAccount, InvalidCredentialsError>>,
+        requesting_admin_session: AdminSession,
+    },
+    AdminDeleteAccount {
+        answer_sender: oneshot::Sender<Result<(), AdminAccountError>>,
+        requesting_admin_session: AdminSession,
+        admin_account_id: AdminAccountId,
+    },
+    ElectionsGetResults {
+        answer_sender: oneshot::Sender<Result<Bytes, ElectionsGetError>>,
+        requesting_credentials: RequestingCredentials,
+        election_id: ElectionId,
+    },
+    AdminCreateElection {
+        answer_sender: oneshot::Sender<Result<(), ElectionsCreateError>>,
+        requesting_credentials: RequestingCredentials,
         admin_create_election_body: AdminCreateElectionBody,
     },
+    AdminCloseElection {
+        answer_sender: oneshot::Sender<Result<(), ElectionsManageError>>,
+        requesting_credentials: RequestingCredentials,
+        election_id: ElectionId,
+    },
+    AdminOpenElection {
+        answer_sender: oneshot::Sender<Result<(), ElectionsManageError>>,
+        requesting_credentials: RequestingCredentials,
+        election_id: ElectionId,
+    },
+    AdminPublishResults {
+        answer_sender: oneshot::Sender<Result<(), ElectionsManageError>>,
+        requesting_credentials: RequestingCredentials,
+        election_id: ElectionId,
+    },
+    AdminGetElectionReports {
+        answer_sender: oneshot::Sender<Result<Vec<ElectionReport>, InvalidCredentialsError>>,
+        requesting_credentials: RequestingCredentials,
+    },
+    AdminGetParticipants {
+        answer_sender: oneshot::Sender<Result<Bytes, InvalidCredentialsError>>,
+        requesting_credentials: RequestingCredentials,
+    },
+    AdminCreateApiToken {
+        answer_sender: oneshot::Sender<Result<ApiToken, InvalidCredentialsError>>,
+        requesting_admin_session: AdminSession,
+        admin_create_api_token_body: AdminCreateApiTokenBody,
+    },
+    AdminGetApiTokens {
+        answer_sender: oneshot::Sender<Result<Vec<ApiToken>, InvalidCredentialsError>>,
+        requesting_admin_session: AdminSession,
+    },
+    AdminRevokeApiToken {
+        answer_sender: oneshot::Sender<Result<(), ApiTokenError>>,
+        requesting_admin_session: AdminSession,
+        api_token_id: ApiTokenId,
+    },
+    AdminCreateAgendaSession {
+        answer_sender: oneshot::Sender<Result<AgendaSession, InvalidCredentialsError>>,
+        requesting_credentials: RequestingCredentials,
+        admin_create_agenda_session_body: AdminCreateAgendaSessionBody,
+    },
+    AdminCreateAgendaItem {
+        answer_sender: oneshot::Sender<Result<AgendaItem, AgendaError>>,
+        requesting_credentials: RequestingCredentials,
+        admin_create_agenda_item_body: AdminCreateAgendaItemBody,
+    },
+    PresentationGet {
+        answer_sender: oneshot::Sender<Result<Presentation, InvalidCredentialsError>>,
+        requesting_credentials: RequestingCredentials,
+    },
+    NominationsGet {
+        answer_sender: oneshot::Sender<Result<Vec<Nomination>, NominationError>>,
+        requesting_credentials: RequestingCredentials,
+        election_id: ElectionId,
+    },
+    NominationsAdd {
+        answer_sender: oneshot::Sender<Result<Nomination, NominationError>>,
+        requesting_participant_credentials: ParticipantCredentials,
+        election_id: ElectionId,
+        nominate_body: NominateBody,
+    },
+    NominationsAct {
+        answer_sender: oneshot::Sender<Result<(), NominationError>>,
+        requesting_participant_credentials: ParticipantCredentials,
+        nomination_id: NominationId,
+        action: NominationAction,
+    },
+    AdminReviewNomination {
+        answer_sender: oneshot::Sender<Result<(), NominationError>>,
+        requesting_credentials: RequestingCredentials,
+        nomination_id: NominationId,
+        review: NominationReview,
+    },
+    SpeakersGet {
+        answer_sender: oneshot::Sender<Result<SpeakersList, SpeakersError>>,
+        requesting_credentials: RequestingCredentials,
+        agenda_item_id: AgendaItemId,
+    },
+    SpeakersRequest {
+        answer_sender: oneshot::Sender<Result<SpeakerRequest, SpeakersError>>,
+        requesting_participant_credentials: ParticipantCredentials,
+        agenda_item_id: AgendaItemId,
+        request_to_speak_body: RequestToSpeakBody,
+    },
+    SpeakersWithdraw {
+        answer_sender: oneshot::Sender<Result<(), SpeakersError>>,
+        requesting_participant_credentials: ParticipantCredentials,
+        speaker_request_id: SpeakerRequestId,
+    },
+    AdminOrderSpeakers {
+        answer_sender: oneshot::Sender<Result<(), SpeakersError>>,
+        requesting_credentials: RequestingCredentials,
+        agenda_item_id: AgendaItemId,
+        admin_order_speakers_body: AdminOrderSpeakersBody,
+    },
+    AdminActOnSpeakerRequest {
+        answer_sender: oneshot::Sender<Result<(), SpeakersError>>,
+        requesting_credentials: RequestingCredentials,
+        speaker_request_id: SpeakerRequestId,
+        action: AdminSpeakerAction,
+    },
+    /// Sent before the upload is read, so that only admins can make the server buffer files.
+    AdminCheckAttachmentUpload {
+        answer_sender: oneshot::Sender<Result<(), InvalidCredentialsError>>,
+        requesting_credentials: RequestingCredentials,
+    },
+    AdminUploadAttachment {
+        answer_sender: oneshot::Sender<Result<Attachment, InvalidCredentialsError>>,
+        requesting_credentials: RequestingCredentials,
+        kind: AttachmentKind,
+        content: Bytes,
+    },
+    AttachmentsGet {
+        answer_sender: oneshot::Sender<Result<(AttachmentKind, Bytes), AttachmentError>>,
+        requesting_credentials: RequestingCredentials,
+        attachment_id: AttachmentId,
+    },
+    AdminSetParticipantGroups {
+        answer_sender: oneshot::Sender<Result<(), ParticipantsManageError>>,
+        requesting_credentials: RequestingCredentials,
+        participant_id: ParticipantId,
+        admin_set_participant_groups_body: AdminSetParticipantGroupsBody,
+    },
+    AttendanceGetSummary {
+        answer_sender: oneshot::Sender<Result<AttendanceSummary, InvalidCredentialsError>>,
+        requesting_credentials: RequestingCredentials,
+    },
+    AdminUpdateAttendanceSettings {
+        answer_sender: oneshot::Sender<Result<(), InvalidCredentialsError>>,
+        requesting_credentials: RequestingCredentials,
+        attendance_settings: AttendanceSettings,
+    },
+    AdminChangeAttendance {
+        answer_sender: oneshot::Sender<Result<(), AttendanceError>>,
+        requesting_credentials: RequestingCredentials,
+        participant_id: ParticipantId,
+        kind: AttendanceChangeKind,
+    },
 }
 
+/// A message together with the ID of the request that sent it, if any.
+pub struct Envelope {
+    request_id: Option<RequestId>,
+    message: Message,
+}
+
+/// Cheaply clonable handle for sending [`Message`]s to the [`central_state_authority`].
+#[derive(Clone)]
+pub struct StateAuthorityHandle {
+    sender: mpsc::Sender<Envelope>,
+}
+
+impl StateAuthorityHandle {
+    pub fn new(sender: mpsc::Sender<Envelope>) -> Self {
+        Self { sender }
+    }
+
+    /// Number of messages that wait to be handled.
+    pub fn queue_depth(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
+
+    pub fn queue_capacity(&self) -> usize {
+        self.sender.max_capacity()
+    }
+
+    /// Whether the state authority answers a ping within `deadline`.
+    pub async fn ping(&self, deadline: std::time::Duration) -> bool {
+        let answer = tokio::time::timeout(
+            deadline,
+            self.ask(|answer_sender| Message::Ping { answer_sender }),
+        )
+        .await;
+
+        matches!(answer, Ok(Ok(())))
+    }
+
+    /// Sends the message built by `make_message` and waits for the answer the state authority
+    /// sends back through the given oneshot channel.
+    pub async fn ask<A>(
+        &self,
+        make_message: impl FnOnce(oneshot::Sender<A>) -> Message,
+    ) -> Result<A, StateAuthorityError> {
+        let (answer_sender, answer_receiver) = oneshot::channel();
+
+        let envelope = Envelope {
+            request_id: RequestId::current(),
+            message: make_message(answer_sender),
+        };
+
+        if let Err(e) = self.sender.send(envelope).await {
+            error!("{e:?}");
+            return Err(StateAuthorityError::Unavailable);
+        }
+
+        answer_receiver.await.map_err(|e| {
+            error!("{e:?}");
+            StateAuthorityError::Unavailable
+        })
+    }
+}
+
+#[derive(Clone)]
 pub enum RequestingCredentials {
     Normal(ParticipantCredentials),
     Admin(AdminSession),
+    ApiToken(ApiTokenCredentials),
 }
 
-pub async fn central_state_authority(mut message_receiver: mpsc::Receiver<Message>) {
+/// `initial_admin_account` is created before any message is handled, so that someone can log in.
+pub async fn central_state_authority(
+    mut message_receiver: mpsc::Receiver<Envelope>,
+    initial_admin_account: AdminCreateAccountBody,
+    with_demo_data: bool,
+) {
     let mut state = State::default();
 
-    state.elections_by_id.insert(
-        0,
-        Election {
-            id: 0,
-            name: String::from("What is your favorite pet?"),
-            ballot_items_by_id: HashMap::from([
-                (
-                    0,
-                    BallotItem {
-                        id: 0,
-                        name: String::from("Cat"),
-                        num_votes: 0,
-                    },
-                ),
-                (
-                    1,
-                    BallotItem {
-                        id: 1,
-                        name: String::from("Dog"),
-                        num_votes: 0,
-                    },
-                ),
-            ]),
-            participant_ids_who_voted: HashSet::new(),
-        },
-    );
+    if let Err(e) = state.insert_admin_account(initial_admin_account) {
+        error!("Unable to create the initial admin account: {e}");
+    }
 
-    state.elections_by_id.insert(
-        1,
-        Election {
-            id: 1,
-            name: String::from("What is your favorite color?"),
-            ballot_items_by_id: HashMap::from([
-                (
-                    0,
-                    BallotItem {
-                        id: 0,
-                        name: String::from("Red"),
-                        num_votes: 0,
-                    },
-                ),
-                (
-                    1,
-                    BallotItem {
-                        id: 1,
-                        name: String::from("Green"),
-                        num_votes: 0,
-                    },
-                ),
-                (
-                    2,
-                    BallotItem {
-                        id: 2,
-                        name: String::from("Blue"),
-                        num_votes: 0,
-                    },
-                ),
-            ]),
-            participant_ids_who_voted: HashSet::new(),
-        },
-    );
+    if with_demo_data {
+        state.insert_demo_data();
+    }
 
-    while let Some(message) = message_receiver.recv().await {
-        let answer_send_is_err = match message {
-            Message::ParticipantsAdd { answer_sender } => {
-                let new_participant_credentials = state.create_participant();
-                answer_sender.send(new_participant_credentials).is_err()
-            }
-            Message::ParticipantsGetVotes {
-                answer_sender,
-                requesting_participant_credentials,
-            } => {
-                let answer = get_votes_of_participant(&state, requesting_participant_credentials);
-                answer_sender.send(answer).is_err()
-            }
-            Message::ElectionsGet {
-                answer_sender,
-                requesting_credentials,
-            } => {
-                let answer = get_elections(&state, requesting_credentials);
-                answer_sender.send(answer).is_err()
-            }
-            Message::ElectionsVote {
-                answer_sender,
-                requesting_participant_credentials,
-                elections_vote_body,
-            } => {
-                let answer = vote(
-                    &mut state,
-                    requesting_participant_credentials,
-                    elections_vote_body,
-                );
-
-                answer_sender.send(answer).is_err()
-            }
-            Message::AdminStartSession { answer_sender } => {
-                let new_admin_session = state.create_admin_session();
-                answer_sender.send(new_admin_session).is_err()
-            }
-            Message::AdminCreateElection {
-                answer_sender,
-                admin_create_election_body,
-                requesting_admin_session: admin_session,
-            } => {
-                let answer =
-                    create_election_as_admin(&mut state, admin_create_election_body, admin_session);
-
-                answer_sender.send(answer).is_err()
-            }
-        };
+    while let Some(Envelope {
+        request_id,
+        message,
+    }) = message_receiver.recv().await
+    {
+        let _span = info_span!(
+            "message",
+            request_id = request_id.as_ref().map(RequestId::as_str)
+        )
+        .entered();
+        state.close_expired_elections(OffsetDateTime::now_utc());
+
+        let answer_send_is_err = handle_message(&mut state, message);
 
         if answer_send_is_err {
             error!("Unexpected send answer error.");
@@ -319,6 +796,420 @@ pub async fn central_state_authority(mut message_receiver: mpsc::Receiver<Messag
     }
 }
 
+/// Applies `message` to `state` and answers it. Returns whether the answer could not be sent.
+///
+/// Debug builds check the vote tally afterwards, which also covers the elections closed by
+/// their deadline right before the message.
+fn handle_message(state: &mut State, message: Message) -> bool {
+    let answer_send_is_err = match message {
+        Message::Ping { answer_sender } => answer_sender.send(()).is_err(),
+        Message::ParticipantsAdd { answer_sender } => {
+            let new_participant_credentials = state.create_participant();
+            answer_sender.send(new_participant_credentials).is_err()
+        }
+        Message::ParticipantsGetVotes {
+            answer_sender,
+            requesting_participant_credentials,
+        } => {
+            let answer = get_votes_of_participant(state, requesting_participant_credentials);
+            answer_sender.send(answer).is_err()
+        }
+        Message::ElectionsGet {
+            answer_sender,
+            requesting_credentials,
+        } => {
+            let answer = get_elections(state, requesting_credentials);
+            answer_sender.send(answer).is_err()
+        }
+        Message::ElectionsGetById {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        } => {
+            let answer = get_election(state, requesting_credentials, election_id);
+            answer_sender.send(answer).is_err()
+        }
+        Message::ElectionsVote {
+            answer_sender,
+            requesting_participant_credentials,
+            elections_vote_body,
+        } => {
+            let answer = vote(
+                state,
+                requesting_participant_credentials,
+                elections_vote_body,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminStartSession {
+            answer_sender,
+            admin_login_attempt_body,
+        } => {
+            let answer = state.start_admin_session(admin_login_attempt_body);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminCheckSuperAdmin {
+            answer_sender,
+            requesting_admin_session,
+        } => {
+            let answer = state
+                .check_super_admin(&requesting_admin_session)
+                .map(|valid_admin_credentials| valid_admin_credentials.actor);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminCreateAccount {
+            answer_sender,
+            requesting_admin_session,
+            admin_create_account_body,
+        } => {
+            let answer = admin_account::create_admin_account(
+                state,
+                requesting_admin_session,
+                admin_create_account_body,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminGetAccounts {
+            answer_sender,
+            requesting_admin_session,
+        } => {
+            let answer = admin_account::get_admin_accounts(state, requesting_admin_session);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminGetOwnAccount {
+            answer_sender,
+            requesting_admin_session,
+        } => {
+            let answer = admin_account::get_own_admin_account(state, requesting_admin_session);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminDeleteAccount {
+            answer_sender,
+            requesting_admin_session,
+            admin_account_id,
+        } => {
+            let answer = admin_account::delete_admin_account(
+                state,
+                requesting_admin_session,
+                admin_account_id,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::ElectionsGetResults {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        } => {
+            let answer = get_election_results(state, requesting_credentials, election_id);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminCreateElection {
+            answer_sender,
+            admin_create_election_body,
+            requesting_credentials,
+        } => {
+            let answer =
+                create_election_as_admin(state, admin_create_election_body, requesting_credentials);
+
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminCloseElection {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        } => {
+            let answer = close_election(state, requesting_credentials, election_id);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminOpenElection {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        } => {
+            let answer = open_election(state, requesting_credentials, election_id);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminPublishResults {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        } => {
+            let answer = publish_results(state, requesting_credentials, election_id);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminGetElectionReports {
+            answer_sender,
+            requesting_credentials,
+        } => {
+            let answer = get_election_reports(state, requesting_credentials);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminGetParticipants {
+            answer_sender,
+            requesting_credentials,
+        } => {
+            let answer = get_participants(state, requesting_credentials);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminCreateApiToken {
+            answer_sender,
+            requesting_admin_session,
+            admin_create_api_token_body,
+        } => {
+            let answer = api_token::create_api_token(
+                state,
+                requesting_admin_session,
+                admin_create_api_token_body,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminGetApiTokens {
+            answer_sender,
+            requesting_admin_session,
+        } => {
+            let answer = api_token::get_api_tokens(state, requesting_admin_session);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminRevokeApiToken {
+            answer_sender,
+            requesting_admin_session,
+            api_token_id,
+        } => {
+            let answer = api_token::revoke_api_token(state, requesting_admin_session, api_token_id);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminCreateAgendaSession {
+            answer_sender,
+            requesting_credentials,
+            admin_create_agenda_session_body,
+        } => {
+            let answer = agenda::create_agenda_session(
+                state,
+                requesting_credentials,
+                admin_create_agenda_session_body,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminCreateAgendaItem {
+            answer_sender,
+            requesting_credentials,
+            admin_create_agenda_item_body,
+        } => {
+            let answer = agenda::create_agenda_item(
+                state,
+                requesting_credentials,
+                admin_create_agenda_item_body,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::PresentationGet {
+            answer_sender,
+            requesting_credentials,
+        } => {
+            let answer = presentation::get_presentation(state, requesting_credentials);
+            answer_sender.send(answer).is_err()
+        }
+        Message::NominationsGet {
+            answer_sender,
+            requesting_credentials,
+            election_id,
+        } => {
+            let answer = nomination::get_nominations(state, requesting_credentials, election_id);
+            answer_sender.send(answer).is_err()
+        }
+        Message::NominationsAdd {
+            answer_sender,
+            requesting_participant_credentials,
+            election_id,
+            nominate_body,
+        } => {
+            let answer = nomination::add_nomination(
+                state,
+                requesting_participant_credentials,
+                election_id,
+                nominate_body,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::NominationsAct {
+            answer_sender,
+            requesting_participant_credentials,
+            nomination_id,
+            action,
+        } => {
+            let answer = nomination::act_on_nomination(
+                state,
+                requesting_participant_credentials,
+                nomination_id,
+                action,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminReviewNomination {
+            answer_sender,
+            requesting_credentials,
+            nomination_id,
+            review,
+        } => {
+            let answer =
+                nomination::review_nomination(state, requesting_credentials, nomination_id, review);
+            answer_sender.send(answer).is_err()
+        }
+        Message::SpeakersGet {
+            answer_sender,
+            requesting_credentials,
+            agenda_item_id,
+        } => {
+            let answer = speaker::get_speakers_list(state, requesting_credentials, agenda_item_id);
+            answer_sender.send(answer).is_err()
+        }
+        Message::SpeakersRequest {
+            answer_sender,
+            requesting_participant_credentials,
+            agenda_item_id,
+            request_to_speak_body,
+        } => {
+            let answer = speaker::request_to_speak(
+                state,
+                requesting_participant_credentials,
+                agenda_item_id,
+                request_to_speak_body,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::SpeakersWithdraw {
+            answer_sender,
+            requesting_participant_credentials,
+            speaker_request_id,
+        } => {
+            let answer = speaker::withdraw_speaker_request(
+                state,
+                requesting_participant_credentials,
+                speaker_request_id,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminOrderSpeakers {
+            answer_sender,
+            requesting_credentials,
+            agenda_item_id,
+            admin_order_speakers_body,
+        } => {
+            let answer = speaker::order_speakers(
+                state,
+                requesting_credentials,
+                agenda_item_id,
+                admin_order_speakers_body,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminActOnSpeakerRequest {
+            answer_sender,
+            requesting_credentials,
+            speaker_request_id,
+            action,
+        } => {
+            let answer = speaker::act_on_speaker_request(
+                state,
+                requesting_credentials,
+                speaker_request_id,
+                action,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminCheckAttachmentUpload {
+            answer_sender,
+            requesting_credentials,
+        } => {
+            let answer = attachment::check_attachment_upload(state, requesting_credentials);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminUploadAttachment {
+            answer_sender,
+            requesting_credentials,
+            kind,
+            content,
+        } => {
+            let answer =
+                attachment::upload_attachment(state, requesting_credentials, kind, content);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AttachmentsGet {
+            answer_sender,
+            requesting_credentials,
+            attachment_id,
+        } => {
+            let answer = attachment::get_attachment(state, requesting_credentials, attachment_id);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminSetParticipantGroups {
+            answer_sender,
+            requesting_credentials,
+            participant_id,
+            admin_set_participant_groups_body,
+        } => {
+            let answer = set_participant_groups(
+                state,
+                requesting_credentials,
+                participant_id,
+                admin_set_participant_groups_body,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AttendanceGetSummary {
+            answer_sender,
+            requesting_credentials,
+        } => {
+            let answer = attendance::get_attendance_summary(state, requesting_credentials);
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminUpdateAttendanceSettings {
+            answer_sender,
+            requesting_credentials,
+            attendance_settings,
+        } => {
+            let answer = attendance::update_attendance_settings(
+                state,
+                requesting_credentials,
+                attendance_settings,
+            );
+            answer_sender.send(answer).is_err()
+        }
+        Message::AdminChangeAttendance {
+            answer_sender,
+            requesting_credentials,
+            participant_id,
+            kind,
+        } => {
+            let answer =
+                attendance::change_attendance(state, requesting_credentials, participant_id, kind);
+            answer_sender.send(answer).is_err()
+        }
+    };
+
+    state.debug_assert_tally_invariants();
+
+    answer_send_is_err
+}
+
+fn voting_deadline(
+    opened_at: OffsetDateTime,
+    duration_seconds: Option<u64>,
+) -> Option<OffsetDateTime> {
+    duration_seconds
+        .and_then(|duration_seconds| i64::try_from(duration_seconds).ok())
+        .and_then(|duration_seconds| opened_at.checked_add(Duration::seconds(duration_seconds)))
+}
+
+fn german_election_name(name: &str) -> Translations<ElectionTranslation> {
+    Translations::from([(
+        Locale::De,
+        ElectionTranslation {
+            name: String::from(name),
+        },
+    )])
+}
+
 fn generate_token() -> String {
     const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
     const TOKEN_LENGTH: usize = 32;
@@ -348,9 +1239,15 @@ fn get_elections(
     requesting_credentials: RequestingCredentials,
 ) -> Result<Bytes, InvalidCredentialsError> {
     // TODO: refactor to use ValidCredentials and make state.elections_by_id inaccessible with it
-    if let Err(err) = state.check_credentials(&requesting_credentials) {
-        Err(err)
-    } else if let Ok(serialized) = serde_json::to_vec(&state.elections_by_id) {
+    state.check_credentials(&requesting_credentials, ApiTokenScope::ReadElections)?;
+
+    let participant_groups = state.participant_groups(&requesting_credentials);
+
+    let elections_by_agenda = state.elections_by_agenda(|election| {
+        participant_groups.is_none_or(|groups| election.eligibility.admits(groups))
+    });
+
+    if let Ok(serialized) = serde_json::to_vec(&elections_by_agenda) {
         Ok(Bytes::from_owner(serialized))
     } else {
         error!("Unexpected serialization error.");
@@ -358,6 +1255,239 @@ fn get_elections(
     }
 }
 
+fn get_election(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+    election_id: ElectionId,
+) -> Result<Bytes, ElectionsGetError> {
+    state.check_credentials(&requesting_credentials, ApiTokenScope::ReadElections)?;
+
+    let Some(election) = state.elections_by_id.get(&election_id) else {
+        return Err(ElectionsGetError::MissingElection);
+    };
+
+    state.check_election_visibility(&requesting_credentials, election)?;
+
+    match serde_json::to_vec(election) {
+        Ok(serialized) => Ok(Bytes::from_owner(serialized)),
+        Err(_) => {
+            error!("Unexpected serialization error.");
+            Err(InvalidCredentialsError::Unexpected.into())
+        }
+    }
+}
+
+fn get_election_results(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+    election_id: ElectionId,
+) -> Result<Bytes, ElectionsGetError> {
+    state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ReadResults)?;
+
+    let Some(election) = state.elections_by_id.get(&election_id) else {
+        return Err(ElectionsGetError::MissingElection);
+    };
+
+    state.check_election_visibility(&requesting_credentials, election)?;
+
+    match serde_json::to_vec(&ElectionResults::from(election)) {
+        Ok(serialized) => Ok(Bytes::from_owner(serialized)),
+        Err(_) => {
+            error!("Unexpected serialization error.");
+            Err(InvalidCredentialsError::Unexpected.into())
+        }
+    }
+}
+
+fn close_election(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    election_id: ElectionId,
+) -> Result<(), ElectionsManageError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
+
+    let Some(election) = state.elections_by_id.get(&election_id) else {
+        return Err(ElectionsManageError::MissingElection);
+    };
+
+    match election.status {
+        ElectionStatus::Nominating { .. } | ElectionStatus::AwaitingAmendments { .. } => {
+            return Err(ElectionsManageError::NotYetOpen);
+        }
+        ElectionStatus::Open => {}
+        ElectionStatus::Closed { .. } => return Err(ElectionsManageError::AlreadyClosed),
+    }
+
+    let num_eligible_participants = state.num_eligible_participants_in(election);
+    let quorum_check = state.quorum_check();
+
+    let Some(election) = state.elections_by_id.get_mut(&election_id) else {
+        return Err(ElectionsManageError::MissingElection);
+    };
+
+    election.status = ElectionStatus::Closed {
+        closed_at: OffsetDateTime::now_utc(),
+        num_eligible_participants,
+        quorum_check,
+    };
+    info!(
+        "{} closed election {election_id}",
+        valid_admin_credentials.actor
+    );
+
+    Ok(())
+}
+
+/// Starts the voting after a nomination phase or once all amendments to a motion are closed.
+fn open_election(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    election_id: ElectionId,
+) -> Result<(), ElectionsManageError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
+
+    let Some(election) = state.elections_by_id.get(&election_id) else {
+        return Err(ElectionsManageError::MissingElection);
+    };
+
+    if matches!(
+        election.status,
+        ElectionStatus::Nominating { .. } | ElectionStatus::AwaitingAmendments { .. }
+    ) {
+        state
+            .check_quorum()
+            .map_err(ElectionsManageError::QuorumNotMet)?;
+    }
+
+    let voting_duration_seconds = match election.status {
+        ElectionStatus::Nominating {
+            required_seconders,
+            voting_duration_seconds,
+        } => {
+            state.add_nominated_ballot_items(election_id, required_seconders)?;
+            voting_duration_seconds
+        }
+        ElectionStatus::AwaitingAmendments {
+            voting_duration_seconds,
+        } => {
+            state.consolidate_motion(election_id)?;
+            voting_duration_seconds
+        }
+        ElectionStatus::Open | ElectionStatus::Closed { .. } => {
+            return Err(ElectionsManageError::NotNominating);
+        }
+    };
+
+    let Some(election) = state.elections_by_id.get_mut(&election_id) else {
+        return Err(ElectionsManageError::MissingElection);
+    };
+
+    election.status = ElectionStatus::Open;
+    election.voting_deadline = voting_deadline(OffsetDateTime::now_utc(), voting_duration_seconds);
+    state.presented_election_id = Some(election_id);
+    info!(
+        "{} opened voting in election {election_id}",
+        valid_admin_credentials.actor
+    );
+
+    Ok(())
+}
+
+/// Allows the results of a closed election to be shown in the presentation mode.
+fn publish_results(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    election_id: ElectionId,
+) -> Result<(), ElectionsManageError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
+
+    let Some(election) = state.elections_by_id.get_mut(&election_id) else {
+        return Err(ElectionsManageError::MissingElection);
+    };
+
+    if !election.is_closed() {
+        return Err(ElectionsManageError::NotClosed);
+    }
+
+    election.results_published = true;
+    state.presented_election_id = Some(election_id);
+    info!(
+        "{} published the results of election {election_id}",
+        valid_admin_credentials.actor
+    );
+
+    Ok(())
+}
+
+/// Reports of all closed elections, ordered by id.
+fn get_election_reports(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+) -> Result<Vec<ElectionReport>, InvalidCredentialsError> {
+    state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ReadResults)?;
+
+    let mut election_reports: Vec<_> = state
+        .elections_by_id
+        .values()
+        .filter_map(ElectionReport::from_closed_election)
+        .collect();
+    election_reports.sort_by_key(|election_report| election_report.election_id);
+
+    Ok(election_reports)
+}
+
+fn get_participants(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+) -> Result<Bytes, InvalidCredentialsError> {
+    state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageParticipants)?;
+
+    let mut participant_summaries: Vec<_> = state
+        .participants_by_id
+        .values()
+        .map(ParticipantSummary::from)
+        .collect();
+    participant_summaries.sort_by_key(|participant_summary| participant_summary.id);
+
+    match serde_json::to_vec(&participant_summaries) {
+        Ok(serialized) => Ok(Bytes::from_owner(serialized)),
+        Err(_) => {
+            error!("Unexpected serialization error.");
+            Err(InvalidCredentialsError::Unexpected)
+        }
+    }
+}
+
+fn set_participant_groups(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    participant_id: ParticipantId,
+    admin_set_participant_groups_body: AdminSetParticipantGroupsBody,
+) -> Result<(), ParticipantsManageError> {
+    let valid_admin_credentials = state
+        .check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageParticipants)?;
+
+    let groups = admin_set_participant_groups_body.groups;
+    if !groups.iter().all(|group| is_valid_group_name(group)) {
+        return Err(ParticipantsManageError::InvalidGroupName);
+    }
+
+    let Some(participant) = state.participants_by_id.get_mut(&participant_id) else {
+        return Err(ParticipantsManageError::MissingParticipant);
+    };
+
+    info!(
+        "{} set the groups of participant {participant_id} to {groups:?}",
+        valid_admin_credentials.actor
+    );
+    participant.groups = groups;
+
+    Ok(())
+}
+
 fn vote(
     state: &mut State,
     requesting_participant_credentials: ParticipantCredentials,
@@ -380,10 +1510,10 @@ fn vote(
 fn create_election_as_admin(
     state: &mut State,
     admin_create_election_body: AdminCreateElectionBody,
-    admin_session: AdminSession,
-) -> Result<(), InvalidCredentialsError> {
-    let valid_admin_session = state.check_admin_session_validity(&admin_session)?;
-    state.create_election(valid_admin_session, admin_create_election_body);
+    requesting_credentials: RequestingCredentials,
+) -> Result<(), ElectionsCreateError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
 
-    Ok(())
+    state.create_election(valid_admin_credentials, admin_create_election_body)
 }
@@ -0,0 +1,103 @@
+use crate::{
+    admin::{AdminCreateElectionBody, AdminMotionBody},
+    election::{ElectionId, ElectionStatus},
+    error::{ElectionsCreateError, ElectionsManageError},
+    motion::{self, Motion},
+    state::State,
+};
+
+impl State {
+    /// Amendments need a main motion that is not voted on yet and that contains the text
+    /// they replace.
+    pub(super) fn check_motion(
+        &self,
+        admin_create_election_body: &AdminCreateElectionBody,
+    ) -> Result<(), ElectionsCreateError> {
+        let Some(admin_motion_body) = &admin_create_election_body.motion else {
+            return Ok(());
+        };
+
+        if admin_create_election_body.nominations.is_some() {
+            return Err(ElectionsCreateError::MotionWithNominations);
+        }
+
+        let AdminMotionBody::Amendment {
+            parent_election_id,
+            original_text,
+            ..
+        } = admin_motion_body
+        else {
+            return Ok(());
+        };
+
+        let Some(parent_election) = self.elections_by_id.get(parent_election_id) else {
+            return Err(ElectionsCreateError::MissingParentMotion);
+        };
+
+        let Some(Motion::Main { text, .. }) = &parent_election.motion else {
+            return Err(ElectionsCreateError::MissingParentMotion);
+        };
+
+        if !matches!(
+            parent_election.status,
+            ElectionStatus::AwaitingAmendments { .. }
+        ) {
+            return Err(ElectionsCreateError::ParentMotionNotAwaitingAmendments);
+        }
+
+        if original_text.is_empty() || !text.contains(original_text.as_str()) {
+            return Err(ElectionsCreateError::AmendedTextNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Ends the amendment phase of a main motion by applying the adopted amendments to its
+    /// text. Fails while any amendment is still being voted on.
+    pub(super) fn consolidate_motion(
+        &mut self,
+        election_id: ElectionId,
+    ) -> Result<(), ElectionsManageError> {
+        let amendments: Vec<_> = self
+            .elections_by_id
+            .values()
+            .filter(|election| {
+                election
+                    .motion
+                    .as_ref()
+                    .and_then(Motion::parent_election_id)
+                    == Some(election_id)
+            })
+            .collect();
+
+        if !amendments.iter().all(|amendment| amendment.is_closed()) {
+            return Err(ElectionsManageError::AmendmentsNotClosed);
+        }
+
+        let Some(election) = self.elections_by_id.get(&election_id) else {
+            return Err(ElectionsManageError::MissingElection);
+        };
+
+        let Some(Motion::Main { text, .. }) = &election.motion else {
+            return Ok(());
+        };
+
+        let (new_consolidated_text, new_unapplied_amendment_ids) =
+            motion::consolidate(text, amendments);
+
+        if let Some(Some(Motion::Main {
+            consolidated_text,
+            unapplied_amendment_ids,
+            ..
+        })) = self
+            .elections_by_id
+            .get_mut(&election_id)
+            .map(|election| &mut election.motion)
+        {
+            *consolidated_text = Some(new_consolidated_text);
+            *unapplied_amendment_ids = new_unapplied_amendment_ids;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeSet;
+
+use time::OffsetDateTime;
+
+use tracing::info;
+
+use crate::{
+    api_token::ApiTokenScope,
+    election::{BallotItem, ElectionId, ElectionStatus},
+    error::{ElectionsManageError, NominationError},
+    nomination::{
+        CandidateResponse, NominateBody, Nomination, NominationAction, NominationId,
+        NominationReview,
+    },
+    participant::{ParticipantCredentials, ValidParticipantId},
+    state::{RequestingCredentials, State},
+};
+
+impl State {
+    /// Nominations can only be changed while their election is nominating, and only by
+    /// participants that may vote in it.
+    fn check_nominating_participant(
+        &self,
+        participant_id: ValidParticipantId,
+        election_id: ElectionId,
+    ) -> Result<(), NominationError> {
+        let Some(election) = self.elections_by_id.get(&election_id) else {
+            return Err(NominationError::MissingElection);
+        };
+
+        if !matches!(election.status, ElectionStatus::Nominating { .. }) {
+            return Err(NominationError::NotNominating);
+        }
+
+        let is_eligible = self
+            .participants_by_id
+            .get(&participant_id.0)
+            .is_some_and(|participant| election.eligibility.admits(&participant.groups));
+
+        if !is_eligible {
+            return Err(NominationError::NotEligible);
+        }
+
+        Ok(())
+    }
+
+    /// Ends the nomination phase: nominations that are accepted, approved and seconded often
+    /// enough are appended to the ballot in the order they were made.
+    pub(super) fn add_nominated_ballot_items(
+        &mut self,
+        election_id: ElectionId,
+        required_seconders: usize,
+    ) -> Result<(), ElectionsManageError> {
+        let Some(election) = self.elections_by_id.get_mut(&election_id) else {
+            return Err(ElectionsManageError::MissingElection);
+        };
+
+        let mut nominations_on_ballot: Vec<_> = self
+            .nominations_by_id
+            .values()
+            .filter(|nomination| nomination.election_id == election_id)
+            .filter(|nomination| nomination.is_on_ballot(required_seconders))
+            .collect();
+        nominations_on_ballot.sort_by_key(|nomination| nomination.id);
+
+        if election.ballot_items_by_id.is_empty() && nominations_on_ballot.is_empty() {
+            return Err(ElectionsManageError::NoCandidates);
+        }
+
+        for nomination in nominations_on_ballot {
+            let id = election.ballot_items_by_id.len();
+            election
+                .ballot_items_by_id
+                .insert(id, BallotItem::named(id, nomination.candidate_name.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Nominations of an election, ordered by id.
+pub(super) fn get_nominations(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+    election_id: ElectionId,
+) -> Result<Vec<Nomination>, NominationError> {
+    state.check_credentials(&requesting_credentials, ApiTokenScope::ReadElections)?;
+
+    if !state.elections_by_id.contains_key(&election_id) {
+        return Err(NominationError::MissingElection);
+    }
+
+    let mut nominations: Vec<_> = state
+        .nominations_by_id
+        .values()
+        .filter(|nomination| nomination.election_id == election_id)
+        .cloned()
+        .collect();
+    nominations.sort_by_key(|nomination| nomination.id);
+
+    Ok(nominations)
+}
+
+pub(super) fn add_nomination(
+    state: &mut State,
+    requesting_participant_credentials: ParticipantCredentials,
+    election_id: ElectionId,
+    nominate_body: NominateBody,
+) -> Result<Nomination, NominationError> {
+    let participant_id = state.check_participant_validity(&requesting_participant_credentials)?;
+    state.check_nominating_participant(participant_id, election_id)?;
+
+    let candidate_name = nominate_body.candidate_name.trim();
+    if candidate_name.is_empty() {
+        return Err(NominationError::EmptyCandidateName);
+    }
+
+    let candidate_participant_id = nominate_body.candidate_participant_id;
+    if !state
+        .participants_by_id
+        .contains_key(&candidate_participant_id)
+    {
+        return Err(NominationError::MissingCandidate);
+    }
+
+    let is_already_nominated = state.nominations_by_id.values().any(|nomination| {
+        nomination.election_id == election_id
+            && nomination.candidate_participant_id == candidate_participant_id
+    });
+    if is_already_nominated {
+        return Err(NominationError::AlreadyNominated);
+    }
+
+    let id = state.nominations_by_id.len();
+    let nomination = Nomination {
+        id,
+        election_id,
+        candidate_name: candidate_name.to_string(),
+        candidate_participant_id,
+        nominated_by: participant_id.0,
+        seconded_by: BTreeSet::new(),
+        candidate_response: if candidate_participant_id == participant_id.0 {
+            CandidateResponse::Accepted
+        } else {
+            CandidateResponse::Pending
+        },
+        review: NominationReview::Pending,
+        created_at: OffsetDateTime::now_utc(),
+    };
+
+    state.nominations_by_id.insert(id, nomination.clone());
+    info!(
+        "Participant {} nominated participant {candidate_participant_id} in election {election_id}",
+        participant_id.0
+    );
+
+    Ok(nomination)
+}
+
+pub(super) fn act_on_nomination(
+    state: &mut State,
+    requesting_participant_credentials: ParticipantCredentials,
+    nomination_id: NominationId,
+    action: NominationAction,
+) -> Result<(), NominationError> {
+    let participant_id = state.check_participant_validity(&requesting_participant_credentials)?;
+
+    let Some(election_id) = state
+        .nominations_by_id
+        .get(&nomination_id)
+        .map(|nomination| nomination.election_id)
+    else {
+        return Err(NominationError::MissingNomination);
+    };
+
+    state.check_nominating_participant(participant_id, election_id)?;
+
+    let Some(nomination) = state.nominations_by_id.get_mut(&nomination_id) else {
+        return Err(NominationError::MissingNomination);
+    };
+
+    match action {
+        NominationAction::Second => {
+            if nomination.nominated_by == participant_id.0 {
+                return Err(NominationError::OwnNomination);
+            }
+
+            if !nomination.seconded_by.insert(participant_id.0) {
+                return Err(NominationError::AlreadySeconded);
+            }
+        }
+        NominationAction::Accept | NominationAction::Decline => {
+            if nomination.candidate_participant_id != participant_id.0 {
+                return Err(NominationError::NotCandidate);
+            }
+
+            nomination.candidate_response = match action {
+                NominationAction::Accept => CandidateResponse::Accepted,
+                _ => CandidateResponse::Declined,
+            };
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn review_nomination(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    nomination_id: NominationId,
+    review: NominationReview,
+) -> Result<(), NominationError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
+
+    let Some(nomination) = state.nominations_by_id.get_mut(&nomination_id) else {
+        return Err(NominationError::MissingNomination);
+    };
+
+    let is_nominating = state
+        .elections_by_id
+        .get(&nomination.election_id)
+        .is_some_and(|election| matches!(election.status, ElectionStatus::Nominating { .. }));
+    if !is_nominating {
+        return Err(NominationError::NotNominating);
+    }
+
+    nomination.review = review;
+    info!(
+        "{} marked nomination {nomination_id} as {review:?}",
+        valid_admin_credentials.actor
+    );
+
+    Ok(())
+}
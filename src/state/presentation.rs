@@ -0,0 +1,23 @@
+use crate::{
+    api_token::ApiTokenScope,
+    error::InvalidCredentialsError,
+    presentation::{Presentation, PresentedElection},
+    state::{RequestingCredentials, State},
+};
+
+pub(super) fn get_presentation(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+) -> Result<Presentation, InvalidCredentialsError> {
+    state.check_credentials(&requesting_credentials, ApiTokenScope::ReadElections)?;
+
+    let election = state
+        .presented_election_id
+        .and_then(|election_id| state.elections_by_id.get(&election_id));
+
+    Ok(Presentation {
+        election: election.map(|election| {
+            PresentedElection::new(election, state.num_eligible_participants_in(election))
+        }),
+    })
+}
@@ -0,0 +1,300 @@
+use std::collections::BTreeSet;
+
+use time::OffsetDateTime;
+use tracing::info;
+
+use crate::{
+    agenda::AgendaItemId,
+    api_token::ApiTokenScope,
+    error::SpeakersError,
+    participant::ParticipantCredentials,
+    speaker::{
+        AdminOrderSpeakersBody, AdminSpeakerAction, RequestToSpeakBody, SpeakerRequest,
+        SpeakerRequestId, SpeakerRequestStatus, SpeakersList,
+    },
+    state::{RequestingCredentials, State, voting_deadline},
+};
+
+impl State {
+    fn waiting_speaker_request_ids(
+        &mut self,
+        agenda_item_id: AgendaItemId,
+    ) -> &mut Vec<SpeakerRequestId> {
+        self.waiting_speaker_request_ids_by_agenda_item_id
+            .entry(agenda_item_id)
+            .or_default()
+    }
+
+    /// Ends the speech of whoever has the floor on the agenda item.
+    fn finish_current_speaker(&mut self, agenda_item_id: AgendaItemId, now: OffsetDateTime) {
+        for speaker_request in self.speaker_requests_by_id.values_mut() {
+            if speaker_request.agenda_item_id != agenda_item_id {
+                continue;
+            }
+
+            if let SpeakerRequestStatus::Speaking { started_at, .. } = speaker_request.status {
+                speaker_request.status = SpeakerRequestStatus::Finished {
+                    started_at,
+                    finished_at: now,
+                };
+            }
+        }
+    }
+}
+
+pub(super) fn get_speakers_list(
+    state: &State,
+    requesting_credentials: RequestingCredentials,
+    agenda_item_id: AgendaItemId,
+) -> Result<SpeakersList, SpeakersError> {
+    state.check_credentials(&requesting_credentials, ApiTokenScope::ReadElections)?;
+
+    if !state.agenda_items_by_id.contains_key(&agenda_item_id) {
+        return Err(SpeakersError::MissingAgendaItem);
+    }
+
+    let waiting = state
+        .waiting_speaker_request_ids_by_agenda_item_id
+        .get(&agenda_item_id)
+        .into_iter()
+        .flatten()
+        .filter_map(|speaker_request_id| state.speaker_requests_by_id.get(speaker_request_id))
+        .cloned()
+        .collect();
+
+    let mut current_speaker = None;
+    let mut finished = Vec::new();
+    for speaker_request in state.speaker_requests_by_id.values() {
+        if speaker_request.agenda_item_id != agenda_item_id {
+            continue;
+        }
+
+        match speaker_request.status {
+            SpeakerRequestStatus::Speaking { .. } => {
+                current_speaker = Some(speaker_request.clone())
+            }
+            SpeakerRequestStatus::Finished { finished_at, .. } => {
+                finished.push((finished_at, speaker_request.clone()));
+            }
+            SpeakerRequestStatus::Waiting | SpeakerRequestStatus::Withdrawn => {}
+        }
+    }
+    finished.sort_by_key(|(finished_at, speaker_request)| (*finished_at, speaker_request.id));
+
+    Ok(SpeakersList {
+        agenda_item_id,
+        current_speaker,
+        waiting,
+        finished: finished
+            .into_iter()
+            .map(|(_, speaker_request)| speaker_request)
+            .collect(),
+    })
+}
+
+/// Points of order are queued behind earlier points of order but before everybody else.
+pub(super) fn request_to_speak(
+    state: &mut State,
+    requesting_participant_credentials: ParticipantCredentials,
+    agenda_item_id: AgendaItemId,
+    request_to_speak_body: RequestToSpeakBody,
+) -> Result<SpeakerRequest, SpeakersError> {
+    let participant_id = state.check_participant_validity(&requesting_participant_credentials)?;
+
+    if !state.agenda_items_by_id.contains_key(&agenda_item_id) {
+        return Err(SpeakersError::MissingAgendaItem);
+    }
+
+    let is_already_listed = state
+        .speaker_requests_by_id
+        .values()
+        .any(|speaker_request| {
+            speaker_request.agenda_item_id == agenda_item_id
+                && speaker_request.participant_id == participant_id.0
+                && matches!(
+                    speaker_request.status,
+                    SpeakerRequestStatus::Waiting | SpeakerRequestStatus::Speaking { .. }
+                )
+        });
+    if is_already_listed {
+        return Err(SpeakersError::AlreadyRequested);
+    }
+
+    let id = state.speaker_requests_by_id.len();
+    let speaker_request = SpeakerRequest {
+        id,
+        agenda_item_id,
+        participant_id: participant_id.0,
+        stance: request_to_speak_body.stance,
+        point_of_order: request_to_speak_body.point_of_order,
+        status: SpeakerRequestStatus::Waiting,
+        requested_at: OffsetDateTime::now_utc(),
+    };
+    state
+        .speaker_requests_by_id
+        .insert(id, speaker_request.clone());
+
+    let num_points_of_order = if speaker_request.point_of_order {
+        let waiting_speaker_request_ids = state
+            .waiting_speaker_request_ids_by_agenda_item_id
+            .get(&agenda_item_id)
+            .into_iter()
+            .flatten();
+        waiting_speaker_request_ids
+            .take_while(|speaker_request_id| {
+                state
+                    .speaker_requests_by_id
+                    .get(speaker_request_id)
+                    .is_some_and(|waiting| waiting.point_of_order)
+            })
+            .count()
+    } else {
+        usize::MAX
+    };
+
+    let waiting_speaker_request_ids = state.waiting_speaker_request_ids(agenda_item_id);
+    let position = num_points_of_order.min(waiting_speaker_request_ids.len());
+    waiting_speaker_request_ids.insert(position, id);
+
+    info!(
+        "Participant {} requested to speak on agenda item {agenda_item_id}",
+        participant_id.0
+    );
+
+    Ok(speaker_request)
+}
+
+pub(super) fn withdraw_speaker_request(
+    state: &mut State,
+    requesting_participant_credentials: ParticipantCredentials,
+    speaker_request_id: SpeakerRequestId,
+) -> Result<(), SpeakersError> {
+    let participant_id = state.check_participant_validity(&requesting_participant_credentials)?;
+
+    let Some(speaker_request) = state.speaker_requests_by_id.get(&speaker_request_id) else {
+        return Err(SpeakersError::MissingSpeakerRequest);
+    };
+
+    if speaker_request.participant_id != participant_id.0 {
+        return Err(SpeakersError::NotOwnRequest);
+    }
+
+    withdraw(state, speaker_request_id)
+}
+
+/// The new order has to be a permutation of the waiting requests, so that nobody is dropped
+/// or added by a chair working with an outdated list.
+pub(super) fn order_speakers(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    agenda_item_id: AgendaItemId,
+    admin_order_speakers_body: AdminOrderSpeakersBody,
+) -> Result<(), SpeakersError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
+
+    if !state.agenda_items_by_id.contains_key(&agenda_item_id) {
+        return Err(SpeakersError::MissingAgendaItem);
+    }
+
+    let new_order = admin_order_speakers_body.speaker_request_ids;
+    let waiting_speaker_request_ids = state.waiting_speaker_request_ids(agenda_item_id);
+
+    let new_ids: BTreeSet<_> = new_order.iter().collect();
+    let waiting_ids: BTreeSet<_> = waiting_speaker_request_ids.iter().collect();
+    if new_ids.len() != new_order.len() || new_ids != waiting_ids {
+        return Err(SpeakersError::InvalidOrder);
+    }
+
+    *waiting_speaker_request_ids = new_order;
+    info!(
+        "{} reordered the speakers list of agenda item {agenda_item_id}",
+        valid_admin_credentials.actor
+    );
+
+    Ok(())
+}
+
+pub(super) fn act_on_speaker_request(
+    state: &mut State,
+    requesting_credentials: RequestingCredentials,
+    speaker_request_id: SpeakerRequestId,
+    action: AdminSpeakerAction,
+) -> Result<(), SpeakersError> {
+    let valid_admin_credentials =
+        state.check_admin_credentials(&requesting_credentials, ApiTokenScope::ManageElections)?;
+
+    let Some(speaker_request) = state
+        .speaker_requests_by_id
+        .get(&speaker_request_id)
+        .cloned()
+    else {
+        return Err(SpeakersError::MissingSpeakerRequest);
+    };
+    let agenda_item_id = speaker_request.agenda_item_id;
+    let now = OffsetDateTime::now_utc();
+
+    match action {
+        AdminSpeakerAction::Call {
+            speaking_time_seconds,
+        } => {
+            if !matches!(speaker_request.status, SpeakerRequestStatus::Waiting) {
+                return Err(SpeakersError::NotWaiting);
+            }
+
+            state.finish_current_speaker(agenda_item_id, now);
+            state
+                .waiting_speaker_request_ids(agenda_item_id)
+                .retain(|waiting_id| *waiting_id != speaker_request_id);
+
+            if let Some(speaker_request) = state.speaker_requests_by_id.get_mut(&speaker_request_id)
+            {
+                speaker_request.status = SpeakerRequestStatus::Speaking {
+                    started_at: now,
+                    speaking_deadline: voting_deadline(now, speaking_time_seconds),
+                };
+            }
+            info!(
+                "{} gave the floor to participant {} on agenda item {agenda_item_id}",
+                valid_admin_credentials.actor, speaker_request.participant_id
+            );
+        }
+        AdminSpeakerAction::Finish => {
+            if !matches!(
+                speaker_request.status,
+                SpeakerRequestStatus::Speaking { .. }
+            ) {
+                return Err(SpeakersError::NotSpeaking);
+            }
+
+            state.finish_current_speaker(agenda_item_id, now);
+        }
+        AdminSpeakerAction::Remove => {
+            withdraw(state, speaker_request_id)?;
+            info!(
+                "{} removed request to speak {speaker_request_id}",
+                valid_admin_credentials.actor
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn withdraw(state: &mut State, speaker_request_id: SpeakerRequestId) -> Result<(), SpeakersError> {
+    let Some(speaker_request) = state.speaker_requests_by_id.get_mut(&speaker_request_id) else {
+        return Err(SpeakersError::MissingSpeakerRequest);
+    };
+
+    if !matches!(speaker_request.status, SpeakerRequestStatus::Waiting) {
+        return Err(SpeakersError::NotWaiting);
+    }
+
+    speaker_request.status = SpeakerRequestStatus::Withdrawn;
+    let agenda_item_id = speaker_request.agenda_item_id;
+    state
+        .waiting_speaker_request_ids(agenda_item_id)
+        .retain(|waiting_id| *waiting_id != speaker_request_id);
+
+    Ok(())
+}
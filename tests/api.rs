@@ -0,0 +1,675 @@
+//! Runs through a whole convention over HTTP and checks that every documented route was used.
+
+mod common;
+
+use hyper::{HeaderMap, Method, StatusCode};
+use serde_json::{Value, json};
+
+use common::{Client, TestServer, create_election};
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\nnot really an image";
+
+#[tokio::test]
+async fn convention_walkthrough() {
+    let server = TestServer::start().await;
+    let mut anonymous = server.client();
+    let mut admin = server.admin().await;
+    let mut participants = [
+        server.participant().await,
+        server.participant().await,
+        server.participant().await,
+    ];
+
+    check_server_endpoints(&mut anonymous).await;
+    manage_admin_accounts(&mut admin).await;
+    let agenda_item_id = create_agenda(&mut admin).await;
+    let election_id = vote_on_election(&mut admin, &mut participants, agenda_item_id).await;
+    export_results(&mut admin, election_id).await;
+    nominate_candidates(&mut admin, &mut participants).await;
+    amend_motion(&mut admin, &mut participants).await;
+    keep_speakers_list(&mut admin, &mut participants, agenda_item_id).await;
+    manage_participants(&mut admin, &participants).await;
+    use_api_token(&server, &mut admin).await;
+    manage_events(&server, &mut admin).await;
+
+    let openapi = anonymous.get("/api/v1/openapi.json").await.ok_json();
+    assert_every_documented_route_was_requested(&openapi, &server);
+}
+
+async fn check_server_endpoints(anonymous: &mut Client) {
+    anonymous
+        .get("/healthz")
+        .await
+        .assert_status(StatusCode::OK);
+
+    let readiness = anonymous.get("/readyz").await.ok_json();
+    assert_eq!(readiness["unresponsiveEvents"], json!([]));
+
+    let event = anonymous.get("/api/v1/event").await.ok_json();
+    assert_eq!(event["name"], "Test convention");
+    assert_eq!(event["isDefault"], true);
+
+    let frontend = anonymous.get("/").await;
+    frontend.assert_status(StatusCode::OK);
+    assert!(
+        frontend
+            .header("content-type")
+            .is_some_and(|content_type| content_type.starts_with("text/html"))
+    );
+    let etag = frontend.header("etag").expect("an ETag").to_string();
+    assert_eq!(frontend.header("cache-control"), Some("no-cache"));
+    assert!(
+        frontend
+            .header("content-security-policy")
+            .is_some_and(|policy| policy.contains("frame-ancestors 'none'"))
+    );
+    assert_eq!(frontend.header("x-content-type-options"), Some("nosniff"));
+
+    let mut headers = HeaderMap::new();
+    headers.insert("if-none-match", etag.parse().unwrap());
+    let response = anonymous.send(Method::GET, "/", None, headers).await;
+    response.assert_status(StatusCode::NOT_MODIFIED);
+
+    let mut headers = HeaderMap::new();
+    headers.insert("accept-encoding", "gzip".parse().unwrap());
+    let response = anonymous.send(Method::GET, "/", None, headers).await;
+    assert_eq!(response.header("content-encoding"), Some("gzip"));
+    assert_ne!(response.header("etag"), Some(etag.as_str()));
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-request-id", "walkthrough-1".parse().unwrap());
+    let response = anonymous.send(Method::GET, "/healthz", None, headers).await;
+    assert_eq!(response.header("x-request-id"), Some("walkthrough-1"));
+
+    let response = anonymous.get("/healthz").await;
+    assert!(
+        response
+            .header("x-request-id")
+            .is_some_and(|id| id.len() == 32)
+    );
+}
+
+async fn manage_admin_accounts(admin: &mut Client) {
+    let own_account = admin.get("/api/v1/admin/accounts/me").await.ok_json();
+    assert_eq!(own_account["username"], "admin");
+    assert_eq!(own_account["role"], "superAdmin");
+
+    let officer = admin
+        .post(
+            "/api/v1/admin/accounts",
+            json!({ "username": "officer", "password": "secret", "role": "electionOfficer" }),
+        )
+        .await;
+    officer.assert_status(StatusCode::CREATED);
+    let officer_id = officer.json()["id"].clone();
+
+    let accounts = admin.get("/api/v1/admin/accounts").await.ok_json();
+    let usernames: Vec<_> = accounts
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|account| account["username"].as_str().unwrap())
+        .collect();
+    assert!(usernames.contains(&"admin") && usernames.contains(&"officer"));
+
+    admin
+        .delete(&format!("/api/v1/admin/accounts/{officer_id}"))
+        .await
+        .assert_status(StatusCode::OK);
+    let accounts = admin.get("/api/v1/admin/accounts").await.ok_json();
+    assert_eq!(accounts.as_array().unwrap().len(), 1);
+}
+
+async fn create_agenda(admin: &mut Client) -> u64 {
+    let session = admin
+        .post("/api/v1/admin/agenda/sessions", json!({ "title": "Day 1" }))
+        .await;
+    session.assert_status(StatusCode::CREATED);
+    let session_id = session.json()["id"].clone();
+
+    let agenda_item = admin
+        .post(
+            "/api/v1/admin/agenda/items",
+            json!({
+                "sessionId": session_id,
+                "number": "1",
+                "title": "Board",
+                "documents": [{ "title": "Rules", "url": "https://example.com/rules.pdf" }],
+            }),
+        )
+        .await;
+    agenda_item.assert_status(StatusCode::CREATED);
+    let agenda_item = agenda_item.json();
+    assert_eq!(agenda_item["sessionId"], session_id);
+
+    agenda_item["id"].as_u64().unwrap()
+}
+
+async fn vote_on_election(
+    admin: &mut Client,
+    participants: &mut [Client; 3],
+    agenda_item_id: u64,
+) -> usize {
+    let attachment = admin
+        .post_raw("/api/v1/admin/attachments", "image/png", PNG)
+        .await;
+    attachment.assert_status(StatusCode::CREATED);
+    let attachment_id = attachment.json()["id"].clone();
+
+    let downloaded = participants[0]
+        .get(&format!("/api/v1/attachments/{attachment_id}"))
+        .await;
+    downloaded.assert_status(StatusCode::OK);
+    assert_eq!(downloaded.header("content-type"), Some("image/png"));
+    assert_eq!(&downloaded.body[..], PNG);
+
+    let election_id = create_election(
+        admin,
+        json!({
+            "name": "Board election",
+            "agendaItemId": agenda_item_id,
+            "ballotItems": [
+                "Alice",
+                { "name": "Bob", "affiliation": "Party", "attachmentIds": [attachment_id] },
+            ],
+        }),
+    )
+    .await;
+
+    let election = participants[0]
+        .get(&format!("/api/v1/elections/{election_id}"))
+        .await
+        .ok_json();
+    assert_eq!(election["status"]["kind"], "open");
+    assert_eq!(election["ballotItemsById"]["1"]["affiliation"], "Party");
+
+    for (participant, selected_ballot_item_id) in participants.iter_mut().zip([0, 0, 1]) {
+        participant
+            .post(
+                "/api/v1/votes",
+                json!({ "electionId": election_id, "selectedBallotItemId": selected_ballot_item_id }),
+            )
+            .await
+            .assert_status(StatusCode::OK);
+    }
+
+    let own_votes = participants[2]
+        .get("/api/v1/participants/me/votes")
+        .await
+        .ok_json();
+    assert_eq!(own_votes, json!({ election_id.to_string(): 1 }));
+
+    let results = admin
+        .get(&format!("/api/v1/elections/{election_id}/results"))
+        .await
+        .ok_json();
+    assert_eq!(results["numVotesCast"], 3);
+    assert_eq!(results["numVotesByBallotItemId"], json!({ "0": 2, "1": 1 }));
+
+    let presentation = participants[0].get("/api/v1/presentation").await.ok_json();
+    assert_eq!(presentation["election"]["id"], election_id);
+    assert_eq!(presentation["election"]["numVotesCast"], 3);
+
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/close"))
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/publish"))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let election = participants[0]
+        .get(&format!("/api/v1/elections/{election_id}"))
+        .await
+        .ok_json();
+    assert_eq!(election["status"]["kind"], "closed");
+    assert_eq!(election["resultsPublished"], true);
+
+    election_id
+}
+
+async fn export_results(admin: &mut Client, election_id: usize) {
+    let results_export = admin
+        .get("/api/v1/admin/exports/results.json")
+        .await
+        .ok_json();
+    assert_eq!(results_export["elections"][0]["name"], "Board election");
+    assert_eq!(results_export["elections"][0]["electionId"], election_id);
+
+    let csv = admin.get("/api/v1/admin/exports/results.csv").await;
+    csv.assert_status(StatusCode::OK);
+    assert!(csv.text().contains("Alice"));
+
+    let minutes = admin.get("/api/v1/admin/exports/minutes.html").await;
+    minutes.assert_status(StatusCode::OK);
+    assert!(minutes.text().contains("Board election"));
+
+    // The minutes are printed from the browser, so their styles must pass the policy.
+    let style_sources = minutes
+        .header("content-security-policy")
+        .and_then(|policy| {
+            policy
+                .split(';')
+                .find_map(|directive| directive.trim().strip_prefix("style-src "))
+        })
+        .expect("a style-src directive")
+        .to_string();
+    assert!(!style_sources.contains("'unsafe-inline'"));
+    assert!(!minutes.text().contains("<style"));
+    assert!(!minutes.text().contains("style="));
+
+    let stylesheet_path = minutes
+        .text()
+        .split_once(r#"<link rel="stylesheet" href=""#)
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .map(|(path, _)| path.to_string())
+        .expect("a stylesheet");
+    assert!(stylesheet_path.starts_with('/'));
+    assert!(
+        style_sources
+            .split_whitespace()
+            .any(|source| source == "'self'")
+    );
+
+    let stylesheet = admin.get(&stylesheet_path).await;
+    stylesheet.assert_status(StatusCode::OK);
+    assert!(
+        stylesheet
+            .header("content-type")
+            .is_some_and(|content_type| content_type.starts_with("text/css"))
+    );
+}
+
+async fn nominate_candidates(admin: &mut Client, participants: &mut [Client; 3]) {
+    let election_id = create_election(
+        admin,
+        json!({
+            "name": "Chair",
+            "ballotItems": [],
+            "nominations": { "requiredSeconders": 1 },
+        }),
+    )
+    .await;
+    let [first, second, third] = participants;
+
+    let own_nomination = first
+        .post(
+            &format!("/api/v1/elections/{election_id}/nominations"),
+            json!({ "candidateName": "First", "candidateParticipantId": first.participant_id() }),
+        )
+        .await;
+    own_nomination.assert_status(StatusCode::CREATED);
+    let own_nomination_id = own_nomination.json()["id"].clone();
+
+    let other_nomination = second
+        .post(
+            &format!("/api/v1/elections/{election_id}/nominations"),
+            json!({ "candidateName": "Third", "candidateParticipantId": third.participant_id() }),
+        )
+        .await;
+    other_nomination.assert_status(StatusCode::CREATED);
+    let other_nomination_id = other_nomination.json()["id"].clone();
+
+    for path in [
+        format!("/api/v1/nominations/{own_nomination_id}/second"),
+        format!("/api/v1/nominations/{other_nomination_id}/decline"),
+        format!("/api/v1/nominations/{other_nomination_id}/accept"),
+    ] {
+        let participant = if path.ends_with("second") {
+            &mut *second
+        } else {
+            &mut *third
+        };
+        participant
+            .post_empty(&path)
+            .await
+            .assert_status(StatusCode::OK);
+    }
+
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/nominations/{own_nomination_id}/approve"
+        ))
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/nominations/{other_nomination_id}/reject"
+        ))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let nominations = first
+        .get(&format!("/api/v1/elections/{election_id}/nominations"))
+        .await
+        .ok_json();
+    assert_eq!(nominations.as_array().unwrap().len(), 2);
+
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/open"))
+        .await
+        .assert_status(StatusCode::OK);
+    let election = first
+        .get(&format!("/api/v1/elections/{election_id}"))
+        .await
+        .ok_json();
+    assert_eq!(election["status"]["kind"], "open");
+    assert_eq!(ballot_item_names(&election), ["First"]);
+}
+
+async fn amend_motion(admin: &mut Client, participants: &mut [Client; 3]) {
+    let motion_id = create_election(
+        admin,
+        json!({
+            "name": "Meetings",
+            "ballotItems": [],
+            "motion": { "kind": "main", "text": "We meet on Mondays." },
+        }),
+    )
+    .await;
+    let amendment_id = create_election(
+        admin,
+        json!({
+            "name": "Meetings, amendment 1",
+            "ballotItems": [],
+            "motion": {
+                "kind": "amendment",
+                "parentElectionId": motion_id,
+                "originalText": "Mondays",
+                "replacementText": "Fridays",
+            },
+        }),
+    )
+    .await;
+
+    let motion = participants[0]
+        .get(&format!("/api/v1/elections/{motion_id}"))
+        .await
+        .ok_json();
+    assert_eq!(motion["status"]["kind"], "awaitingAmendments");
+
+    for participant in participants.iter_mut() {
+        participant
+            .post(
+                "/api/v1/votes",
+                json!({ "electionId": amendment_id, "selectedBallotItemId": 0 }),
+            )
+            .await
+            .assert_status(StatusCode::OK);
+    }
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{amendment_id}/close"))
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{motion_id}/open"))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let motion = participants[0]
+        .get(&format!("/api/v1/elections/{motion_id}"))
+        .await
+        .ok_json();
+    assert_eq!(motion["status"]["kind"], "open");
+    assert_eq!(motion["motion"]["consolidatedText"], "We meet on Fridays.");
+    assert_eq!(ballot_item_names(&motion), ["Yes", "No"]);
+}
+
+async fn keep_speakers_list(
+    admin: &mut Client,
+    participants: &mut [Client; 3],
+    agenda_item_id: u64,
+) {
+    let speakers_path = format!("/api/v1/agenda/items/{agenda_item_id}/speakers");
+    let mut request_ids = Vec::new();
+    for (participant, body) in participants.iter_mut().zip([
+        json!({ "stance": "pro" }),
+        json!({}),
+        json!({ "pointOfOrder": true }),
+    ]) {
+        let speaker_request = participant.post(&speakers_path, body).await;
+        speaker_request.assert_status(StatusCode::CREATED);
+        request_ids.push(speaker_request.json()["id"].as_u64().unwrap());
+    }
+    let [pro, neutral, point_of_order] = request_ids[..] else {
+        unreachable!()
+    };
+
+    let speakers_list = participants[0].get(&speakers_path).await.ok_json();
+    assert_eq!(
+        speaker_request_ids(&speakers_list["waiting"]),
+        [point_of_order, pro, neutral]
+    );
+
+    admin
+        .put(
+            &format!("/api/v1/admin/agenda/items/{agenda_item_id}/speakers/order"),
+            json!({ "speakerRequestIds": [pro, point_of_order, neutral] }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    participants[1]
+        .delete(&format!("/api/v1/speaker-requests/{neutral}"))
+        .await
+        .assert_status(StatusCode::OK);
+
+    admin
+        .post(
+            &format!("/api/v1/admin/speaker-requests/{pro}/call"),
+            json!({ "speakingTimeSeconds": 60 }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    let speakers_list = participants[0].get(&speakers_path).await.ok_json();
+    assert_eq!(speakers_list["currentSpeaker"]["id"], pro);
+    assert!(speakers_list["currentSpeaker"]["status"]["speakingDeadline"].is_string());
+
+    admin
+        .post(
+            &format!("/api/v1/admin/speaker-requests/{point_of_order}/call"),
+            json!({}),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/speaker-requests/{point_of_order}/finish"
+        ))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let late_request = participants[1].post(&speakers_path, json!({})).await;
+    late_request.assert_status(StatusCode::CREATED);
+    let late_request_id = late_request.json()["id"].clone();
+    admin
+        .delete(&format!("/api/v1/admin/speaker-requests/{late_request_id}"))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let speakers_list = participants[0].get(&speakers_path).await.ok_json();
+    assert_eq!(speakers_list["currentSpeaker"], Value::Null);
+    assert_eq!(speakers_list["waiting"], json!([]));
+    assert_eq!(
+        speaker_request_ids(&speakers_list["finished"]),
+        [pro, point_of_order]
+    );
+}
+
+async fn manage_participants(admin: &mut Client, participants: &[Client; 3]) {
+    let participant_id = participants[0].participant_id();
+
+    let all_participants = admin.get("/api/v1/admin/participants").await.ok_json();
+    assert_eq!(all_participants.as_array().unwrap().len(), 3);
+
+    admin
+        .put(
+            &format!("/api/v1/admin/participants/{participant_id}/groups"),
+            json!({ "groups": ["delegates"] }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .put(
+            "/api/v1/admin/attendance/settings",
+            json!({ "requireCheckInToVote": true }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/participants/{participant_id}/check-in"
+        ))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let attendance = admin.get("/api/v1/attendance").await.ok_json();
+    assert_eq!(attendance["numRegistered"], 3);
+    assert_eq!(attendance["numPresent"], 1);
+    assert_eq!(attendance["settings"]["requireCheckInToVote"], true);
+
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/participants/{participant_id}/check-out"
+        ))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let all_participants = admin.get("/api/v1/admin/participants").await.ok_json();
+    let participant = all_participants
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|participant| participant["id"] == participant_id)
+        .unwrap();
+    assert_eq!(participant["groups"], json!(["delegates"]));
+    assert_eq!(participant["checkedInSince"], Value::Null);
+    assert_eq!(
+        participant["attendanceHistory"].as_array().unwrap().len(),
+        2
+    );
+}
+
+async fn use_api_token(server: &TestServer, admin: &mut Client) {
+    let created_api_token = admin
+        .post(
+            "/api/v1/admin/api-tokens",
+            json!({ "name": "Projector", "scopes": ["readElections"] }),
+        )
+        .await;
+    created_api_token.assert_status(StatusCode::CREATED);
+    let created_api_token = created_api_token.json();
+
+    let api_tokens = admin.get("/api/v1/admin/api-tokens").await.ok_json();
+    assert_eq!(api_tokens.as_array().unwrap().len(), 1);
+    assert_eq!(api_tokens[0]["name"], "Projector");
+
+    let mut projector = server.client();
+    projector.use_bearer_token(created_api_token["bearerToken"].as_str().unwrap());
+    projector
+        .get("/api/v1/presentation")
+        .await
+        .assert_status(StatusCode::OK);
+
+    admin
+        .delete(&format!(
+            "/api/v1/admin/api-tokens/{}",
+            created_api_token["id"]
+        ))
+        .await
+        .assert_status(StatusCode::OK);
+    let response = projector.get("/api/v1/presentation").await;
+    assert_eq!(response.status, StatusCode::UNAUTHORIZED);
+}
+
+async fn manage_events(server: &TestServer, admin: &mut Client) {
+    let created_event = admin
+        .post(
+            "/api/v1/admin/events",
+            json!({
+                "slug": "regional",
+                "name": "Regional convention",
+                "participantPassword": "regional-participant",
+                "adminPassword": "regional-admin",
+            }),
+        )
+        .await;
+    created_event.assert_status(StatusCode::CREATED);
+
+    let events = admin.get("/api/v1/admin/events").await.ok_json();
+    let slugs: Vec<_> = events
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| event["slug"].as_str().unwrap())
+        .collect();
+    assert_eq!(slugs, ["main", "regional"]);
+
+    let mut regional = server.client_for_event("regional");
+    let event = regional.get("/api/v1/event").await.ok_json();
+    assert_eq!(event["name"], "Regional convention");
+    regional.log_in_as_participant("regional-participant").await;
+    let elections = regional.get("/api/v1/elections").await.ok_json();
+    assert_eq!(elections["sessions"], json!([]));
+
+    let metrics = admin.get("/metrics").await;
+    metrics.assert_status(StatusCode::OK);
+    let metrics = metrics.text();
+    assert!(metrics.contains("coco_votes_accepted_total"));
+    assert!(metrics.contains("coco_state_authority_queue_depth{event=\"regional\"}"));
+}
+
+fn ballot_item_names(election: &Value) -> Vec<&str> {
+    let ballot_items_by_id = election["ballotItemsById"].as_object().unwrap();
+    (0..ballot_items_by_id.len())
+        .map(|id| {
+            ballot_items_by_id[&id.to_string()]["name"]
+                .as_str()
+                .unwrap()
+        })
+        .collect()
+}
+
+fn speaker_request_ids(speaker_requests: &Value) -> Vec<u64> {
+    speaker_requests
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|speaker_request| speaker_request["id"].as_u64().unwrap())
+        .collect()
+}
+
+fn assert_every_documented_route_was_requested(openapi: &Value, server: &TestServer) {
+    let seen_requests = server.seen_requests();
+
+    let mut missing_routes = Vec::new();
+    for (pattern, operations) in openapi["paths"].as_object().unwrap() {
+        for method in operations.as_object().unwrap().keys() {
+            let method = method.to_uppercase();
+            let was_requested = seen_requests.iter().any(|(seen_method, seen_path)| {
+                *seen_method == method && path_matches(pattern, seen_path)
+            });
+            if !was_requested {
+                missing_routes.push(format!("{method} {pattern}"));
+            }
+        }
+    }
+
+    assert!(
+        missing_routes.is_empty(),
+        "routes without a request: {missing_routes:?}"
+    );
+}
+
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern_segments: Vec<_> = pattern.split('/').collect();
+    let path_segments: Vec<_> = path.split('/').collect();
+
+    pattern_segments.len() == path_segments.len()
+        && pattern_segments
+            .iter()
+            .zip(&path_segments)
+            .all(|(pattern_segment, path_segment)| {
+                pattern_segment.starts_with('{') || pattern_segment == path_segment
+            })
+}
@@ -0,0 +1,159 @@
+//! Checks that turnout stays within 100 % and the quorum is judged by the present participants
+//! while they come and go.
+
+mod common;
+
+use hyper::StatusCode;
+use serde_json::json;
+
+use common::{Client, TestServer, create_election};
+
+async fn change_attendance(admin: &mut Client, participant: &Client, kind: &str) {
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/participants/{}/{kind}",
+            participant.participant_id()
+        ))
+        .await
+        .assert_status(StatusCode::OK);
+}
+
+#[tokio::test]
+async fn turnout_counts_participants_who_voted_and_left() {
+    let server = TestServer::start().await;
+    let mut admin = server.admin().await;
+    let mut staying = server.participant().await;
+    let mut leaving = server.participant().await;
+    let _absent = server.participant().await;
+
+    let election_id = create_election(
+        &mut admin,
+        json!({ "name": "Board", "ballotItems": ["Alice", "Bob"] }),
+    )
+    .await;
+
+    // Without required check-in, a stray check-in does not change who counts.
+    change_attendance(&mut admin, &staying, "check-in").await;
+    let presentation = admin.get("/api/v1/presentation").await.ok_json();
+    assert_eq!(presentation["election"]["numEligibleParticipants"], 3);
+
+    admin
+        .put(
+            "/api/v1/admin/attendance/settings",
+            json!({ "requireCheckInToVote": true }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    change_attendance(&mut admin, &leaving, "check-in").await;
+
+    for participant in [&mut staying, &mut leaving] {
+        participant
+            .post(
+                "/api/v1/votes",
+                json!({ "electionId": election_id, "selectedBallotItemId": 0 }),
+            )
+            .await
+            .assert_status(StatusCode::OK);
+    }
+
+    let presentation = admin.get("/api/v1/presentation").await.ok_json();
+    assert_eq!(presentation["election"]["numVotesCast"], 2);
+    assert_eq!(presentation["election"]["numEligibleParticipants"], 2);
+
+    change_attendance(&mut admin, &leaving, "check-out").await;
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/close"))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let results_export = admin
+        .get("/api/v1/admin/exports/results.json")
+        .await
+        .ok_json();
+    let election = &results_export["elections"][0];
+    assert_eq!(election["numEligibleParticipants"], 2);
+    assert_eq!(election["turnoutPercent"], 100.0);
+
+    let attendance = admin.get("/api/v1/attendance").await.ok_json();
+    assert_eq!(attendance["numRegistered"], 3);
+    assert_eq!(attendance["numEligible"], 1);
+}
+
+#[tokio::test]
+async fn quorum_is_checked_against_present_participants() {
+    let server = TestServer::start().await;
+    let mut admin = server.admin().await;
+    let mut first = server.participant().await;
+    let second = server.participant().await;
+
+    admin
+        .put(
+            "/api/v1/admin/attendance/settings",
+            json!({ "requireCheckInToVote": true, "quorum": 2 }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    change_attendance(&mut admin, &first, "check-in").await;
+
+    let response = admin
+        .post(
+            "/api/v1/admin/elections",
+            json!({ "name": "Budget", "ballotItems": ["Yes", "No"] }),
+        )
+        .await;
+    response.assert_error(StatusCode::CONFLICT, "QUORUM_NOT_MET");
+    assert_eq!(
+        response.json()["details"],
+        json!({ "quorum": 2, "numPresent": 1 })
+    );
+
+    // A main motion waits for its amendments, so the quorum is checked when it is opened.
+    let election_id = create_election(
+        &mut admin,
+        json!({
+            "name": "Budget",
+            "ballotItems": [],
+            "motion": { "kind": "main", "text": "We spend less." },
+        }),
+    )
+    .await;
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/open"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "QUORUM_NOT_MET");
+
+    change_attendance(&mut admin, &second, "check-in").await;
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/open"))
+        .await
+        .assert_status(StatusCode::OK);
+    first
+        .post(
+            "/api/v1/votes",
+            json!({ "electionId": election_id, "selectedBallotItemId": 0 }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+
+    // Leaving during the voting does not block the close, but is reported.
+    change_attendance(&mut admin, &second, "check-out").await;
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/close"))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let quorum_check = json!({ "quorum": 2, "numPresent": 1, "isMet": false });
+    let results = admin
+        .get(&format!("/api/v1/elections/{election_id}/results"))
+        .await
+        .ok_json();
+    assert_eq!(results["quorumCheck"], quorum_check);
+    let results_export = admin
+        .get("/api/v1/admin/exports/results.json")
+        .await
+        .ok_json();
+    assert_eq!(results_export["elections"][0]["quorumCheck"], quorum_check);
+
+    let minutes = admin.get("/api/v1/admin/exports/minutes.html").await.text();
+    assert!(minutes.contains("<dt>Quorum</dt><dd>1 of 2 present, not met</dd>"));
+}
@@ -0,0 +1,404 @@
+//! Boots the server on an ephemeral port and talks to it over real HTTP.
+
+#![allow(dead_code)]
+
+use std::{
+    collections::BTreeSet,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use coco::event::{AdminCreateEventBody, DEFAULT_EVENT_SLUG, Events};
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    HeaderMap, Method, Request, StatusCode,
+    body::Bytes,
+    header::{AUTHORIZATION, CONTENT_TYPE, COOKIE, HOST, SET_COOKIE},
+};
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use tokio::{net::TcpListener, net::TcpStream, task::JoinHandle};
+
+pub const PARTICIPANT_PASSWORD: &str = "participant-password";
+pub const ADMIN_PASSWORD: &str = "admin-password";
+pub const BASE_DOMAIN: &str = "localhost";
+
+/// What the clients of one server have seen, to check that a test covers everything.
+#[derive(Default)]
+struct Seen {
+    /// Method and concrete path of every request.
+    requests: BTreeSet<(String, String)>,
+    error_codes: BTreeSet<String>,
+}
+
+pub struct TestServer {
+    address: SocketAddr,
+    task: JoinHandle<()>,
+    seen: Arc<Mutex<Seen>>,
+}
+
+impl TestServer {
+    /// Starts a server whose default event has no demo data.
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("binding an ephemeral port");
+        let address = listener.local_addr().expect("reading the bound address");
+
+        let (events, state_authority_panic_receiver) = Events::new(
+            BASE_DOMAIN,
+            AdminCreateEventBody {
+                slug: String::from(DEFAULT_EVENT_SLUG),
+                name: String::from("Test convention"),
+                participant_password: String::from(PARTICIPANT_PASSWORD),
+                admin_password: String::from(ADMIN_PASSWORD),
+            },
+            false,
+        );
+
+        let task = tokio::spawn(async move {
+            if let Err(e) = coco::serve(listener, events, state_authority_panic_receiver).await {
+                panic!("The server stopped: {e}");
+            }
+        });
+
+        Self {
+            address,
+            task,
+            seen: Arc::default(),
+        }
+    }
+
+    /// A client without credentials for the default event.
+    pub fn client(&self) -> Client {
+        self.client_for_host(BASE_DOMAIN)
+    }
+
+    /// A client without credentials for the event served at `<slug>.<base domain>`.
+    pub fn client_for_event(&self, slug: &str) -> Client {
+        self.client_for_host(&format!("{slug}.{BASE_DOMAIN}"))
+    }
+
+    fn client_for_host(&self, host: &str) -> Client {
+        Client {
+            address: self.address,
+            host: format!("{host}:{}", self.address.port()),
+            cookies: Vec::new(),
+            bearer_token: None,
+            seen: Arc::clone(&self.seen),
+        }
+    }
+
+    /// A client logged in as a new participant of the default event.
+    pub async fn participant(&self) -> Client {
+        let mut client = self.client();
+        client.log_in_as_participant(PARTICIPANT_PASSWORD).await;
+        client
+    }
+
+    /// A client logged in as the initial super admin of the default event.
+    pub async fn admin(&self) -> Client {
+        let mut client = self.client();
+        client.log_in_as_admin("admin", ADMIN_PASSWORD).await;
+        client
+    }
+
+    pub fn seen_requests(&self) -> BTreeSet<(String, String)> {
+        self.seen.lock().unwrap().requests.clone()
+    }
+
+    pub fn seen_error_codes(&self) -> BTreeSet<String> {
+        self.seen.lock().unwrap().error_codes.clone()
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Keeps the cookies set by the server, like a browser would.
+pub struct Client {
+    address: SocketAddr,
+    host: String,
+    cookies: Vec<(String, String)>,
+    bearer_token: Option<String>,
+    seen: Arc<Mutex<Seen>>,
+}
+
+impl Client {
+    pub async fn log_in_as_participant(&mut self, password: &str) {
+        self.post(
+            "/api/v1/participants",
+            serde_json::json!({ "password": password }),
+        )
+        .await
+        .assert_status(StatusCode::CREATED);
+    }
+
+    pub async fn log_in_as_admin(&mut self, username: &str, password: &str) {
+        self.post(
+            "/api/v1/admin/sessions",
+            serde_json::json!({ "username": username, "password": password }),
+        )
+        .await
+        .assert_status(StatusCode::CREATED);
+    }
+
+    /// Sends `Authorization: Bearer <token>` with every request from now on.
+    pub fn use_bearer_token(&mut self, bearer_token: &str) {
+        self.bearer_token = Some(bearer_token.to_string());
+    }
+
+    pub fn cookie(&self, key: &str) -> Option<&str> {
+        self.cookies
+            .iter()
+            .find(|(cookie_key, _)| cookie_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_cookie(&mut self, key: &str, value: &str) {
+        self.cookies.retain(|(cookie_key, _)| cookie_key != key);
+        self.cookies.push((key.to_string(), value.to_string()));
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The `Host` header this client sends.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Records an error code seen outside of [`Client::send`], e.g. over a raw connection.
+    pub fn record_error_code(&self, error_code: &str) {
+        self.seen
+            .lock()
+            .unwrap()
+            .error_codes
+            .insert(error_code.to_string());
+    }
+
+    /// The id of the participant this client is logged in as.
+    pub fn participant_id(&self) -> usize {
+        self.cookie("coco_participant_id")
+            .and_then(|id| id.parse().ok())
+            .expect("the client is logged in as a participant")
+    }
+
+    pub async fn get(&mut self, path: &str) -> TestResponse {
+        self.send(Method::GET, path, None, HeaderMap::new()).await
+    }
+
+    pub async fn delete(&mut self, path: &str) -> TestResponse {
+        self.send(Method::DELETE, path, None, HeaderMap::new())
+            .await
+    }
+
+    pub async fn post(&mut self, path: &str, body: Value) -> TestResponse {
+        self.send_json(Method::POST, path, body).await
+    }
+
+    pub async fn put(&mut self, path: &str, body: Value) -> TestResponse {
+        self.send_json(Method::PUT, path, body).await
+    }
+
+    /// A `POST` without a body.
+    pub async fn post_empty(&mut self, path: &str) -> TestResponse {
+        self.send(Method::POST, path, None, HeaderMap::new()).await
+    }
+
+    async fn send_json(&mut self, method: Method, path: &str, body: Value) -> TestResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+        self.send(method, path, Some(Bytes::from(body.to_string())), headers)
+            .await
+    }
+
+    /// Sends `body` as is, e.g. an attachment or malformed JSON.
+    pub async fn post_raw(
+        &mut self,
+        path: &str,
+        content_type: &str,
+        body: impl Into<Bytes>,
+    ) -> TestResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, content_type.parse().unwrap());
+        self.send(Method::POST, path, Some(body.into()), headers)
+            .await
+    }
+
+    pub async fn send(
+        &mut self,
+        method: Method,
+        path: &str,
+        body: Option<Bytes>,
+        headers: HeaderMap,
+    ) -> TestResponse {
+        let stream = TcpStream::connect(self.address)
+            .await
+            .expect("connecting to the test server");
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .expect("HTTP handshake with the test server");
+        tokio::spawn(connection);
+
+        let mut request_builder = Request::builder()
+            .method(method.clone())
+            .uri(path)
+            .header(HOST, &self.host);
+        for (name, value) in &headers {
+            request_builder = request_builder.header(name, value);
+        }
+        if !self.cookies.is_empty() {
+            let cookie = self
+                .cookies
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join("; ");
+            request_builder = request_builder.header(COOKIE, cookie);
+        }
+        if let Some(bearer_token) = &self.bearer_token {
+            request_builder =
+                request_builder.header(AUTHORIZATION, format!("Bearer {bearer_token}"));
+        }
+        let request = request_builder
+            .body(Full::new(body.unwrap_or_default()))
+            .expect("building the request");
+
+        let response = sender
+            .send_request(request)
+            .await
+            .expect("sending the request");
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .expect("reading the response body")
+            .to_bytes();
+
+        for set_cookie in headers.get_all(SET_COOKIE) {
+            let set_cookie = set_cookie.to_str().expect("ASCII cookie");
+            let key_value = set_cookie.split(';').next().unwrap_or_default();
+            if let Some((key, value)) = key_value.split_once('=') {
+                self.set_cookie(key.trim(), value.trim());
+            }
+        }
+
+        let response = TestResponse {
+            status,
+            headers,
+            body,
+        };
+
+        let mut seen = self.seen.lock().unwrap();
+        seen.requests.insert((
+            method.to_string(),
+            path.split('?').next().unwrap_or(path).to_string(),
+        ));
+        if let Some(error_code) = response.try_error_code() {
+            seen.error_codes.insert(error_code);
+        }
+        drop(seen);
+
+        response
+    }
+}
+
+#[derive(Debug)]
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl TestResponse {
+    #[track_caller]
+    pub fn assert_status(&self, status: StatusCode) -> &Self {
+        assert_eq!(self.status, status, "unexpected response: {}", self.text());
+        self
+    }
+
+    /// Asserts a successful response and returns its JSON body.
+    #[track_caller]
+    pub fn ok_json(&self) -> Value {
+        assert!(
+            self.status.is_success(),
+            "unexpected response {}: {}",
+            self.status,
+            self.text()
+        );
+        self.json()
+    }
+
+    #[track_caller]
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body)
+            .unwrap_or_else(|e| panic!("response is not JSON ({e}): {}", self.text()))
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Asserts that the response is an API error with the given status and code.
+    #[track_caller]
+    pub fn assert_error(&self, status: StatusCode, error_code: &str) {
+        assert_eq!(
+            (self.status, self.try_error_code().as_deref()),
+            (status, Some(error_code)),
+            "unexpected response: {}",
+            self.text()
+        );
+    }
+
+    fn try_error_code(&self) -> Option<String> {
+        if self.status.is_success() {
+            return None;
+        }
+        let body: Value = serde_json::from_slice(&self.body).ok()?;
+        body.get("code")?.as_str().map(String::from)
+    }
+}
+
+/// Looks up the id of the election with the given name, wherever it is on the agenda.
+pub async fn election_id(client: &mut Client, name: &str) -> usize {
+    let elections_by_agenda = client.get("/api/v1/elections").await.ok_json();
+
+    let scheduled = elections_by_agenda["sessions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|session| session["agendaItems"].as_array().into_iter().flatten())
+        .flat_map(|agenda_item| agenda_item["elections"].as_array().into_iter().flatten());
+    let unscheduled = elections_by_agenda["unscheduledElections"]
+        .as_array()
+        .into_iter()
+        .flatten();
+
+    scheduled
+        .chain(unscheduled)
+        .find(|election| election["name"] == name)
+        .and_then(|election| election["id"].as_u64())
+        .unwrap_or_else(|| panic!("no election named {name}")) as usize
+}
+
+/// Creates an election with the given body and returns its id.
+pub async fn create_election(admin: &mut Client, body: Value) -> usize {
+    let name = body["name"].as_str().expect("election name").to_string();
+    admin
+        .post("/api/v1/admin/elections", body)
+        .await
+        .assert_status(StatusCode::OK);
+
+    election_id(admin, &name).await
+}
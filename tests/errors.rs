@@ -0,0 +1,780 @@
+//! Provokes every error the API can answer with, grouped by the error enum in `error.rs`.
+
+mod common;
+
+use std::{collections::BTreeSet, time::Duration};
+
+use hyper::StatusCode;
+use serde_json::json;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::timeout,
+};
+
+use common::{Client, TestServer, create_election};
+
+/// Codes that no request can provoke while the server works as intended.
+const UNREACHABLE_ERROR_CODES: [&str; 1] = ["INTERNAL_ERROR"];
+
+const MISSING_ID: usize = 999;
+
+#[tokio::test]
+async fn every_error_code_is_answered() {
+    let server = TestServer::start().await;
+    let mut anonymous = server.client();
+    let mut admin = server.admin().await;
+
+    router_errors(&mut anonymous).await;
+    json_body_errors(&mut anonymous).await;
+    invalid_credentials_errors(&server, &mut admin).await;
+    event_errors(&server, &mut admin).await;
+    admin_account_errors(&mut admin).await;
+    agenda_errors(&mut admin).await;
+    elections_create_errors(&mut admin).await;
+    elections_vote_and_manage_errors(&server, &mut admin).await;
+    participants_manage_errors(&mut admin).await;
+    attendance_errors(&server, &mut admin).await;
+    nomination_errors(&server, &mut admin).await;
+    attachment_errors(&server, &mut admin).await;
+    api_token_errors(&mut admin).await;
+    speakers_errors(&server, &mut admin).await;
+
+    let openapi = anonymous.get("/api/v1/openapi.json").await.ok_json();
+    let documented_error_codes: BTreeSet<_> = openapi["components"]["schemas"]["ErrorCode"]["enum"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|error_code| error_code.as_str().unwrap().to_string())
+        .filter(|error_code| !UNREACHABLE_ERROR_CODES.contains(&error_code.as_str()))
+        .collect();
+    let missing_error_codes: Vec<_> = documented_error_codes
+        .difference(&server.seen_error_codes())
+        .cloned()
+        .collect();
+    assert!(
+        missing_error_codes.is_empty(),
+        "error codes that no request provoked: {missing_error_codes:?}"
+    );
+}
+
+async fn router_errors(anonymous: &mut Client) {
+    anonymous
+        .get("/does-not-exist")
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "NOT_FOUND");
+
+    let response = anonymous.delete("/api/v1/event").await;
+    response.assert_error(StatusCode::METHOD_NOT_ALLOWED, "METHOD_NOT_ALLOWED");
+    assert_eq!(response.json()["details"]["allowedMethods"], json!(["GET"]));
+}
+
+async fn json_body_errors(anonymous: &mut Client) {
+    let response = anonymous
+        .post(
+            "/api/v1/participants",
+            json!({ "password": "x".repeat(2000) }),
+        )
+        .await;
+    response.assert_error(StatusCode::PAYLOAD_TOO_LARGE, "BODY_TOO_LARGE");
+    assert_eq!(response.json()["details"]["limitBytes"], 1024);
+
+    let response = anonymous
+        .post_raw("/api/v1/participants", "application/json", "{\"password\":")
+        .await;
+    response.assert_error(StatusCode::BAD_REQUEST, "BODY_MALFORMED");
+    assert!(response.json()["details"]["column"].is_number());
+
+    // The body ends before the announced length, so it cannot be read completely.
+    let response = send_truncated_body(anonymous).await;
+    assert!(
+        response.starts_with("HTTP/1.1 400"),
+        "unexpected response: {response}"
+    );
+    assert!(response.contains("BODY_UNREADABLE"));
+    anonymous.record_error_code("BODY_UNREADABLE");
+}
+
+async fn invalid_credentials_errors(server: &TestServer, admin: &mut Client) {
+    let mut anonymous = server.client();
+    anonymous
+        .get("/api/v1/elections")
+        .await
+        .assert_error(StatusCode::UNAUTHORIZED, "NOT_LOGGED_IN");
+    anonymous
+        .post("/api/v1/participants", json!({ "password": "wrong" }))
+        .await
+        .assert_error(StatusCode::UNAUTHORIZED, "WRONG_PASSWORD");
+    anonymous
+        .post(
+            "/api/v1/admin/sessions",
+            json!({ "username": "admin", "password": "wrong" }),
+        )
+        .await
+        .assert_error(StatusCode::UNAUTHORIZED, "WRONG_PASSWORD");
+
+    let mut participant = server.participant().await;
+    participant.set_cookie("coco_token", "wrong");
+    participant
+        .get("/api/v1/participants/me/votes")
+        .await
+        .assert_error(StatusCode::UNAUTHORIZED, "WRONG_TOKEN");
+
+    let mut participant = server.participant().await;
+    participant
+        .get("/api/v1/admin/participants")
+        .await
+        .assert_error(StatusCode::UNAUTHORIZED, "NOT_LOGGED_IN");
+
+    admin
+        .post(
+            "/api/v1/admin/accounts",
+            json!({ "username": "observer", "password": "observer", "role": "observer" }),
+        )
+        .await
+        .assert_status(StatusCode::CREATED);
+    let mut observer = server.client();
+    observer.log_in_as_admin("observer", "observer").await;
+    observer
+        .post(
+            "/api/v1/admin/elections",
+            json!({ "name": "Not allowed", "ballotItems": ["Yes"] }),
+        )
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "INSUFFICIENT_ROLE");
+
+    let created_api_token = admin
+        .post(
+            "/api/v1/admin/api-tokens",
+            json!({ "name": "Read only", "scopes": ["readElections"] }),
+        )
+        .await
+        .ok_json();
+    let mut machine = server.client();
+    machine.use_bearer_token(created_api_token["bearerToken"].as_str().unwrap());
+    machine
+        .get("/api/v1/admin/exports/results.json")
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "INSUFFICIENT_SCOPE");
+}
+
+async fn event_errors(server: &TestServer, admin: &mut Client) {
+    let mut missing_event = server.client_for_event("missing");
+    missing_event
+        .get("/api/v1/event")
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "EVENT_NOT_FOUND");
+
+    // Probes and scrapers do not know about events.
+    for path in ["/healthz", "/readyz", "/metrics"] {
+        missing_event.get(path).await.assert_status(StatusCode::OK);
+    }
+
+    let event_body = |slug: &str| {
+        json!({
+            "slug": slug,
+            "name": "Other",
+            "participantPassword": "other-participant",
+            "adminPassword": "other-admin",
+        })
+    };
+    admin
+        .post("/api/v1/admin/events", event_body("Not A Slug"))
+        .await
+        .assert_error(StatusCode::BAD_REQUEST, "INVALID_EVENT_SLUG");
+    admin
+        .post("/api/v1/admin/events", event_body("main"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "EVENT_SLUG_TAKEN");
+    admin
+        .post("/api/v1/admin/events", event_body("other"))
+        .await
+        .assert_status(StatusCode::CREATED);
+
+    let mut other_admin = server.client_for_event("other");
+    other_admin.log_in_as_admin("admin", "other-admin").await;
+    other_admin
+        .get("/api/v1/admin/events")
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "NOT_DEFAULT_EVENT");
+}
+
+async fn admin_account_errors(admin: &mut Client) {
+    admin
+        .delete(&format!("/api/v1/admin/accounts/{MISSING_ID}"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "ADMIN_ACCOUNT_NOT_FOUND");
+    admin
+        .post(
+            "/api/v1/admin/accounts",
+            json!({ "username": "admin", "password": "again", "role": "observer" }),
+        )
+        .await
+        .assert_error(StatusCode::CONFLICT, "ADMIN_USERNAME_TAKEN");
+
+    let own_account = admin.get("/api/v1/admin/accounts/me").await.ok_json();
+    admin
+        .delete(&format!("/api/v1/admin/accounts/{}", own_account["id"]))
+        .await
+        .assert_error(StatusCode::CONFLICT, "LAST_SUPER_ADMIN");
+}
+
+async fn agenda_errors(admin: &mut Client) {
+    admin
+        .post(
+            "/api/v1/admin/agenda/items",
+            json!({ "sessionId": MISSING_ID, "number": "1", "title": "Lost" }),
+        )
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "AGENDA_SESSION_NOT_FOUND");
+
+    let session = admin
+        .post("/api/v1/admin/agenda/sessions", json!({ "title": "Day 1" }))
+        .await
+        .ok_json();
+    admin
+        .post(
+            "/api/v1/admin/agenda/items",
+            json!({
+                "sessionId": session["id"],
+                "number": "1",
+                "title": "Documents",
+                "documents": [{ "title": "Script", "url": "javascript:alert(1)" }],
+            }),
+        )
+        .await
+        .assert_error(StatusCode::BAD_REQUEST, "INVALID_DOCUMENT_URL");
+}
+
+async fn elections_create_errors(admin: &mut Client) {
+    let mut create = async |body| admin.post("/api/v1/admin/elections", body).await;
+
+    create(json!({ "name": "Lost", "ballotItems": ["Yes"], "agendaItemId": MISSING_ID }))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "AGENDA_ITEM_NOT_FOUND");
+    create(json!({
+        "name": "Bad group",
+        "ballotItems": ["Yes"],
+        "eligibility": { "kind": "groups", "groups": [" padded "] },
+    }))
+    .await
+    .assert_error(StatusCode::BAD_REQUEST, "INVALID_GROUP_NAME");
+    create(json!({
+        "name": "Nobody",
+        "ballotItems": ["Yes"],
+        "eligibility": { "kind": "groups", "groups": [] },
+    }))
+    .await
+    .assert_error(StatusCode::BAD_REQUEST, "NO_ELIGIBLE_GROUPS");
+    create(json!({
+        "name": "Missing attachment",
+        "ballotItems": [{ "name": "Alice", "attachmentIds": [MISSING_ID] }],
+    }))
+    .await
+    .assert_error(StatusCode::NOT_FOUND, "ATTACHMENT_NOT_FOUND");
+    create(json!({
+        "name": "Nominated motion",
+        "ballotItems": [],
+        "nominations": {},
+        "motion": { "kind": "main", "text": "We nominate motions." },
+    }))
+    .await
+    .assert_error(StatusCode::BAD_REQUEST, "MOTION_WITH_NOMINATIONS");
+
+    let amendment = |parent_election_id: usize, original_text: &str| {
+        json!({
+            "name": format!("Amendment to {parent_election_id}"),
+            "ballotItems": [],
+            "motion": {
+                "kind": "amendment",
+                "parentElectionId": parent_election_id,
+                "originalText": original_text,
+                "replacementText": "evenings",
+            },
+        })
+    };
+    create(amendment(MISSING_ID, "mornings"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "PARENT_MOTION_NOT_FOUND");
+
+    let motion_id = create_election(
+        admin,
+        json!({
+            "name": "Meetings",
+            "ballotItems": [],
+            "motion": { "kind": "main", "text": "We meet in the mornings." },
+        }),
+    )
+    .await;
+    let mut create = async |body| admin.post("/api/v1/admin/elections", body).await;
+    create(amendment(motion_id, "afternoons"))
+        .await
+        .assert_error(StatusCode::BAD_REQUEST, "AMENDED_TEXT_NOT_FOUND");
+    create(amendment(motion_id, "mornings"))
+        .await
+        .assert_status(StatusCode::OK);
+
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{motion_id}/open"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "AMENDMENTS_NOT_CLOSED");
+
+    let amendment_id = common::election_id(admin, &format!("Amendment to {motion_id}")).await;
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{amendment_id}/close"))
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{motion_id}/open"))
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post("/api/v1/admin/elections", amendment(motion_id, "mornings"))
+        .await
+        .assert_error(
+            StatusCode::CONFLICT,
+            "PARENT_MOTION_NOT_AWAITING_AMENDMENTS",
+        );
+}
+
+async fn elections_vote_and_manage_errors(server: &TestServer, admin: &mut Client) {
+    let mut participant = server.participant().await;
+    let election_id = create_election(
+        admin,
+        json!({ "name": "Lunch", "ballotItems": ["Pizza", "Pasta"] }),
+    )
+    .await;
+    let vote = |election_id: usize, selected_ballot_item_id: usize| json!({ "electionId": election_id, "selectedBallotItemId": selected_ballot_item_id });
+
+    participant
+        .get(&format!("/api/v1/elections/{MISSING_ID}"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "ELECTION_NOT_FOUND");
+    participant
+        .post("/api/v1/votes", vote(MISSING_ID, 0))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "ELECTION_NOT_FOUND");
+    participant
+        .post("/api/v1/votes", vote(election_id, MISSING_ID))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "BALLOT_ITEM_NOT_FOUND");
+    participant
+        .post("/api/v1/votes", vote(election_id, 0))
+        .await
+        .assert_status(StatusCode::OK);
+    participant
+        .post("/api/v1/votes", vote(election_id, 1))
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "ALREADY_VOTED");
+
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/publish"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "ELECTION_NOT_CLOSED");
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/open"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "ELECTION_NOT_NOMINATING");
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{MISSING_ID}/close"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "ELECTION_NOT_FOUND");
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/close"))
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/close"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "ELECTION_ALREADY_CLOSED");
+
+    let mut late_participant = server.participant().await;
+    late_participant
+        .post("/api/v1/votes", vote(election_id, 0))
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "ELECTION_CLOSED");
+
+    let nominating_id = create_election(
+        admin,
+        json!({ "name": "Treasurer", "ballotItems": [], "nominations": {} }),
+    )
+    .await;
+    participant
+        .post("/api/v1/votes", vote(nominating_id, 0))
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "ELECTION_NOT_YET_OPEN");
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{nominating_id}/close"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "ELECTION_NOT_YET_OPEN");
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{nominating_id}/open"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "NO_CANDIDATES");
+
+    let restricted_id = create_election(
+        admin,
+        json!({
+            "name": "Delegates only",
+            "ballotItems": ["Yes", "No"],
+            "eligibility": { "kind": "groups", "groups": ["delegates"] },
+        }),
+    )
+    .await;
+    participant
+        .post("/api/v1/votes", vote(restricted_id, 0))
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "NOT_ELIGIBLE");
+    participant
+        .get(&format!("/api/v1/elections/{restricted_id}"))
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "NOT_ELIGIBLE");
+    admin
+        .get(&format!("/api/v1/elections/{restricted_id}"))
+        .await
+        .assert_status(StatusCode::OK);
+}
+
+async fn participants_manage_errors(admin: &mut Client) {
+    admin
+        .put(
+            &format!("/api/v1/admin/participants/{MISSING_ID}/groups"),
+            json!({ "groups": ["delegates"] }),
+        )
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "PARTICIPANT_NOT_FOUND");
+
+    let participants = admin.get("/api/v1/admin/participants").await.ok_json();
+    let participant_id = &participants[0]["id"];
+    admin
+        .put(
+            &format!("/api/v1/admin/participants/{participant_id}/groups"),
+            json!({ "groups": [""] }),
+        )
+        .await
+        .assert_error(StatusCode::BAD_REQUEST, "INVALID_GROUP_NAME");
+}
+
+async fn attendance_errors(server: &TestServer, admin: &mut Client) {
+    let mut participant = server.participant().await;
+    let participant_id = participant.participant_id();
+
+    admin
+        .post_empty(&format!("/api/v1/admin/participants/{MISSING_ID}/check-in"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "PARTICIPANT_NOT_FOUND");
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/participants/{participant_id}/check-out"
+        ))
+        .await
+        .assert_error(StatusCode::CONFLICT, "ALREADY_CHECKED_OUT");
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/participants/{participant_id}/check-in"
+        ))
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/participants/{participant_id}/check-in"
+        ))
+        .await
+        .assert_error(StatusCode::CONFLICT, "ALREADY_CHECKED_IN");
+
+    admin
+        .put(
+            "/api/v1/admin/attendance/settings",
+            json!({ "requireCheckInToVote": true }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    let election_id = create_election(
+        admin,
+        json!({ "name": "Checked in only", "ballotItems": ["Yes", "No"] }),
+    )
+    .await;
+    let mut absent_participant = server.participant().await;
+    absent_participant
+        .post(
+            "/api/v1/votes",
+            json!({ "electionId": election_id, "selectedBallotItemId": 0 }),
+        )
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "NOT_CHECKED_IN");
+    participant
+        .post(
+            "/api/v1/votes",
+            json!({ "electionId": election_id, "selectedBallotItemId": 0 }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+
+    admin
+        .put(
+            "/api/v1/admin/attendance/settings",
+            json!({ "requireCheckInToVote": true, "quorum": 2 }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post(
+            "/api/v1/admin/elections",
+            json!({ "name": "Without quorum", "ballotItems": ["Yes", "No"] }),
+        )
+        .await
+        .assert_error(StatusCode::CONFLICT, "QUORUM_NOT_MET");
+    admin
+        .put(
+            "/api/v1/admin/attendance/settings",
+            json!({ "requireCheckInToVote": false }),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+}
+
+async fn nomination_errors(server: &TestServer, admin: &mut Client) {
+    let mut nominator = server.participant().await;
+    let mut candidate = server.participant().await;
+    let candidate_id = candidate.participant_id();
+    let election_id = create_election(
+        admin,
+        json!({ "name": "Secretary", "ballotItems": [], "nominations": {} }),
+    )
+    .await;
+    let nominations_path = format!("/api/v1/elections/{election_id}/nominations");
+    let nominate = |candidate_name: &str, candidate_participant_id: usize| json!({ "candidateName": candidate_name, "candidateParticipantId": candidate_participant_id });
+
+    nominator
+        .get(&format!("/api/v1/elections/{MISSING_ID}/nominations"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "ELECTION_NOT_FOUND");
+    nominator
+        .post(&nominations_path, nominate("  ", candidate_id))
+        .await
+        .assert_error(StatusCode::BAD_REQUEST, "EMPTY_CANDIDATE_NAME");
+    nominator
+        .post(&nominations_path, nominate("Nobody", MISSING_ID))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "PARTICIPANT_NOT_FOUND");
+
+    let nomination = nominator
+        .post(&nominations_path, nominate("Candidate", candidate_id))
+        .await
+        .ok_json();
+    let nomination_id = &nomination["id"];
+    nominator
+        .post(&nominations_path, nominate("Candidate again", candidate_id))
+        .await
+        .assert_error(StatusCode::CONFLICT, "CANDIDATE_ALREADY_NOMINATED");
+
+    nominator
+        .post_empty(&format!("/api/v1/nominations/{MISSING_ID}/second"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "NOMINATION_NOT_FOUND");
+    nominator
+        .post_empty(&format!("/api/v1/nominations/{nomination_id}/second"))
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "CANNOT_SECOND_OWN_NOMINATION");
+    candidate
+        .post_empty(&format!("/api/v1/nominations/{nomination_id}/second"))
+        .await
+        .assert_status(StatusCode::OK);
+    candidate
+        .post_empty(&format!("/api/v1/nominations/{nomination_id}/second"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "ALREADY_SECONDED");
+    nominator
+        .post_empty(&format!("/api/v1/nominations/{nomination_id}/accept"))
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "NOT_CANDIDATE");
+    admin
+        .post_empty(&format!("/api/v1/admin/nominations/{MISSING_ID}/approve"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "NOMINATION_NOT_FOUND");
+
+    let restricted_id = create_election(
+        admin,
+        json!({
+            "name": "Delegates nominate",
+            "ballotItems": [],
+            "nominations": {},
+            "eligibility": { "kind": "groups", "groups": ["delegates"] },
+        }),
+    )
+    .await;
+    nominator
+        .post(
+            &format!("/api/v1/elections/{restricted_id}/nominations"),
+            nominate("Candidate", candidate_id),
+        )
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "NOT_ELIGIBLE");
+
+    candidate
+        .post_empty(&format!("/api/v1/nominations/{nomination_id}/accept"))
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/nominations/{nomination_id}/approve"
+        ))
+        .await
+        .assert_status(StatusCode::OK);
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/open"))
+        .await
+        .assert_status(StatusCode::OK);
+    nominator
+        .post(
+            &nominations_path,
+            nominate("Too late", nominator.participant_id()),
+        )
+        .await
+        .assert_error(StatusCode::CONFLICT, "ELECTION_NOT_NOMINATING");
+    admin
+        .post_empty(&format!("/api/v1/admin/nominations/{nomination_id}/reject"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "ELECTION_NOT_NOMINATING");
+}
+
+async fn attachment_errors(server: &TestServer, admin: &mut Client) {
+    admin
+        .post_raw("/api/v1/admin/attachments", "text/plain", "hello")
+        .await
+        .assert_error(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "UNSUPPORTED_ATTACHMENT_TYPE",
+        );
+    admin
+        .post_raw("/api/v1/admin/attachments", "image/png", "hello")
+        .await
+        .assert_error(StatusCode::BAD_REQUEST, "ATTACHMENT_CONTENT_MISMATCH");
+
+    // Made-up credentials are rejected before the server waits for the announced file.
+    let response = send_upload_without_body(admin).await;
+    assert!(
+        response.starts_with("HTTP/1.1 401"),
+        "unexpected response: {response}"
+    );
+
+    server
+        .participant()
+        .await
+        .get(&format!("/api/v1/attachments/{MISSING_ID}"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "ATTACHMENT_NOT_FOUND");
+}
+
+async fn api_token_errors(admin: &mut Client) {
+    admin
+        .delete(&format!("/api/v1/admin/api-tokens/{MISSING_ID}"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "API_TOKEN_NOT_FOUND");
+}
+
+async fn speakers_errors(server: &TestServer, admin: &mut Client) {
+    let mut first = server.participant().await;
+    let mut second = server.participant().await;
+
+    first
+        .get(&format!("/api/v1/agenda/items/{MISSING_ID}/speakers"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "AGENDA_ITEM_NOT_FOUND");
+
+    let session = admin
+        .post(
+            "/api/v1/admin/agenda/sessions",
+            json!({ "title": "Debate" }),
+        )
+        .await
+        .ok_json();
+    let agenda_item = admin
+        .post(
+            "/api/v1/admin/agenda/items",
+            json!({ "sessionId": session["id"], "number": "2", "title": "Budget" }),
+        )
+        .await
+        .ok_json();
+    let speakers_path = format!("/api/v1/agenda/items/{}/speakers", agenda_item["id"]);
+
+    let speaker_request = first.post(&speakers_path, json!({})).await.ok_json();
+    let speaker_request_id = &speaker_request["id"];
+    first
+        .post(&speakers_path, json!({}))
+        .await
+        .assert_error(StatusCode::CONFLICT, "ALREADY_REQUESTED_TO_SPEAK");
+    second
+        .delete(&format!("/api/v1/speaker-requests/{speaker_request_id}"))
+        .await
+        .assert_error(StatusCode::FORBIDDEN, "NOT_OWN_SPEAKER_REQUEST");
+    second
+        .delete(&format!("/api/v1/speaker-requests/{MISSING_ID}"))
+        .await
+        .assert_error(StatusCode::NOT_FOUND, "SPEAKER_REQUEST_NOT_FOUND");
+
+    admin
+        .put(
+            &format!(
+                "/api/v1/admin/agenda/items/{}/speakers/order",
+                agenda_item["id"]
+            ),
+            json!({ "speakerRequestIds": [] }),
+        )
+        .await
+        .assert_error(StatusCode::BAD_REQUEST, "INVALID_SPEAKER_ORDER");
+    admin
+        .post_empty(&format!(
+            "/api/v1/admin/speaker-requests/{speaker_request_id}/finish"
+        ))
+        .await
+        .assert_error(StatusCode::CONFLICT, "NOT_SPEAKING");
+    admin
+        .post(
+            &format!("/api/v1/admin/speaker-requests/{speaker_request_id}/call"),
+            json!({}),
+        )
+        .await
+        .assert_status(StatusCode::OK);
+    first
+        .delete(&format!("/api/v1/speaker-requests/{speaker_request_id}"))
+        .await
+        .assert_error(StatusCode::CONFLICT, "SPEAKER_REQUEST_NOT_WAITING");
+}
+
+/// Announces a longer body than it sends, then stops writing. Answers with the raw response.
+async fn send_truncated_body(anonymous: &Client) -> String {
+    let mut stream = TcpStream::connect(anonymous.address())
+        .await
+        .expect("connecting to the test server");
+    let request = format!(
+        "POST /api/v1/participants HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: 100\r\n\r\n{{\"password\"",
+        anonymous.host()
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    stream.shutdown().await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    response
+}
+
+async fn send_upload_without_body(client: &Client) -> String {
+    let mut stream = TcpStream::connect(client.address())
+        .await
+        .expect("connecting to the test server");
+    let request = format!(
+        "POST /api/v1/admin/attachments HTTP/1.1\r\nHost: {}\r\nCookie: coco_admin_session_id=0; coco_admin_token=made-up\r\nContent-Type: image/png\r\nContent-Length: 5242880\r\n\r\n",
+        client.host()
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = vec![0; 4096];
+    let len = timeout(Duration::from_secs(5), stream.read(&mut response))
+        .await
+        .expect("an answer without the body being sent")
+        .unwrap();
+
+    String::from_utf8_lossy(&response[..len]).into_owned()
+}
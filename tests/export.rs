@@ -0,0 +1,71 @@
+//! Checks that the exports are safe to open, even with names entered by participants, and
+//! list every closed election.
+
+mod common;
+
+use hyper::StatusCode;
+use serde_json::json;
+
+use common::{TestServer, create_election};
+
+#[tokio::test]
+async fn csv_export_neutralises_formulas() {
+    let server = TestServer::start().await;
+    let mut admin = server.admin().await;
+    let mut participant = server.participant().await;
+
+    let election_id = create_election(
+        &mut admin,
+        json!({ "name": "Chair", "ballotItems": [], "nominations": {} }),
+    )
+    .await;
+
+    let nomination = participant
+        .post(
+            &format!("/api/v1/elections/{election_id}/nominations"),
+            json!({
+                "candidateName": "=HYPERLINK(\"https://example.com\",\"Click\")",
+                "candidateParticipantId": participant.participant_id(),
+            }),
+        )
+        .await;
+    nomination.assert_status(StatusCode::CREATED);
+    let nomination_id = nomination.json()["id"].clone();
+
+    for path in [
+        format!("/api/v1/admin/nominations/{nomination_id}/approve"),
+        format!("/api/v1/admin/elections/{election_id}/open"),
+        format!("/api/v1/admin/elections/{election_id}/close"),
+    ] {
+        admin.post_empty(&path).await.assert_status(StatusCode::OK);
+    }
+
+    let csv = admin.get("/api/v1/admin/exports/results.csv").await;
+    csv.assert_status(StatusCode::OK);
+    let csv = csv.text();
+    assert!(csv.contains(r#","'=HYPERLINK(""https://example.com"",""Click"")","#));
+    assert!(csv.lines().flat_map(|line| line.split(',')).all(|field| {
+        !field
+            .trim_start_matches('"')
+            .starts_with(['=', '+', '-', '@'])
+    }));
+}
+
+#[tokio::test]
+async fn csv_export_lists_elections_without_ballot_items() {
+    let server = TestServer::start().await;
+    let mut admin = server.admin().await;
+
+    let election_id =
+        create_election(&mut admin, json!({ "name": "Empty", "ballotItems": [] })).await;
+    admin
+        .post_empty(&format!("/api/v1/admin/elections/{election_id}/close"))
+        .await
+        .assert_status(StatusCode::OK);
+
+    let csv = admin.get("/api/v1/admin/exports/results.csv").await.text();
+    let rows: Vec<_> = csv.lines().skip(1).collect();
+    assert_eq!(rows.len(), 1, "unexpected CSV: {csv}");
+    assert!(rows[0].starts_with(&format!("{election_id},Empty,")));
+    assert!(rows[0].ends_with(",,,,,,No votes were cast"));
+}
@@ -0,0 +1,124 @@
+//! Checks that error messages follow the language of the request and that elections and ballot
+//! items carry the translations an admin entered.
+
+mod common;
+
+use hyper::{HeaderMap, Method, StatusCode, header::ACCEPT_LANGUAGE};
+use serde_json::{Value, json};
+
+use common::{Client, TestServer, create_election};
+
+async fn get_in_language(client: &mut Client, path: &str, accept_language: &str) -> Value {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT_LANGUAGE, accept_language.parse().unwrap());
+
+    client.send(Method::GET, path, None, headers).await.json()
+}
+
+#[tokio::test]
+async fn error_messages_follow_the_requested_language() {
+    let server = TestServer::start().await;
+    let mut anonymous = server.client();
+
+    let error = anonymous.get("/api/v1/elections").await;
+    error.assert_error(StatusCode::UNAUTHORIZED, "NOT_LOGGED_IN");
+    assert_eq!(error.json()["message"], "You need to log in first.");
+
+    let error = get_in_language(
+        &mut anonymous,
+        "/api/v1/elections",
+        "fr-CH, de;q=0.8, en;q=0.5",
+    )
+    .await;
+    assert_eq!(error["code"], "NOT_LOGGED_IN");
+    assert_eq!(error["message"], "Bitte melde dich zuerst an.");
+
+    let error = get_in_language(&mut anonymous, "/api/v1/elections", "de;q=0.5, en").await;
+    assert_eq!(error["message"], "You need to log in first.");
+
+    let error = get_in_language(&mut anonymous, "/api/v1/unknown", "de-AT").await;
+    assert_eq!(error["code"], "NOT_FOUND");
+    assert_eq!(error["message"], "CoCo kennt diese Seite nicht.");
+
+    // The language switcher of the frontend overrides the browser's languages.
+    anonymous.set_cookie("coco_locale", "de");
+    let error = get_in_language(&mut anonymous, "/api/v1/elections", "en-US").await;
+    assert_eq!(error["message"], "Bitte melde dich zuerst an.");
+}
+
+#[tokio::test]
+async fn elections_carry_translations() {
+    let server = TestServer::start().await;
+    let mut admin = server.admin().await;
+    let mut participant = server.participant().await;
+
+    let election_id = create_election(
+        &mut admin,
+        json!({
+            "name": "Board",
+            "translations": { "de": { "name": "Vorstand" } },
+            "ballotItems": [
+                {
+                    "name": "Alice",
+                    "description": "Treasurer for two years.",
+                    "translations": {
+                        "de": { "name": "Alice", "description": "Seit zwei Jahren Kassenwartin." },
+                    },
+                },
+                "Bob",
+            ],
+        }),
+    )
+    .await;
+
+    let election = participant
+        .get(&format!("/api/v1/elections/{election_id}"))
+        .await
+        .ok_json();
+    assert_eq!(
+        election["translations"],
+        json!({ "de": { "name": "Vorstand" } })
+    );
+    assert_eq!(
+        election["ballotItemsById"]["0"]["translations"]["de"]["description"],
+        "Seit zwei Jahren Kassenwartin."
+    );
+    assert_eq!(election["ballotItemsById"]["1"]["translations"], json!({}));
+
+    let presentation = participant.get("/api/v1/presentation").await.ok_json();
+    assert_eq!(
+        presentation["election"]["translations"]["de"]["name"],
+        "Vorstand"
+    );
+
+    let motion_id = create_election(
+        &mut admin,
+        json!({
+            "name": "Meetings",
+            "ballotItems": [],
+            "motion": { "kind": "main", "text": "We meet on Mondays." },
+        }),
+    )
+    .await;
+    let motion = participant
+        .get(&format!("/api/v1/elections/{motion_id}"))
+        .await
+        .ok_json();
+    assert_eq!(motion["ballotItemsById"]["0"]["name"], "Yes");
+    assert_eq!(
+        motion["ballotItemsById"]["0"]["translations"]["de"]["name"],
+        "Ja"
+    );
+    assert_eq!(
+        motion["ballotItemsById"]["1"]["translations"]["de"]["name"],
+        "Nein"
+    );
+
+    admin
+        .post(
+            "/api/v1/admin/elections",
+            json!({ "name": "Broken", "translations": { "fr": { "name": "Cassé" } }, "ballotItems": [] }),
+        )
+        .await
+        .assert_error(StatusCode::BAD_REQUEST, "BODY_MALFORMED");
+}